@@ -3,9 +3,9 @@
 //! This module intentionally keeps business logic minimal and focuses on
 //! deterministic data access operations.
 
-use sqlx::{Row, SqlitePool};
+use sqlx::{Row, Sqlite, SqlitePool, Transaction};
 
-use crate::models::Game;
+use crate::models::{Game, GameSource, PlaySession};
 
 /// Fetch all games ordered by most recently added.
 pub async fn get_all_games(pool: &SqlitePool) -> Result<Vec<Game>, sqlx::Error> {
@@ -36,60 +36,549 @@ pub async fn get_games_by_status(
 }
 
 /// Insert a game and return the newly assigned SQLite row id.
+///
+/// `playtime_hours` and `last_played` are stored as given so launcher-reported
+/// playtime survives the first import. A game with a `source` and `source_id`
+/// also gets its first `game_sources` row.
 pub async fn insert_game(pool: &SqlitePool, game: &Game) -> Result<i64, sqlx::Error> {
     let result = sqlx::query(
         "INSERT INTO games (title, platform, status, description, genre, release_year, \
-         icon_path, cover_url, rawg_id, exe_path, source, source_id, install_path) \
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+         icon_path, cover_url, rawg_id, exe_path, playtime_hours, last_played, \
+         source, source_id, install_path, normalized_title) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&game.title)
     .bind(&game.platform)
     .bind(&game.status)
     .bind(&game.description)
     .bind(&game.genre)
-    .bind(&game.release_year)
+    .bind(game.release_year)
     .bind(&game.icon_path)
     .bind(&game.cover_url)
-    .bind(&game.rawg_id)
+    .bind(game.rawg_id)
     .bind(&game.exe_path)
+    .bind(game.playtime_hours)
+    .bind(&game.last_played)
     .bind(&game.source)
     .bind(&game.source_id)
     .bind(&game.install_path)
+    .bind(normalize_title(&game.title))
     .execute(pool)
     .await?;
 
-    Ok(result.last_insert_rowid())
+    let id = result.last_insert_rowid();
+    if game.source.is_some() && game.source_id.is_some() {
+        insert_game_source(pool, id, game).await?;
+    }
+    Ok(id)
 }
 
-/// Upsert by `(source, source_id)` and return the affected game id.
-///
-/// Existing rows are updated with latest title/install/executable path so
-/// repeated index runs refresh metadata instead of creating duplicates.
-pub async fn upsert_game_by_source(pool: &SqlitePool, game: &Game) -> Result<i64, sqlx::Error> {
-    // Check if game already exists by source + source_id
-    let existing = sqlx::query_as::<_, Game>(
-        "SELECT * FROM games WHERE source = ? AND source_id = ?",
+/// Add `game`'s source to the game with id `game_id`.
+async fn insert_game_source(
+    pool: &SqlitePool,
+    game_id: i64,
+    game: &Game,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO game_sources \
+         (game_id, source, source_id, install_path, exe_path, installed, playtime_hours) \
+         VALUES (?, ?, ?, ?, ?, 1, ?)",
+    )
+    .bind(game_id)
+    .bind(&game.source)
+    .bind(&game.source_id)
+    .bind(&game.install_path)
+    .bind(&game.exe_path)
+    .bind(game.playtime_hours)
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "INSERT OR IGNORE INTO playtime_snapshots (game_source_id, playtime_hours, last_played) \
+         SELECT id, ?, ? FROM game_sources WHERE source = ? AND source_id = ?",
     )
+    .bind(game.playtime_hours)
+    .bind(&game.last_played)
     .bind(&game.source)
     .bind(&game.source_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Title reduced to lowercase words, used to match one game across
+/// launchers: `DOOM®`, `Doom` and `doom` all become `doom`, and
+/// `Hitman: Absolution` matches `Hitman - Absolution`.
+pub fn normalize_title(title: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .filter(|c| !matches!(c, '\'' | '\u{2019}'))
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    cleaned
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Fill `normalized_title` on games stored without one, such as those from
+/// before the column existed. Returns the number of games updated.
+pub async fn fill_normalized_titles(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+    let rows: Vec<(i32, String)> =
+        sqlx::query_as("SELECT id, title FROM games WHERE normalized_title IS NULL")
+            .fetch_all(pool)
+            .await?;
+
+    let mut tx = pool.begin().await?;
+    for (id, title) in &rows {
+        sqlx::query("UPDATE games SET normalized_title = ? WHERE id = ?")
+            .bind(normalize_title(title))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(rows.len() as u64)
+}
+
+/// Existing game a new source of `game` should attach to: same `rawg_id`,
+/// else same normalized title on the same platform.
+///
+/// Indexers don't report a `rawg_id`, so the first rule only applies when
+/// `game` was created by hand with RAWG details; launcher entries always
+/// match by title.
+pub async fn find_game_for_source(
+    pool: &SqlitePool,
+    game: &Game,
+) -> Result<Option<Game>, sqlx::Error> {
+    if let Some(rawg_id) = game.rawg_id {
+        let by_rawg = sqlx::query_as::<_, Game>(
+            "SELECT * FROM games WHERE rawg_id = ? ORDER BY id LIMIT 1",
+        )
+        .bind(rawg_id)
+        .fetch_optional(pool)
+        .await?;
+        if by_rawg.is_some() {
+            return Ok(by_rawg);
+        }
+    }
+
+    sqlx::query_as::<_, Game>(
+        "SELECT * FROM games WHERE normalized_title = ? AND platform = ? ORDER BY id LIMIT 1",
+    )
+    .bind(normalize_title(&game.title))
+    .bind(&game.platform)
     .fetch_optional(pool)
+    .await
+}
+
+/// Upsert by `(source, source_id)` and return the game id the source
+/// belongs to.
+///
+/// A known source refreshes its install/executable path. An unknown one is
+/// attached to the game found by [`find_game_for_source`], or inserted as a
+/// new game. Title and paths on the `games` row follow its primary source
+/// only; an executable the user picked (`exe_path_locked`) is kept, and a
+/// game that was marked uninstalled becomes installed again.
+/// Launcher playtime and last-played only ever move forward: a smaller value
+/// (or none at all) never overwrites what is stored. A new source brings its
+/// whole playtime along; growth on a known one is logged as an inferred
+/// session (see [`infer_launcher_session`]), which adds to the game's
+/// playtime.
+pub async fn upsert_game_by_source(pool: &SqlitePool, game: &Game) -> Result<i64, sqlx::Error> {
+    let (Some(source), Some(source_id)) = (&game.source, &game.source_id) else {
+        return insert_game(pool, game).await;
+    };
+
+    let existing = sqlx::query_as::<_, GameSource>(
+        "SELECT * FROM game_sources WHERE source = ? AND source_id = ?",
+    )
+    .bind(source)
+    .bind(source_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let (game_id, playtime_gain) = match existing {
+        Some(row) => {
+            sqlx::query(
+                "UPDATE game_sources SET install_path = ?, exe_path = ?, installed = 1, \
+                 playtime_hours = MAX(playtime_hours, ?) WHERE id = ?",
+            )
+            .bind(&game.install_path)
+            .bind(&game.exe_path)
+            .bind(game.playtime_hours)
+            .bind(row.id)
+            .execute(pool)
+            .await?;
+            infer_launcher_session(pool, row.id, row.game_id, game).await?;
+            (row.game_id as i64, 0.0)
+        }
+        None => match find_game_for_source(pool, game).await? {
+            Some(owner) => {
+                insert_game_source(pool, owner.id as i64, game).await?;
+                (owner.id as i64, game.playtime_hours.max(0.0))
+            }
+            None => return insert_game(pool, game).await,
+        },
+    };
+
+    // Take over as primary when this is the primary source, or the game has
+    // none or only an uninstalled one
+    sqlx::query(
+        "UPDATE games SET source = ?, source_id = ?, install_path = ?, \
+         exe_path = CASE WHEN exe_path_locked THEN exe_path ELSE ? END, title = ?, \
+         normalized_title = ? \
+         WHERE id = ? AND (source_id IS NULL OR installed = 0 \
+         OR (source = ? AND source_id = ?))",
+    )
+    .bind(source)
+    .bind(source_id)
+    .bind(&game.install_path)
+    .bind(&game.exe_path)
+    .bind(&game.title)
+    .bind(normalize_title(&game.title))
+    .bind(game_id)
+    .bind(source)
+    .bind(source_id)
+    .execute(pool)
     .await?;
 
-    if let Some(existing) = existing {
-        // Update install path and exe path if they changed
+    sqlx::query(
+        "UPDATE games SET playtime_hours = COALESCE(playtime_hours, 0) + ?, \
+         last_played = COALESCE(MAX(last_played, ?), last_played, ?), \
+         installed = 1, uninstalled_at = NULL \
+         WHERE id = ?",
+    )
+    .bind(playtime_gain)
+    .bind(&game.last_played)
+    .bind(&game.last_played)
+    .bind(game_id)
+    .execute(pool)
+    .await?;
+
+    Ok(game_id)
+}
+
+/// Compare the playtime a launcher reports for source row `source_row_id`
+/// with its snapshot and log the growth as an inferred session dated at the
+/// launcher's last-played time.
+///
+/// Minutes the app recorded itself for the game since the snapshot (tracked
+/// or logged sessions) are already counted, so only the rest is inferred.
+/// Growth under a minute is left in the snapshot to add up with the next run.
+async fn infer_launcher_session(
+    pool: &SqlitePool,
+    source_row_id: i32,
+    game_id: i32,
+    game: &Game,
+) -> Result<(), sqlx::Error> {
+    let snapshot: Option<(f32, String)> = sqlx::query_as(
+        "SELECT playtime_hours, taken_at FROM playtime_snapshots WHERE game_source_id = ?",
+    )
+    .bind(source_row_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some((baseline, taken_at)) = snapshot else {
         sqlx::query(
-            "UPDATE games SET install_path = ?, exe_path = ?, title = ? WHERE id = ?",
+            "INSERT INTO playtime_snapshots (game_source_id, playtime_hours, last_played) \
+             VALUES (?, ?, ?)",
         )
-        .bind(&game.install_path)
-        .bind(&game.exe_path)
-        .bind(&game.title)
-        .bind(existing.id)
+        .bind(source_row_id)
+        .bind(game.playtime_hours)
+        .bind(&game.last_played)
+        .execute(pool)
+        .await?;
+        return Ok(());
+    };
+
+    let grown_minutes = ((game.playtime_hours - baseline) * 60.0).round() as i32;
+    if grown_minutes <= 0 {
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await?;
+    let seen_minutes: i32 = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(duration_minutes), 0) FROM play_sessions \
+         WHERE game_id = ? AND inferred = 0 AND COALESCE(last_seen, session_date) >= ?",
+    )
+    .bind(game_id)
+    .bind(&taken_at)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let minutes = grown_minutes - seen_minutes;
+    if minutes > 0 {
+        let id = sqlx::query(
+            "INSERT INTO play_sessions (game_id, session_date, duration_minutes, inferred) \
+             VALUES (?, COALESCE(?, CURRENT_TIMESTAMP), ?, 1)",
+        )
+        .bind(game_id)
+        .bind(&game.last_played)
+        .bind(minutes)
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+        let date: String =
+            sqlx::query_scalar("SELECT session_date FROM play_sessions WHERE id = ?")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?;
+        add_session_time(&mut tx, game_id, minutes, Some(&date)).await?;
+    }
+
+    sqlx::query(
+        "UPDATE playtime_snapshots SET playtime_hours = ?, \
+         last_played = COALESCE(?, last_played), taken_at = CURRENT_TIMESTAMP \
+         WHERE game_source_id = ?",
+    )
+    .bind(game.playtime_hours)
+    .bind(&game.last_played)
+    .bind(source_row_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Fetch the sources of one game, oldest first.
+pub async fn get_game_sources(
+    pool: &SqlitePool,
+    game_id: i32,
+) -> Result<Vec<GameSource>, sqlx::Error> {
+    sqlx::query_as::<_, GameSource>("SELECT * FROM game_sources WHERE game_id = ? ORDER BY id")
+        .bind(game_id)
+        .fetch_all(pool)
+        .await
+}
+
+/// Fetch every source row in the library.
+pub async fn get_all_game_sources(pool: &SqlitePool) -> Result<Vec<GameSource>, sqlx::Error> {
+    sqlx::query_as::<_, GameSource>("SELECT * FROM game_sources ORDER BY id")
+        .fetch_all(pool)
+        .await
+}
+
+/// Mark installed sources of `source` whose `source_id` is not in `present`
+/// as uninstalled. Returns the number of games newly marked uninstalled,
+/// i.e. those left without any installed source.
+///
+/// Only the install state changes; status, playtime and sessions are kept so
+/// a reinstall picks up where the user left off.
+pub async fn mark_missing_uninstalled(
+    pool: &SqlitePool,
+    source: &str,
+    present: &[String],
+) -> Result<u64, sqlx::Error> {
+    let present = serde_json::to_string(present).expect("string list serializes");
+    let missing = "source = ? AND installed = 1 \
+                   AND source_id NOT IN (SELECT value FROM json_each(?))";
+
+    let game_ids: Vec<i32> = sqlx::query_scalar(&format!(
+        "SELECT DISTINCT game_id FROM game_sources WHERE {}",
+        missing
+    ))
+    .bind(source)
+    .bind(&present)
+    .fetch_all(pool)
+    .await?;
+    sqlx::query(&format!("UPDATE game_sources SET installed = 0 WHERE {}", missing))
+        .bind(source)
+        .bind(&present)
         .execute(pool)
         .await?;
-        Ok(existing.id as i64)
-    } else {
-        insert_game(pool, game).await
+
+    let mut marked = 0;
+    for id in game_ids {
+        if refresh_primary_source(pool, id).await? {
+            marked += 1;
+        }
+    }
+    Ok(marked)
+}
+
+/// Mark one source as uninstalled. Returns whether its game was left
+/// without any installed source.
+pub async fn mark_source_uninstalled(
+    pool: &SqlitePool,
+    source: &str,
+    source_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let game_id: Option<i32> = sqlx::query_scalar(
+        "UPDATE game_sources SET installed = 0 WHERE source = ? AND source_id = ? \
+         RETURNING game_id",
+    )
+    .bind(source)
+    .bind(source_id)
+    .fetch_optional(pool)
+    .await?;
+    match game_id {
+        Some(id) => refresh_primary_source(pool, id).await,
+        None => Ok(false),
+    }
+}
+
+/// Point a game's primary source fields at one of its `game_sources` rows.
+///
+/// The current primary is kept while it is installed; otherwise any
+/// installed source takes over, and the game is uninstalled when none is
+/// left. Returns whether the game went from installed to uninstalled.
+pub async fn refresh_primary_source(pool: &SqlitePool, game_id: i32) -> Result<bool, sqlx::Error> {
+    let game = get_game_by_id(pool, game_id).await?;
+    let sources = get_game_sources(pool, game_id).await?;
+
+    let primary = sources.iter().find(|s| {
+        game.source.as_deref() == Some(s.source.as_str())
+            && game.source_id.as_deref() == Some(s.source_id.as_str())
+    });
+    if primary.is_some_and(|p| p.installed) {
+        return Ok(false);
     }
+    let Some(pick) = sources.iter().find(|s| s.installed).or(primary).or(sources.first()) else {
+        // Manual games have no sources to follow
+        return Ok(false);
+    };
+
+    sqlx::query(
+        "UPDATE games SET source = ?, source_id = ?, install_path = ?, \
+         exe_path = CASE WHEN exe_path_locked THEN exe_path ELSE ? END, installed = ?, \
+         uninstalled_at = CASE WHEN ? THEN NULL \
+         ELSE COALESCE(uninstalled_at, CURRENT_TIMESTAMP) END \
+         WHERE id = ?",
+    )
+    .bind(&pick.source)
+    .bind(&pick.source_id)
+    .bind(&pick.install_path)
+    .bind(&pick.exe_path)
+    .bind(pick.installed)
+    .bind(pick.installed)
+    .bind(game_id)
+    .execute(pool)
+    .await?;
+
+    Ok(game.installed && !pick.installed)
+}
+
+/// Merge game `from` into game `into`: sources and play sessions move over,
+/// playtime adds up, last-played keeps the later value and metadata missing
+/// on `into` is filled from `from`. `from` is deleted afterwards.
+pub async fn merge_games(pool: &SqlitePool, into: i32, from: i32) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("UPDATE game_sources SET game_id = ? WHERE game_id = ?")
+        .bind(into)
+        .bind(from)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE play_sessions SET game_id = ? WHERE game_id = ?")
+        .bind(into)
+        .bind(from)
+        .execute(&mut *tx)
+        .await?;
+
+    let merged = sqlx::query(
+        "UPDATE games AS g SET \
+         playtime_hours = COALESCE(g.playtime_hours, 0) + COALESCE(f.playtime_hours, 0), \
+         last_played = COALESCE(MAX(g.last_played, f.last_played), g.last_played, f.last_played), \
+         description = COALESCE(g.description, f.description), \
+         genre = COALESCE(g.genre, f.genre), \
+         release_year = COALESCE(g.release_year, f.release_year), \
+         icon_path = COALESCE(g.icon_path, f.icon_path), \
+         cover_url = COALESCE(g.cover_url, f.cover_url), \
+         rawg_id = COALESCE(g.rawg_id, f.rawg_id), \
+         rating = COALESCE(g.rating, f.rating), \
+         launch_override = COALESCE(g.launch_override, f.launch_override), \
+         installed = MAX(g.installed, f.installed) \
+         FROM (SELECT * FROM games WHERE id = ?) AS f \
+         WHERE g.id = ?",
+    )
+    .bind(from)
+    .bind(into)
+    .execute(&mut *tx)
+    .await?;
+    if merged.rows_affected() == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    sqlx::query("DELETE FROM games WHERE id = ?")
+        .bind(from)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    refresh_primary_source(pool, into).await?;
+    Ok(())
+}
+
+/// Move one `game_sources` row out of its game into a new game with the
+/// same title and platform. The source's launcher playtime moves with it;
+/// play sessions stay, since they aren't tied to a source. Returns the new
+/// game's id.
+pub async fn split_game_source(pool: &SqlitePool, source_row_id: i32) -> Result<i64, sqlx::Error> {
+    let row = sqlx::query_as::<_, GameSource>("SELECT * FROM game_sources WHERE id = ?")
+        .bind(source_row_id)
+        .fetch_one(pool)
+        .await?;
+    let game = get_game_by_id(pool, row.game_id).await?;
+
+    let mut tx = pool.begin().await?;
+    let new_id = sqlx::query(
+        "INSERT INTO games (title, platform, status, exe_path, playtime_hours, \
+         source, source_id, install_path, installed, normalized_title) \
+         VALUES (?, ?, 'Backlog', ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&game.title)
+    .bind(&game.platform)
+    .bind(&row.exe_path)
+    .bind(row.playtime_hours)
+    .bind(&row.source)
+    .bind(&row.source_id)
+    .bind(&row.install_path)
+    .bind(row.installed)
+    .bind(normalize_title(&game.title))
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    sqlx::query("UPDATE game_sources SET game_id = ? WHERE id = ?")
+        .bind(new_id)
+        .bind(row.id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        "UPDATE games SET playtime_hours = MAX(COALESCE(playtime_hours, 0) - ?, 0) WHERE id = ?",
+    )
+    .bind(row.playtime_hours)
+    .bind(game.id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    refresh_primary_source(pool, game.id).await?;
+    Ok(new_id)
+}
+
+/// Set a user-chosen executable and lock it against re-indexing.
+pub async fn set_game_exe(pool: &SqlitePool, id: i32, exe_path: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE games SET exe_path = ?, exe_path_locked = 1 WHERE id = ?")
+        .bind(exe_path)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Set or clear a game's launch override.
+pub async fn set_launch_override(
+    pool: &SqlitePool,
+    id: i32,
+    launch_override: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE games SET launch_override = ? WHERE id = ?")
+        .bind(launch_override)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
 }
 
 /// Update a game's status and stamp `last_played` with current time.
@@ -115,6 +604,250 @@ pub async fn delete_game(pool: &SqlitePool, id: i32) -> Result<(), sqlx::Error>
     Ok(())
 }
 
+/// Fetch the play sessions of one game, most recent first.
+pub async fn get_play_sessions(
+    pool: &SqlitePool,
+    game_id: i32,
+) -> Result<Vec<PlaySession>, sqlx::Error> {
+    sqlx::query_as::<_, PlaySession>(
+        "SELECT * FROM play_sessions WHERE game_id = ? ORDER BY session_date DESC, id DESC",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Fetch a single play session by primary key.
+pub async fn get_play_session(pool: &SqlitePool, id: i32) -> Result<PlaySession, sqlx::Error> {
+    sqlx::query_as::<_, PlaySession>("SELECT * FROM play_sessions WHERE id = ?")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Log a play session and add its duration to the game's playtime. A
+/// missing `session_date` means now. Returns the new session id.
+pub async fn insert_play_session(
+    pool: &SqlitePool,
+    game_id: i32,
+    session_date: Option<&str>,
+    duration_minutes: i32,
+    notes: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let id = sqlx::query(
+        "INSERT INTO play_sessions (game_id, session_date, duration_minutes, notes) \
+         VALUES (?, COALESCE(?, CURRENT_TIMESTAMP), ?, ?)",
+    )
+    .bind(game_id)
+    .bind(session_date)
+    .bind(duration_minutes)
+    .bind(notes)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    let date: String = sqlx::query_scalar("SELECT session_date FROM play_sessions WHERE id = ?")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+    add_session_time(&mut tx, game_id, duration_minutes, Some(&date)).await?;
+    tx.commit().await?;
+    Ok(id)
+}
+
+/// Replace a session's date, duration and notes, shifting the game's
+/// playtime by the difference in duration.
+pub async fn update_play_session(
+    pool: &SqlitePool,
+    id: i32,
+    session_date: &str,
+    duration_minutes: i32,
+    notes: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let old = get_play_session(pool, id).await?;
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "UPDATE play_sessions SET session_date = ?, duration_minutes = ?, notes = ? WHERE id = ?",
+    )
+    .bind(session_date)
+    .bind(duration_minutes)
+    .bind(notes)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+    forget_session_date(&mut tx, old.game_id, &old.session_date).await?;
+    add_session_time(
+        &mut tx,
+        old.game_id,
+        duration_minutes - old.duration_minutes,
+        Some(session_date),
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Delete a session and take its duration off the game's playtime.
+pub async fn delete_play_session(pool: &SqlitePool, id: i32) -> Result<(), sqlx::Error> {
+    let old = get_play_session(pool, id).await?;
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM play_sessions WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    forget_session_date(&mut tx, old.game_id, &old.session_date).await?;
+    add_session_time(&mut tx, old.game_id, -old.duration_minutes, None).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Open a tracked session for a game that was just launched: the session
+/// starts now with no duration and the game moves to "Playing". Returns the
+/// session id.
+pub async fn start_play_session(pool: &SqlitePool, game_id: i32) -> Result<i64, sqlx::Error> {
+    start_play_session_at(pool, game_id, None).await
+}
+
+/// [`start_play_session`] for a game that has been running since
+/// `started_at` (`YYYY-MM-DD HH:MM:SS`); `None` means now.
+pub async fn start_play_session_at(
+    pool: &SqlitePool,
+    game_id: i32,
+    started_at: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let id = sqlx::query(
+        "INSERT INTO play_sessions (game_id, session_date, duration_minutes, active, last_seen) \
+         VALUES (?, COALESCE(?, CURRENT_TIMESTAMP), 0, 1, CURRENT_TIMESTAMP)",
+    )
+    .bind(game_id)
+    .bind(started_at)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+    sqlx::query(
+        "UPDATE games SET status = 'Playing', \
+         last_played = (SELECT session_date FROM play_sessions WHERE id = ?) \
+         WHERE id = ?",
+    )
+    .bind(id)
+    .bind(game_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(id)
+}
+
+/// Heartbeat of a tracked session: store its duration so far and add the
+/// increase to the game's playtime.
+pub async fn record_session_progress(
+    pool: &SqlitePool,
+    id: i64,
+    duration_minutes: i32,
+) -> Result<(), sqlx::Error> {
+    let old = get_play_session(pool, id as i32).await?;
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "UPDATE play_sessions SET duration_minutes = ?, last_seen = CURRENT_TIMESTAMP \
+         WHERE id = ?",
+    )
+    .bind(duration_minutes)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+    add_session_time(&mut tx, old.game_id, duration_minutes - old.duration_minutes, None).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Close a tracked session with its final duration. Sessions shorter than a
+/// minute are deleted instead. Returns the closed session, if kept.
+pub async fn finish_play_session(
+    pool: &SqlitePool,
+    id: i64,
+    duration_minutes: i32,
+) -> Result<Option<PlaySession>, sqlx::Error> {
+    if duration_minutes <= 0 {
+        delete_play_session(pool, id as i32).await?;
+        return Ok(None);
+    }
+    record_session_progress(pool, id, duration_minutes).await?;
+    sqlx::query("UPDATE play_sessions SET active = 0 WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    get_play_session(pool, id as i32).await.map(Some)
+}
+
+/// `(session id, game id)` of every tracked session still running.
+pub async fn get_active_sessions(pool: &SqlitePool) -> Result<Vec<(i64, i32)>, sqlx::Error> {
+    sqlx::query_as("SELECT id, game_id FROM play_sessions WHERE active = 1")
+        .fetch_all(pool)
+        .await
+}
+
+/// Close sessions left active by a crash or shutdown. Their duration is
+/// what the last heartbeat recorded; empty ones are deleted. Returns the
+/// number of sessions kept.
+pub async fn close_stale_sessions(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM play_sessions WHERE active = 1 AND duration_minutes <= 0")
+        .execute(&mut *tx)
+        .await?;
+    let closed = sqlx::query("UPDATE play_sessions SET active = 0 WHERE active = 1")
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    tx.commit().await?;
+    Ok(closed)
+}
+
+/// Shift a game's playtime by `minutes` (never below zero) and move
+/// `last_played` forward to `session_date` if that is later.
+async fn add_session_time(
+    tx: &mut Transaction<'_, Sqlite>,
+    game_id: i32,
+    minutes: i32,
+    session_date: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE games SET \
+         playtime_hours = MAX(COALESCE(playtime_hours, 0) + ? / 60.0, 0), \
+         last_played = COALESCE(MAX(last_played, ?), last_played, ?) \
+         WHERE id = ?",
+    )
+    .bind(minutes)
+    .bind(session_date)
+    .bind(session_date)
+    .bind(game_id)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// If `last_played` came from a session that moved or was deleted, fall
+/// back to the latest remaining session.
+async fn forget_session_date(
+    tx: &mut Transaction<'_, Sqlite>,
+    game_id: i32,
+    session_date: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE games SET last_played = \
+         (SELECT MAX(session_date) FROM play_sessions WHERE game_id = games.id) \
+         WHERE id = ? AND last_played = ?",
+    )
+    .bind(game_id)
+    .bind(session_date)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
 /// Search games by title or genre using a `%LIKE%` pattern.
 pub async fn search_games(pool: &SqlitePool, query: &str) -> Result<Vec<Game>, sqlx::Error> {
     let pattern = format!("%{}%", query);
@@ -168,3 +901,25 @@ pub async fn total_playtime(pool: &SqlitePool) -> Result<f64, sqlx::Error> {
         .await?;
     Ok(row.get("total"))
 }
+
+/// Read a raw setting value by key.
+pub async fn get_setting(pool: &SqlitePool, key: &str) -> Result<Option<String>, sqlx::Error> {
+    let row = sqlx::query("SELECT value FROM settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(|r| r.get("value")))
+}
+
+/// Insert or replace a raw setting value.
+pub async fn set_setting(pool: &SqlitePool, key: &str, value: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES (?, ?) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
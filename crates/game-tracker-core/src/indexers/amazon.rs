@@ -0,0 +1,203 @@
+//! Amazon Games auto-indexer.
+//!
+//! The Amazon Games app keeps its library in two SQLite stores under
+//! `%LOCALAPPDATA%\Amazon Games\Data\Games\Sql\`:
+//! - `GameInstallInfo.sqlite`: installed products and their install directory
+//! - `GameProductInfo.sqlite`: product metadata (title, publisher, artwork)
+//!
+//! On Linux the app runs inside a Wine prefix, so the same directory is looked
+//! up under each user profile of each discovered prefix.
+
+use crate::indexers::{exe_detect, wine, IndexerError, LauncherIndexer};
+use crate::models::DiscoveredGame;
+use rusqlite::{Connection, OpenFlags};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// [`LauncherIndexer`] for the Amazon Games app.
+pub struct AmazonIndexer {
+    /// Directories containing `GameInstallInfo.sqlite`.
+    pub sql_dirs: Vec<PathBuf>,
+}
+
+impl Default for AmazonIndexer {
+    fn default() -> Self {
+        Self {
+            sql_dirs: default_sql_dirs(),
+        }
+    }
+}
+
+impl LauncherIndexer for AmazonIndexer {
+    fn id(&self) -> &'static str {
+        "amazon"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Amazon Games"
+    }
+
+    fn is_available(&self) -> bool {
+        self.sql_dirs
+            .iter()
+            .any(|d| d.join(INSTALL_INFO_DB).is_file())
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        let mut games: Vec<DiscoveredGame> = Vec::new();
+        for dir in self.sql_dirs.iter().filter(|d| d.is_dir()) {
+            for game in scan_amazon_games_from(dir)? {
+                if !games.iter().any(|g| g.source_id == game.source_id) {
+                    games.push(game);
+                }
+            }
+        }
+        Ok(games)
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.sql_dirs.iter().filter(|d| d.is_dir()).cloned().collect()
+    }
+}
+
+const INSTALL_INFO_DB: &str = "GameInstallInfo.sqlite";
+const PRODUCT_INFO_DB: &str = "GameProductInfo.sqlite";
+
+/// Store directory relative to a user's `AppData\Local`.
+const SQL_SUBDIR: &str = "Amazon Games/Data/Games/Sql";
+
+/// Native Windows store directory plus one per user of each Wine prefix.
+fn default_sql_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if cfg!(target_os = "windows") {
+        if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+            dirs.push(PathBuf::from(local).join(SQL_SUBDIR));
+        }
+    }
+    for prefix in wine::discover_prefixes() {
+        let Ok(users) = std::fs::read_dir(prefix.join("drive_c/users")) else {
+            continue;
+        };
+        for user in users.flatten() {
+            let dir = user.path().join("AppData/Local").join(SQL_SUBDIR);
+            if dir.is_dir() {
+                dirs.push(dir);
+            }
+        }
+    }
+    dirs
+}
+
+/// Scan the default Amazon Games stores and return discovered games.
+pub fn scan_amazon_games() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    AmazonIndexer::default().scan()
+}
+
+/// Scan the stores in one `Sql` directory (useful for testing).
+///
+/// If the directory is inside a Wine prefix, install paths are translated to
+/// host paths through that prefix.
+pub fn scan_amazon_games_from(sql_dir: &Path) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games = Vec::new();
+    let install_db = sql_dir.join(INSTALL_INFO_DB);
+
+    if !install_db.is_file() {
+        tracing::warn!("Amazon Games install database not found: {:?}", install_db);
+        return Ok(games);
+    }
+    let prefix = wine::prefix_of(sql_dir);
+    let titles = read_product_titles(&sql_dir.join(PRODUCT_INFO_DB));
+
+    let conn = Connection::open_with_flags(&install_db, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare(
+        "SELECT Id, ProductTitle, InstallDirectory FROM DbSet \
+         WHERE Installed = 1 AND InstallDirectory IS NOT NULL",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+
+    for row in rows {
+        let (product_id, install_title, install_dir) = row?;
+        // The product store has the storefront title; the install store's
+        // copy can be stale
+        let title = match titles.get(&product_id).or(install_title.as_ref()) {
+            Some(t) if !t.is_empty() => t.clone(),
+            _ => continue,
+        };
+
+        let install_path = prefix
+            .as_deref()
+            .and_then(|p| wine::windows_to_host(p, &install_dir))
+            .unwrap_or_else(|| PathBuf::from(&install_dir));
+        let exe_path = main_exe(&install_path, &title);
+
+        games.push(DiscoveredGame {
+            title,
+            platform: "PC".to_string(),
+            exe_path,
+            install_path: Some(install_path.to_string_lossy().to_string()),
+            source: "amazon".to_string(),
+            source_id: product_id,
+            playtime_hours: None,
+            last_played: None,
+        });
+    }
+
+    Ok(games)
+}
+
+/// Product id to title from `GameProductInfo.sqlite`; empty if unreadable.
+fn read_product_titles(db_path: &Path) -> HashMap<String, String> {
+    let read = || -> Result<HashMap<String, String>, rusqlite::Error> {
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut stmt = conn.prepare(
+            "SELECT ProductIdStr, ProductTitle FROM DbSet \
+             WHERE ProductIdStr IS NOT NULL AND ProductTitle IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    };
+
+    if !db_path.is_file() {
+        return HashMap::new();
+    }
+    read().unwrap_or_else(|e| {
+        tracing::warn!("Failed to read Amazon product info {:?}: {}", db_path, e);
+        HashMap::new()
+    })
+}
+
+/// Launch configuration each Amazon game ships in `fuel.json`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Fuel {
+    main: Option<FuelMain>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct FuelMain {
+    command: Option<String>,
+}
+
+/// Main executable: `fuel.json`'s `Main.Command` when it exists on disk,
+/// otherwise the best [`exe_detect`] candidate.
+pub fn main_exe(install_dir: &Path, title: &str) -> Option<String> {
+    let from_fuel = std::fs::read_to_string(install_dir.join("fuel.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str::<Fuel>(&json).ok())
+        .and_then(|fuel| fuel.main?.command)
+        .map(|command| install_dir.join(command.replace('\\', "/")))
+        .filter(|exe| exe.is_file());
+
+    match from_fuel {
+        Some(exe) => Some(exe.to_string_lossy().to_string()),
+        None => exe_detect::find_main_exe(install_dir, title),
+    }
+}
@@ -0,0 +1,334 @@
+//! Battle.net auto-indexer.
+//!
+//! The Battle.net agent records installed products in
+//! `C:\ProgramData\Battle.net\Agent\product.db`, a protobuf `Database`
+//! message. Only the fields we need are decoded:
+//!
+//! ```text
+//! Database            { repeated ProductInstall product_install = 1; }
+//! ProductInstall      { string uid = 1; string product_code = 2;
+//!                       UserSettings settings = 3;
+//!                       CachedProductState cached_product_state = 4; }
+//! UserSettings        { string install_path = 1; }
+//! CachedProductState  { BaseProductState base_product_state = 1; }
+//! BaseProductState    { bool installed = 1; }
+//! ```
+//!
+//! product.db has no display names, so product codes are mapped to titles
+//! with [`DEFAULT_PRODUCT_TITLES`], which settings can extend or override.
+//! Products with an unknown code (the agent itself, the app) are skipped.
+
+use crate::indexers::{exe_detect, wine, IndexerError, LauncherIndexer};
+use crate::models::DiscoveredGame;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// [`LauncherIndexer`] for Battle.net.
+pub struct BattleNetIndexer {
+    /// `product.db` files to read.
+    pub db_paths: Vec<PathBuf>,
+    /// Product code to title; entries override [`DEFAULT_PRODUCT_TITLES`].
+    pub product_titles: HashMap<String, String>,
+}
+
+impl BattleNetIndexer {
+    /// Default `product.db` locations with `overrides` applied on top of the
+    /// built-in title table.
+    pub fn new(overrides: &HashMap<String, String>) -> Self {
+        Self {
+            db_paths: default_db_paths(),
+            product_titles: product_titles(overrides),
+        }
+    }
+}
+
+impl Default for BattleNetIndexer {
+    fn default() -> Self {
+        Self::new(&HashMap::new())
+    }
+}
+
+impl LauncherIndexer for BattleNetIndexer {
+    fn id(&self) -> &'static str {
+        "battlenet"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Battle.net"
+    }
+
+    fn is_available(&self) -> bool {
+        self.db_paths.iter().any(|p| p.is_file())
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        let mut games: Vec<DiscoveredGame> = Vec::new();
+        for path in self.db_paths.iter().filter(|p| p.is_file()) {
+            for game in scan_battlenet_games_from(path, &self.product_titles)? {
+                if !games.iter().any(|g| g.source_id == game.source_id) {
+                    games.push(game);
+                }
+            }
+        }
+        Ok(games)
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.db_paths
+            .iter()
+            .filter_map(|p| p.parent())
+            .filter(|d| d.is_dir())
+            .map(Path::to_path_buf)
+            .collect()
+    }
+}
+
+/// Built-in product code to title table.
+pub const DEFAULT_PRODUCT_TITLES: &[(&str, &str)] = &[
+    ("wow", "World of Warcraft"),
+    ("wow_classic", "World of Warcraft Classic"),
+    ("wow_classic_era", "World of Warcraft Classic Era"),
+    ("d3", "Diablo III"),
+    ("osi", "Diablo II: Resurrected"),
+    ("fenris", "Diablo IV"),
+    ("anbs", "Diablo Immortal"),
+    ("pro", "Overwatch 2"),
+    ("hs_beta", "Hearthstone"),
+    ("hero", "Heroes of the Storm"),
+    ("s1", "StarCraft Remastered"),
+    ("s2", "StarCraft II"),
+    ("w3", "Warcraft III: Reforged"),
+    ("rtro", "Blizzard Arcade Collection"),
+    ("wlby", "Crash Bandicoot 4: It's About Time"),
+    ("viper", "Call of Duty: Black Ops 4"),
+    ("odin", "Call of Duty: Modern Warfare"),
+    ("lazr", "Call of Duty: Modern Warfare 2 Campaign Remastered"),
+    ("zeus", "Call of Duty: Black Ops Cold War"),
+    ("fore", "Call of Duty: Vanguard"),
+    ("auks", "Call of Duty"),
+];
+
+/// [`DEFAULT_PRODUCT_TITLES`] with `overrides` applied; codes are lowercase.
+pub fn product_titles(overrides: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut titles: HashMap<String, String> = DEFAULT_PRODUCT_TITLES
+        .iter()
+        .map(|(code, title)| (code.to_string(), title.to_string()))
+        .collect();
+    for (code, title) in overrides {
+        titles.insert(code.trim().to_lowercase(), title.clone());
+    }
+    titles
+}
+
+/// `product.db` relative to the system drive.
+const PRODUCT_DB_SUBPATH: &str = "ProgramData/Battle.net/Agent/product.db";
+
+/// Native Windows location plus one per discovered Wine prefix.
+fn default_db_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if cfg!(target_os = "windows") {
+        paths.push(PathBuf::from(r"C:\ProgramData\Battle.net\Agent\product.db"));
+    }
+    paths.extend(
+        wine::discover_prefixes()
+            .into_iter()
+            .map(|prefix| prefix.join("drive_c").join(PRODUCT_DB_SUBPATH)),
+    );
+    paths
+}
+
+/// Scan the default Battle.net locations and return discovered games.
+pub fn scan_battlenet_games() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    BattleNetIndexer::default().scan()
+}
+
+/// Scan a specific `product.db` file (useful for testing).
+///
+/// If the file is inside a Wine prefix, install paths are translated to host
+/// paths through that prefix.
+pub fn scan_battlenet_games_from(
+    db_path: &Path,
+    titles: &HashMap<String, String>,
+) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games = Vec::new();
+
+    if !db_path.is_file() {
+        tracing::warn!("Battle.net product.db not found: {:?}", db_path);
+        return Ok(games);
+    }
+    let prefix = wine::prefix_of(db_path);
+
+    for install in parse_product_db(&std::fs::read(db_path)?)? {
+        if !install.installed {
+            continue;
+        }
+        let Some(title) = titles.get(&install.product_code.to_lowercase()) else {
+            tracing::debug!("Skipping unknown Battle.net product {}", install.product_code);
+            continue;
+        };
+        if install.install_path.is_empty() {
+            continue;
+        }
+
+        let install_path = prefix
+            .as_deref()
+            .and_then(|p| wine::windows_to_host(p, &install.install_path))
+            .unwrap_or_else(|| PathBuf::from(&install.install_path));
+        let exe_path = exe_detect::find_main_exe(&install_path, title);
+
+        games.push(DiscoveredGame {
+            title: title.clone(),
+            platform: "PC".to_string(),
+            exe_path,
+            install_path: Some(install_path.to_string_lossy().to_string()),
+            source: "battlenet".to_string(),
+            // `uid` tells apart several installs of one product (e.g. PTR)
+            source_id: install.uid,
+            playtime_hours: None,
+            last_played: None,
+        });
+    }
+
+    Ok(games)
+}
+
+/// One `ProductInstall` entry of `product.db`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProductInstall {
+    pub uid: String,
+    pub product_code: String,
+    pub install_path: String,
+    /// `BaseProductState.installed`; assumed true when the state is missing.
+    pub installed: bool,
+}
+
+/// Decode the `ProductInstall` entries of a `product.db` file.
+pub fn parse_product_db(bytes: &[u8]) -> Result<Vec<ProductInstall>, IndexerError> {
+    let mut installs = Vec::new();
+    for (field, value) in ProtoReader::new(bytes).fields()? {
+        if let (1, ProtoValue::Bytes(message)) = (field, value) {
+            installs.push(parse_product_install(message)?);
+        }
+    }
+    Ok(installs)
+}
+
+fn parse_product_install(bytes: &[u8]) -> Result<ProductInstall, IndexerError> {
+    let mut install = ProductInstall {
+        installed: true,
+        ..Default::default()
+    };
+    for (field, value) in ProtoReader::new(bytes).fields()? {
+        match (field, value) {
+            (1, ProtoValue::Bytes(uid)) => install.uid = String::from_utf8_lossy(uid).to_string(),
+            (2, ProtoValue::Bytes(code)) => {
+                install.product_code = String::from_utf8_lossy(code).to_string()
+            }
+            (3, ProtoValue::Bytes(settings)) => {
+                if let Some(path) = nested_field(settings, &[1])? {
+                    install.install_path = String::from_utf8_lossy(path).to_string();
+                }
+            }
+            (4, ProtoValue::Bytes(state)) => {
+                if let Some(base) = nested_field(state, &[1])? {
+                    install.installed = ProtoReader::new(base)
+                        .fields()?
+                        .into_iter()
+                        .any(|(f, v)| f == 1 && matches!(v, ProtoValue::Varint(1)));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(install)
+}
+
+/// Follow a path of length-delimited field numbers through nested messages.
+fn nested_field<'a>(bytes: &'a [u8], path: &[u32]) -> Result<Option<&'a [u8]>, IndexerError> {
+    let Some((&first, rest)) = path.split_first() else {
+        return Ok(Some(bytes));
+    };
+    for (field, value) in ProtoReader::new(bytes).fields()? {
+        if let (true, ProtoValue::Bytes(inner)) = (field == first, value) {
+            return nested_field(inner, rest);
+        }
+    }
+    Ok(None)
+}
+
+/// Decoded protobuf field value; fixed-width values aren't needed.
+enum ProtoValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    Fixed,
+}
+
+/// Minimal protobuf wire-format reader.
+struct ProtoReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ProtoReader<'a> {
+    const VARINT: u64 = 0;
+    const FIXED64: u64 = 1;
+    const LEN: u64 = 2;
+    const FIXED32: u64 = 5;
+
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Every field of the message, in order.
+    fn fields(mut self) -> Result<Vec<(u32, ProtoValue<'a>)>, IndexerError> {
+        let mut fields = Vec::new();
+        while self.pos < self.bytes.len() {
+            let key = self.read_varint()?;
+            let field = (key >> 3) as u32;
+            let value = match key & 0x7 {
+                Self::VARINT => ProtoValue::Varint(self.read_varint()?),
+                Self::LEN => {
+                    let len = self.read_varint()? as usize;
+                    ProtoValue::Bytes(self.take(len)?)
+                }
+                Self::FIXED64 => {
+                    self.take(8)?;
+                    ProtoValue::Fixed
+                }
+                Self::FIXED32 => {
+                    self.take(4)?;
+                    ProtoValue::Fixed
+                }
+                other => return Err(format!("unsupported protobuf wire type {}", other).into()),
+            };
+            fields.push((field, value));
+        }
+        Ok(fields)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, IndexerError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .bytes
+                .get(self.pos)
+                .ok_or("unexpected end of protobuf data")?;
+            self.pos += 1;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("protobuf varint too long".into())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], IndexerError> {
+        let end = self.pos.checked_add(len).ok_or("protobuf length overflow")?;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or("unexpected end of protobuf data")?;
+        self.pos = end;
+        Ok(slice)
+    }
+}
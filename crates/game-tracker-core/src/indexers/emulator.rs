@@ -0,0 +1,242 @@
+//! Standalone emulator ROM folder indexer.
+//!
+//! Scans user-configured [`RomFolderRule`]s (folder, system, extensions,
+//! emulator) for ROMs. Multi-disc sets are grouped into one game:
+//! - an `.m3u` playlist absorbs the discs it lists
+//! - a `.cue` sheet absorbs its `.bin` tracks
+//! - files differing only by a `(Disc N)` tag share one entry
+//!
+//! `.m3u` and `.cue` files are always considered, whatever the rule's
+//! extensions. The `source_id` is the path of the file the emulator opens
+//! (the playlist, cue sheet or first disc), so it stays stable across scans.
+
+use crate::indexers::rom_id::{canonical_title, RomDatabase};
+use crate::indexers::{systems, IndexerError, LauncherIndexer};
+use crate::models::{DiscoveredGame, RomFolderRule};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// [`LauncherIndexer`] for standalone emulator ROM folders.
+#[derive(Default)]
+pub struct EmulatorIndexer {
+    pub rules: Vec<RomFolderRule>,
+    /// Logiqx DAT files used to identify single-file ROMs.
+    pub dat_files: Vec<PathBuf>,
+    /// Sidecar file caching ROM hashes between scans.
+    pub hash_cache: Option<PathBuf>,
+}
+
+impl LauncherIndexer for EmulatorIndexer {
+    fn id(&self) -> &'static str {
+        "emulator"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Emulator ROM folders"
+    }
+
+    fn is_available(&self) -> bool {
+        self.rules.iter().any(|r| Path::new(&r.folder).is_dir())
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        let roms = RomDatabase::load_cached(&self.dat_files, self.hash_cache.as_deref());
+        let mut games: Vec<DiscoveredGame> = Vec::new();
+        for rule in &self.rules {
+            for game in scan_rom_folder(rule, &roms)? {
+                // Overlapping rules keep the first match
+                if !games.iter().any(|g| g.source_id == game.source_id) {
+                    games.push(game);
+                }
+            }
+        }
+        roms.save_hash_cache();
+        Ok(games)
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.rules
+            .iter()
+            .map(|r| PathBuf::from(&r.folder))
+            .filter(|f| f.is_dir())
+            .collect()
+    }
+
+    fn watch_recursive(&self) -> bool {
+        // ROMs are found anywhere below the rule folders
+        true
+    }
+}
+
+/// Scan one rule's folder (recursively), identifying ROMs against `roms`.
+pub fn scan_rom_folder(
+    rule: &RomFolderRule,
+    roms: &RomDatabase,
+) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games = Vec::new();
+    let folder = Path::new(&rule.folder);
+
+    if !folder.is_dir() {
+        tracing::warn!("ROM folder not found: {:?}", folder);
+        return Ok(games);
+    }
+
+    let extensions: Vec<String> = rule
+        .extensions
+        .iter()
+        .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect();
+    let platform = systems::platform_for_system(&rule.system).unwrap_or(rule.system.trim());
+
+    let mut files = Vec::new();
+    collect_files(folder, &mut files)?;
+    files.sort();
+
+    // Tracks and discs referenced by a cue sheet or playlist aren't games
+    let mut absorbed = HashSet::new();
+    for file in &files {
+        match ext_of(file).as_str() {
+            "cue" => absorbed.extend(cue_tracks(file)),
+            "m3u" => absorbed.extend(m3u_entries(file)),
+            _ => {}
+        }
+    }
+
+    // Group key: directory plus file stem with any disc tag removed
+    let mut groups: BTreeMap<(PathBuf, String), Vec<PathBuf>> = BTreeMap::new();
+    for file in files {
+        let ext = ext_of(&file);
+        let is_container = ext == "m3u" || ext == "cue";
+        if absorbed.contains(&file) || !(is_container || extensions.contains(&ext)) {
+            continue;
+        }
+        let Some(stem) = file.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let name = strip_disc_tag(&stem).unwrap_or(stem);
+        let dir = file.parent().map(Path::to_path_buf).unwrap_or_default();
+        groups.entry((dir, name)).or_default().push(file);
+    }
+
+    for ((_, name), mut discs) in groups {
+        // A playlist wins over the discs it sits next to; otherwise disc 1
+        discs.sort_by_key(|d| (ext_of(d) != "m3u", d.clone()));
+        let primary = &discs[0];
+
+        // Hashing only makes sense for single-file cartridge-style ROMs
+        let identity = (discs.len() == 1 && !matches!(ext_of(primary).as_str(), "m3u" | "cue"))
+            .then(|| roms.identify(primary))
+            .flatten();
+
+        let title = canonical_title(&name);
+        if title.is_empty() {
+            continue;
+        }
+        let path = primary.to_string_lossy().to_string();
+        let mut game = DiscoveredGame {
+            title,
+            platform: platform.to_string(),
+            exe_path: None,
+            install_path: Some(path.clone()),
+            source: "emulator".to_string(),
+            source_id: path,
+            playtime_hours: None,
+            last_played: None,
+        };
+        if let Some(identity) = identity {
+            identity.apply_to(&mut game);
+        }
+        games.push(game);
+    }
+
+    Ok(games)
+}
+
+/// Recursively collect files, skipping hidden entries. Symlinked files are
+/// kept but symlinked directories are not followed, so a link back up the
+/// tree can't loop.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+        {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&path, files)?;
+        } else if !(file_type.is_symlink() && path.is_dir()) {
+            files.push(normalize(&path));
+        }
+    }
+    Ok(())
+}
+
+fn ext_of(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Files named by `FILE "track.bin" BINARY` lines of a cue sheet.
+fn cue_tracks(cue: &Path) -> Vec<PathBuf> {
+    let Ok(sheet) = std::fs::read_to_string(cue) else {
+        return Vec::new();
+    };
+    let dir = cue.parent().unwrap_or(Path::new(""));
+    sheet
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("FILE ")?;
+            let name = match rest.strip_prefix('"') {
+                Some(quoted) => quoted.split('"').next()?,
+                None => rest.split_whitespace().next()?,
+            };
+            Some(normalize(&dir.join(name)))
+        })
+        .collect()
+}
+
+/// Non-comment lines of an `.m3u` playlist, relative to its directory.
+fn m3u_entries(m3u: &Path) -> Vec<PathBuf> {
+    let Ok(playlist) = std::fs::read_to_string(m3u) else {
+        return Vec::new();
+    };
+    let dir = m3u.parent().unwrap_or(Path::new(""));
+    playlist
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| normalize(&dir.join(line)))
+        .collect()
+}
+
+/// Drop `.` components so `./disc1.cue` matches the scanned path.
+fn normalize(path: &Path) -> PathBuf {
+    path.components().collect()
+}
+
+/// `Final Fantasy VII (USA) (Disc 2)` -> `Final Fantasy VII (USA)`.
+///
+/// Recognises `(Disc N)`, `(Disc N of M)`, `(Disk N)` and `(CD N)`; returns
+/// `None` for names without a disc tag.
+pub fn strip_disc_tag(stem: &str) -> Option<String> {
+    let lower = stem.to_ascii_lowercase();
+    let start = ["(disc", "(disk", "(cd"]
+        .iter()
+        .filter_map(|tag| lower.find(tag))
+        .find(|&pos| {
+            lower[pos..]
+                .trim_start_matches(|c: char| c == '(' || c.is_alphabetic())
+                .trim_start()
+                .starts_with(|c: char| c.is_ascii_digit())
+        })?;
+    let end = lower[start..].find(')')? + start + 1;
+
+    let name = format!("{}{}", stem[..start].trim_end(), &stem[end..]);
+    Some(name.trim().to_string())
+}
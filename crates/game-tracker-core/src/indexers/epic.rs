@@ -2,11 +2,77 @@
 //!
 //! Reads `.item` JSON manifest files from:
 //! `C:\ProgramData\Epic\EpicGamesLauncher\Data\Manifests\`
+//!
+//! On Linux the launcher runs inside a Wine/Proton/Bottles prefix, so the same
+//! directory is looked up under each prefix's `drive_c`, and the `C:\` install
+//! paths in its manifests are translated back through that prefix.
 
+use crate::indexers::{wine, IndexerError, LauncherIndexer};
 use crate::models::DiscoveredGame;
 use serde::Deserialize;
 use std::path::{Path, PathBuf};
 
+/// [`LauncherIndexer`] for Epic Games Launcher manifests.
+pub struct EpicIndexer {
+    /// Directories containing `.item` manifest files.
+    pub manifests_dirs: Vec<PathBuf>,
+}
+
+impl EpicIndexer {
+    /// User-configured manifest directories, followed by the default and
+    /// auto-discovered ones.
+    pub fn new(configured: &[String]) -> Self {
+        let mut manifests_dirs: Vec<PathBuf> = configured.iter().map(PathBuf::from).collect();
+        for dir in default_manifests_dirs() {
+            if !manifests_dirs.contains(&dir) {
+                manifests_dirs.push(dir);
+            }
+        }
+        Self { manifests_dirs }
+    }
+}
+
+impl Default for EpicIndexer {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl LauncherIndexer for EpicIndexer {
+    fn id(&self) -> &'static str {
+        "epic"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Epic Games"
+    }
+
+    fn is_available(&self) -> bool {
+        self.manifests_dirs.iter().any(|d| d.is_dir())
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        let mut games: Vec<DiscoveredGame> = Vec::new();
+        for dir in self.manifests_dirs.iter().filter(|d| d.is_dir()) {
+            for game in scan_epic_games_from(dir)? {
+                // The same game can be installed into several prefixes
+                if !games.iter().any(|g| g.source_id == game.source_id) {
+                    games.push(game);
+                }
+            }
+        }
+        Ok(games)
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.manifests_dirs
+            .iter()
+            .filter(|d| d.is_dir())
+            .cloned()
+            .collect()
+    }
+}
+
 /// Fields we care about from an Epic `.item` manifest.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -19,26 +85,44 @@ struct EpicManifest {
     b_is_application: bool,
 }
 
-/// Default manifest directory on Windows.
-fn default_manifests_dir() -> PathBuf {
-    PathBuf::from(r"C:\ProgramData\Epic\EpicGamesLauncher\Data\Manifests")
+/// Manifest directory relative to the system drive.
+const MANIFESTS_SUBDIR: &str = "ProgramData/Epic/EpicGamesLauncher/Data/Manifests";
+
+/// Native Windows manifest directory plus one per discovered Wine prefix.
+fn default_manifests_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if cfg!(target_os = "windows") {
+        dirs.push(PathBuf::from(
+            r"C:\ProgramData\Epic\EpicGamesLauncher\Data\Manifests",
+        ));
+    }
+    dirs.extend(
+        wine::discover_prefixes()
+            .into_iter()
+            .map(|prefix| prefix.join("drive_c").join(MANIFESTS_SUBDIR)),
+    );
+    dirs
 }
 
 /// Scan Epic Games manifests and return discovered games.
-pub fn scan_epic_games() -> Result<Vec<DiscoveredGame>, Box<dyn std::error::Error + Send + Sync>> {
-    scan_epic_games_from(&default_manifests_dir())
+pub fn scan_epic_games() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    EpicIndexer::default().scan()
 }
 
 /// Scan Epic Games manifests from a specific directory (useful for testing).
+///
+/// If the directory is inside a Wine prefix, install paths are translated to
+/// host paths through that prefix.
 pub fn scan_epic_games_from(
     manifests_dir: &Path,
-) -> Result<Vec<DiscoveredGame>, Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<Vec<DiscoveredGame>, IndexerError> {
     let mut games = Vec::new();
 
     if !manifests_dir.is_dir() {
         tracing::warn!("Epic manifests directory not found: {:?}", manifests_dir);
         return Ok(games);
     }
+    let prefix = wine::prefix_of(manifests_dir);
 
     for entry in std::fs::read_dir(manifests_dir)? {
         let entry = entry?;
@@ -48,7 +132,7 @@ pub fn scan_epic_games_from(
             continue;
         }
 
-        match parse_manifest(&path) {
+        match parse_manifest(&path, prefix.as_deref()) {
             Ok(Some(game)) => games.push(game),
             Ok(None) => {} // not a game (e.g. engine, tool)
             Err(e) => {
@@ -60,9 +144,7 @@ pub fn scan_epic_games_from(
     Ok(games)
 }
 
-fn parse_manifest(
-    path: &Path,
-) -> Result<Option<DiscoveredGame>, Box<dyn std::error::Error + Send + Sync>> {
+fn parse_manifest(path: &Path, prefix: Option<&Path>) -> Result<Option<DiscoveredGame>, IndexerError> {
     let contents = std::fs::read_to_string(path)?;
     let manifest: EpicManifest = serde_json::from_str(&contents)?;
 
@@ -81,11 +163,21 @@ fn parse_manifest(
         _ => return Ok(None),
     };
 
-    let install_path = manifest.install_location.clone();
+    // Inside a prefix, `C:\Games\Foo` means `<prefix>/drive_c/Games/Foo`
+    let install_path = manifest.install_location.as_deref().map(|install| {
+        prefix
+            .and_then(|p| wine::windows_to_host(p, install))
+            .map(|host| host.to_string_lossy().to_string())
+            .unwrap_or_else(|| install.to_string())
+    });
 
     // Build full exe path from install location + launch executable
-    let exe_path = match (&manifest.install_location, &manifest.launch_executable) {
+    let exe_path = match (&install_path, &manifest.launch_executable) {
         (Some(install), Some(launch)) => {
+            let launch = match prefix {
+                Some(_) => launch.replace('\\', "/"),
+                None => launch.clone(),
+            };
             let full = PathBuf::from(install).join(launch);
             if full.exists() {
                 Some(full.to_string_lossy().to_string())
@@ -103,5 +195,7 @@ fn parse_manifest(
         install_path,
         source: "epic".to_string(),
         source_id: app_name,
+        playtime_hours: None,
+        last_played: None,
     }))
 }
@@ -0,0 +1,259 @@
+//! Main executable detection for installed games.
+//!
+//! Walks an install directory to a bounded depth and scores every executable
+//! it finds: Windows PE files, Linux ELF binaries (`.x86_64`, extensionless)
+//! and launch scripts such as `start.sh`. Higher scores mean "more likely to
+//! be the game itself".
+
+use crate::models::{ExeCandidate, ExeDetection};
+use std::path::Path;
+
+/// Maximum directory depth below the install root (covers `bin/Win64/`
+/// and Unreal's `<Game>/Binaries/Win64/`).
+const MAX_DEPTH: usize = 4;
+
+/// Upper bound on directory entries visited per install.
+const MAX_ENTRIES: usize = 5_000;
+
+/// File name fragments that are never the game.
+const EXCLUDED: &[&str] = &[
+    "unins",
+    "redist",
+    "setup",
+    "crash",
+    "ue4prereq",
+    "ueprereq",
+    "dxsetup",
+    "vcredist",
+    "dotnet",
+    "installer",
+    "updater",
+    "report",
+    "cefprocess",
+    "easyanticheat",
+    "battleye",
+];
+
+/// File name fragments that are rarely the game but sometimes are.
+const PENALIZED: &[&str] = &[
+    "launcher",
+    "editor",
+    "server",
+    "config",
+    "settings",
+    "tool",
+    "helper",
+    "benchmark",
+];
+
+/// Directories that only ever contain support binaries.
+const SKIPPED_DIRS: &[&str] = &[
+    "_commonredist",
+    "commonredist",
+    "redist",
+    "redistributables",
+    "directx",
+    "vcredist",
+    "dotnet",
+    "__installer",
+    "support",
+    "prereqs",
+    "easyanticheat",
+    "battleye",
+];
+
+/// Executable format detected from the file header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExeKind {
+    Pe,
+    Elf,
+    Script,
+}
+
+/// Find and rank executables under `install_dir` for a game called `title`.
+pub fn detect_main_exe(install_dir: &Path, title: &str) -> ExeDetection {
+    let mut candidates = Vec::new();
+    if install_dir.is_dir() {
+        let mut visited = 0;
+        walk(
+            install_dir,
+            install_dir,
+            0,
+            title,
+            &mut visited,
+            &mut candidates,
+        );
+    }
+
+    // Highest score first; shorter paths break ties
+    candidates.sort_by(|a: &ExeCandidate, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.path.len().cmp(&b.path.len()))
+    });
+
+    let mut candidates = candidates.into_iter();
+    ExeDetection {
+        best: candidates.next(),
+        alternatives: candidates.collect(),
+    }
+}
+
+/// Best-scoring executable path under `install_dir`, if any.
+pub fn find_main_exe(install_dir: &Path, title: &str) -> Option<String> {
+    detect_main_exe(install_dir, title).best.map(|c| c.path)
+}
+
+fn walk(
+    root: &Path,
+    dir: &Path,
+    depth: usize,
+    title: &str,
+    visited: &mut usize,
+    out: &mut Vec<ExeCandidate>,
+) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        *visited += 1;
+        if *visited > MAX_ENTRIES {
+            return;
+        }
+
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+
+        if file_type.is_dir() {
+            if depth < MAX_DEPTH && !SKIPPED_DIRS.contains(&name.as_str()) {
+                walk(root, &path, depth + 1, title, visited, out);
+            }
+            continue;
+        }
+
+        if EXCLUDED.iter().any(|x| name.contains(x)) {
+            continue;
+        }
+        let Some(kind) = executable_kind(&path, &name) else {
+            continue;
+        };
+
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        let rel_depth = path
+            .strip_prefix(root)
+            .map(|p| p.components().count().saturating_sub(1))
+            .unwrap_or(depth);
+
+        out.push(ExeCandidate {
+            path: path.to_string_lossy().to_string(),
+            score: score(&path, &name, kind, size, rel_depth, title),
+        });
+    }
+}
+
+/// Classify a file as an executable by extension and header magic.
+fn executable_kind(path: &Path, name: &str) -> Option<ExeKind> {
+    let ext = Path::new(name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    match ext.as_str() {
+        "sh" => return Some(ExeKind::Script),
+        // Anything else with an extension that isn't a known binary is data
+        "exe" | "x86_64" | "x86" | "appimage" | "bin" | "" => {}
+        _ => return None,
+    }
+
+    let mut magic = [0u8; 4];
+    let read = std::fs::File::open(path)
+        .and_then(|mut f| std::io::Read::read(&mut f, &mut magic))
+        .ok()?;
+    if read < 4 {
+        return None;
+    }
+
+    if magic == [0x7F, b'E', b'L', b'F'] {
+        Some(ExeKind::Elf)
+    } else if magic[..2] == *b"MZ" && ext == "exe" {
+        Some(ExeKind::Pe)
+    } else {
+        None
+    }
+}
+
+fn score(path: &Path, name: &str, kind: ExeKind, size: u64, depth: usize, title: &str) -> i32 {
+    let stem = normalize(
+        Path::new(name)
+            .file_stem()
+            .map(|s| s.to_string_lossy())
+            .unwrap_or_default()
+            .as_ref(),
+    );
+    let wanted = normalize(title);
+    let mut score = 0;
+
+    // Name similarity to the title
+    if !stem.is_empty() && !wanted.is_empty() {
+        if stem == wanted || stem == acronym(title) {
+            score += 50;
+        } else if stem.len() >= 3 && (wanted.contains(&stem) || stem.contains(&wanted)) {
+            score += 30;
+        } else {
+            let words: Vec<String> = title
+                .split(|c: char| !c.is_alphanumeric())
+                .map(|w| w.to_lowercase())
+                .filter(|w| w.len() >= 3)
+                .collect();
+            score += 10 * words.iter().filter(|w| stem.contains(w.as_str())).count() as i32;
+        }
+    }
+
+    // Native binaries first on the platform we're running on
+    score += match kind {
+        ExeKind::Pe if cfg!(windows) => 20,
+        ExeKind::Elf if !cfg!(windows) => 20,
+        ExeKind::Pe | ExeKind::Elf => 10,
+        ExeKind::Script if matches!(stem.as_str(), "start" | "run" | "launch" | "play") => 15,
+        ExeKind::Script => 0,
+    };
+
+    // Bigger binaries are more likely the game than small helpers
+    let size_mb = size as f64 / (1024.0 * 1024.0);
+    score += ((size_mb + 1.0).log2() * 3.0).min(20.0) as i32;
+
+    // Prefer 64-bit binary folders, but otherwise shallower paths
+    let lower_path = path.to_string_lossy().to_lowercase();
+    if lower_path.contains("win64") || lower_path.contains("x86_64") || lower_path.contains("x64") {
+        score += 5;
+    }
+    score -= 5 * depth as i32;
+
+    if PENALIZED.iter().any(|p| name.contains(p)) {
+        score -= 20;
+    }
+
+    score
+}
+
+/// Lowercase alphanumerics only, so "Hollow_Knight" matches "Hollow Knight".
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// First letter of each word, e.g. "Grand Theft Auto V" -> "gtav".
+fn acronym(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter_map(|w| w.chars().next())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
@@ -0,0 +1,221 @@
+//! Custom folder indexer for DRM-free and portable games.
+//!
+//! Each subfolder of a user-configured root (e.g. `~/Games`, `D:\Games`) is a
+//! game candidate. The main executable is found with [`exe_detect`] and the
+//! title is derived from the folder name. The `source_id` is the folder's
+//! path relative to its root, so moving or renaming a root keeps its games.
+//! When an earlier root already has a folder of that name, the root's label
+//! (its own name, or `root<N>` when two roots share a name) is prepended:
+//! `D:\Games\Doom` and `E:\Portable\Doom` are `Doom` and `Portable/Doom`.
+
+use crate::indexers::{exe_detect, IndexerError, LauncherIndexer};
+use crate::models::DiscoveredGame;
+use std::path::{Path, PathBuf};
+
+/// [`LauncherIndexer`] for user-configured game folders.
+#[derive(Default)]
+pub struct FolderIndexer {
+    /// Root folders whose subfolders are games.
+    pub roots: Vec<PathBuf>,
+}
+
+impl LauncherIndexer for FolderIndexer {
+    fn id(&self) -> &'static str {
+        "folder"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Game folders"
+    }
+
+    fn is_available(&self) -> bool {
+        self.roots.iter().any(|r| r.is_dir())
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        // A root listed twice reports its folders once
+        let mut roots: Vec<&PathBuf> = Vec::new();
+        for root in &self.roots {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        let labels = root_labels(&roots);
+
+        let mut games: Vec<DiscoveredGame> = Vec::new();
+        let mut seen: Vec<String> = Vec::new();
+        for (root, label) in roots.iter().zip(&labels) {
+            let mut found = scan_game_folder(root)?;
+            // Only later roots are prefixed, so adding a root never changes
+            // the ids of games already indexed
+            for game in &mut found {
+                if seen.contains(&game.source_id) {
+                    game.source_id = format!("{}/{}", label, game.source_id);
+                }
+            }
+            seen.extend(found.iter().map(|g| g.source_id.clone()));
+            games.extend(found);
+        }
+        Ok(games)
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.roots.iter().filter(|r| r.is_dir()).cloned().collect()
+    }
+}
+
+/// Label of each root for disambiguating `source_id`s: its folder name, or
+/// `root<N>` (1-based position) when another root has the same name.
+fn root_labels(roots: &[&PathBuf]) -> Vec<String> {
+    let names: Vec<String> = roots
+        .iter()
+        .map(|r| r.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default())
+        .collect();
+    names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let unique = !name.is_empty() && names.iter().filter(|n| *n == name).count() == 1;
+            if unique {
+                name.clone()
+            } else {
+                format!("root{}", i + 1)
+            }
+        })
+        .collect()
+}
+
+/// Scan one root folder; subfolders without an executable are skipped.
+/// Each game's `source_id` is its folder name.
+pub fn scan_game_folder(root: &Path) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games = Vec::new();
+
+    if !root.is_dir() {
+        tracing::warn!("Game folder not found: {:?}", root);
+        return Ok(games);
+    }
+
+    for entry in std::fs::read_dir(root)? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(folder_name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if folder_name.starts_with('.') {
+            continue;
+        }
+
+        let title = clean_folder_title(&folder_name);
+        if title.is_empty() {
+            continue;
+        }
+        let Some(exe_path) = exe_detect::find_main_exe(&path, &title) else {
+            continue;
+        };
+
+        games.push(DiscoveredGame {
+            title,
+            platform: "PC".to_string(),
+            exe_path: Some(exe_path),
+            install_path: Some(path.to_string_lossy().to_string()),
+            source: "folder".to_string(),
+            source_id: folder_name,
+            playtime_hours: None,
+            last_played: None,
+        });
+    }
+
+    Ok(games)
+}
+
+/// Trailing words that describe the build rather than the game.
+const BUILD_TAGS: &[&str] = &[
+    "linux", "windows", "win", "win32", "win64", "x86", "x64", "x86_64", "amd64", "mac", "osx",
+    "portable", "gog", "drmfree", "drm-free", "repack",
+];
+
+/// Turn an install folder name into a display title.
+///
+/// Underscores become spaces, dot-separated names are split into words, and
+/// bracketed tags, trailing version numbers and build tags are dropped:
+/// `Hollow_Knight_v1.5.78` becomes `Hollow Knight`.
+pub fn clean_folder_title(folder_name: &str) -> String {
+    let mut name = strip_brackets(folder_name).replace('_', " ");
+
+    // `FTL.Faster.Than.Light.v1.6` style names: dots separate words, except
+    // between digits where they belong to a version number
+    if !name.trim().contains(' ') {
+        let chars: Vec<char> = name.chars().collect();
+        name = chars
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| {
+                let between_digits = i > 0
+                    && chars[i - 1].is_ascii_digit()
+                    && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit());
+                if c == '.' && !between_digits {
+                    ' '
+                } else {
+                    c
+                }
+            })
+            .collect();
+    }
+
+    let mut words: Vec<String> = name.split_whitespace().map(str::to_string).collect();
+
+    // `Celeste-1.4.0.0`: version glued on with a dash
+    if let Some(last) = words.last_mut() {
+        if let Some((head, tail)) = last.rsplit_once('-') {
+            if !head.is_empty() && is_version(tail) {
+                *last = head.to_string();
+            }
+        }
+    }
+
+    while let Some(last) = words.last() {
+        let lower = last.to_lowercase();
+        let is_build_word = is_version(last)
+            || BUILD_TAGS.contains(&lower.as_str())
+            || matches!(lower.as_str(), "-" | "build")
+            || (words.len() >= 2
+                && words[words.len() - 2].eq_ignore_ascii_case("build")
+                && last.chars().all(|c| c.is_ascii_digit()));
+        if !is_build_word || words.len() == 1 {
+            break;
+        }
+        words.pop();
+    }
+
+    words.join(" ")
+}
+
+/// `v1.2`, `V3`, `1.0.5`: a `v` prefix or at least one dot is required so
+/// titles like "Portal 2" keep their number.
+fn is_version(word: &str) -> bool {
+    let (prefixed, rest) = match word.strip_prefix(['v', 'V']) {
+        Some(rest) => (true, rest),
+        None => (false, word),
+    };
+    !rest.is_empty()
+        && rest.starts_with(|c: char| c.is_ascii_digit())
+        && rest.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && (prefixed || rest.contains('.'))
+}
+
+/// Remove `(...)` and `[...]` segments.
+fn strip_brackets(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut depth = 0usize;
+    for c in value.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
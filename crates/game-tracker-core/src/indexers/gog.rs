@@ -0,0 +1,134 @@
+//! GOG Galaxy 2.0 auto-indexer.
+//!
+//! Reads installed products from Galaxy's SQLite database at:
+//! `C:\ProgramData\GOG.com\Galaxy\storage\galaxy-2.0.db`
+
+use crate::indexers::{IndexerError, LauncherIndexer};
+use crate::models::DiscoveredGame;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use std::path::{Path, PathBuf};
+
+/// [`LauncherIndexer`] for GOG Galaxy 2.0.
+pub struct GogIndexer {
+    /// Path to `galaxy-2.0.db`.
+    pub db_path: PathBuf,
+}
+
+impl Default for GogIndexer {
+    fn default() -> Self {
+        Self {
+            db_path: default_db_path(),
+        }
+    }
+}
+
+impl LauncherIndexer for GogIndexer {
+    fn id(&self) -> &'static str {
+        "gog"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "GOG Galaxy"
+    }
+
+    fn is_available(&self) -> bool {
+        self.db_path.is_file()
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        scan_gog_games_from(&self.db_path)
+    }
+}
+
+/// Default Galaxy database location on Windows.
+fn default_db_path() -> PathBuf {
+    PathBuf::from(r"C:\ProgramData\GOG.com\Galaxy\storage\galaxy-2.0.db")
+}
+
+/// Scan the GOG Galaxy database and return discovered games.
+pub fn scan_gog_games() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    scan_gog_games_from(&default_db_path())
+}
+
+/// Scan a specific `galaxy-2.0.db` file (useful for testing).
+pub fn scan_gog_games_from(db_path: &Path) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games = Vec::new();
+
+    if !db_path.is_file() {
+        tracing::warn!("GOG Galaxy database not found: {:?}", db_path);
+        return Ok(games);
+    }
+
+    // Galaxy keeps the database open while running, so never take a write lock.
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT ibp.productId, ibp.installationPath, \
+         (SELECT ld.title FROM LimitedDetails ld WHERE ld.productId = ibp.productId LIMIT 1) \
+         FROM InstalledBaseProducts ibp",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+        ))
+    })?;
+
+    for row in rows {
+        let (product_id, install_path, title) = row?;
+
+        let title = match title {
+            Some(t) if !t.is_empty() => t,
+            // Fall back to the install folder name when details are missing
+            _ => match install_path.as_deref().and_then(folder_name) {
+                Some(name) => name,
+                None => continue,
+            },
+        };
+
+        let exe_path = match primary_play_task_exe(&conn, product_id) {
+            Ok(path) => path.filter(|p| Path::new(p).exists()),
+            Err(e) => {
+                tracing::warn!("Failed to read GOG play tasks for {}: {}", product_id, e);
+                None
+            }
+        };
+
+        games.push(DiscoveredGame {
+            title,
+            platform: "PC".to_string(),
+            exe_path,
+            install_path,
+            source: "gog".to_string(),
+            source_id: product_id.to_string(),
+            playtime_hours: None,
+            last_played: None,
+        });
+    }
+
+    Ok(games)
+}
+
+/// Executable of the primary play task for a product, if Galaxy recorded one.
+fn primary_play_task_exe(conn: &Connection, product_id: i64) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT ptlp.executablePath FROM PlayTasks pt \
+         JOIN PlayTaskLaunchParameters ptlp ON ptlp.playTaskId = pt.id \
+         WHERE pt.gameReleaseKey = ? \
+         ORDER BY pt.isPrimary DESC, pt.\"order\" ASC LIMIT 1",
+        [format!("gog_{}", product_id)],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .optional()
+    .map(Option::flatten)
+}
+
+/// Last component of a Windows or Unix style path.
+fn folder_name(path: &str) -> Option<String> {
+    path.trim_end_matches(['\\', '/'])
+        .rsplit(['\\', '/'])
+        .next()
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,325 @@
+//! Heroic Games Launcher auto-indexer (Linux).
+//!
+//! Heroic wraps three store backends, each with its own install database
+//! inside the Heroic config directory:
+//! - Epic (legendary): `legendaryConfig/legendary/installed.json`
+//! - GOG: `gog_store/installed.json`, titles from `store_cache/gog_library.json`
+//! - Amazon (nile): `nile_config/nile/installed.json` and `library.json`
+//!
+//! Playtime comes from `store/timestamp.json`, keyed by app name.
+//!
+//! `source_id` is `<backend>:<app name>` (e.g. `legendary:Fortnite`) so the
+//! same app name can never collide across backends.
+
+use crate::indexers::{sqlite_timestamp, IndexerError, LauncherIndexer};
+use crate::models::DiscoveredGame;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// [`LauncherIndexer`] for Heroic's native and Flatpak config directories.
+pub struct HeroicIndexer {
+    /// Heroic config roots to scan (e.g. `~/.config/heroic`).
+    pub config_dirs: Vec<PathBuf>,
+}
+
+impl Default for HeroicIndexer {
+    fn default() -> Self {
+        Self {
+            config_dirs: default_config_dirs(),
+        }
+    }
+}
+
+impl LauncherIndexer for HeroicIndexer {
+    fn id(&self) -> &'static str {
+        "heroic"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Heroic"
+    }
+
+    fn is_available(&self) -> bool {
+        self.config_dirs.iter().any(|d| d.is_dir())
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        let mut games: Vec<DiscoveredGame> = Vec::new();
+        for dir in &self.config_dirs {
+            for game in scan_heroic_games_from(dir)? {
+                // Native and Flatpak installs can both exist; keep the first hit
+                if !games.iter().any(|g| g.source_id == game.source_id) {
+                    games.push(game);
+                }
+            }
+        }
+        Ok(games)
+    }
+}
+
+/// Native (`~/.config/heroic`) and Flatpak Heroic config directories.
+fn default_config_dirs() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"));
+
+    vec![
+        config_home.join("heroic"),
+        home.join(".var/app/com.heroicgameslauncher.hgl/config/heroic"),
+    ]
+}
+
+/// Entry in legendary's `installed.json` (a map keyed by app name).
+#[derive(Debug, Deserialize)]
+struct LegendaryInstall {
+    app_name: String,
+    title: Option<String>,
+    install_path: Option<String>,
+    executable: Option<String>,
+    #[serde(default)]
+    is_dlc: bool,
+}
+
+/// `gog_store/installed.json` wrapper.
+#[derive(Debug, Deserialize)]
+struct GogInstalledFile {
+    #[serde(default)]
+    installed: Vec<GogInstall>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GogInstall {
+    app_name: String,
+    #[serde(rename = "install_path")]
+    install_path: Option<String>,
+    executable: Option<String>,
+    #[serde(default, rename = "is_dlc")]
+    is_dlc: bool,
+}
+
+/// `store_cache/gog_library.json` wrapper, used only for titles.
+#[derive(Debug, Deserialize)]
+struct GogLibraryFile {
+    #[serde(default)]
+    games: Vec<GogLibraryEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GogLibraryEntry {
+    app_name: String,
+    title: Option<String>,
+}
+
+/// Entry in nile's `installed.json` (a list).
+#[derive(Debug, Deserialize)]
+struct NileInstall {
+    id: String,
+    path: Option<String>,
+}
+
+/// Entry in nile's `library.json`, used only for titles.
+#[derive(Debug, Deserialize)]
+struct NileLibraryEntry {
+    id: String,
+    product: Option<NileProduct>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NileProduct {
+    title: Option<String>,
+}
+
+/// Entry in `store/timestamp.json`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlayTimestamp {
+    last_played: Option<String>,
+    /// Total playtime in minutes.
+    total_played: Option<f64>,
+}
+
+/// Scan the default Heroic config directories and return discovered games.
+pub fn scan_heroic_games() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    HeroicIndexer::default().scan()
+}
+
+/// Scan a specific Heroic config directory (useful for testing).
+pub fn scan_heroic_games_from(config_dir: &Path) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games = Vec::new();
+
+    if !config_dir.is_dir() {
+        tracing::warn!("Heroic config directory not found: {:?}", config_dir);
+        return Ok(games);
+    }
+
+    let timestamps: HashMap<String, PlayTimestamp> =
+        read_metadata(&config_dir.join("store/timestamp.json")).unwrap_or_default();
+
+    // Epic via legendary
+    let legendary: HashMap<String, LegendaryInstall> =
+        read_json(&config_dir.join("legendaryConfig/legendary/installed.json"))?
+            .unwrap_or_default();
+    for install in legendary.into_values() {
+        if install.is_dlc {
+            continue;
+        }
+        let title = match install.title {
+            Some(t) if !t.is_empty() => t,
+            _ => continue,
+        };
+        let exe_path = join_existing(
+            install.install_path.as_deref(),
+            install.executable.as_deref(),
+        );
+        games.push(heroic_game(
+            "legendary",
+            &install.app_name,
+            title,
+            install.install_path,
+            exe_path,
+            timestamps.get(&install.app_name),
+        ));
+    }
+
+    // GOG
+    let gog: Option<GogInstalledFile> = read_json(&config_dir.join("gog_store/installed.json"))?;
+    if let Some(gog) = gog {
+        let titles: HashMap<String, String> =
+            read_metadata::<GogLibraryFile>(&config_dir.join("store_cache/gog_library.json"))
+                .map(|lib| {
+                    lib.games
+                        .into_iter()
+                        .filter_map(|g| g.title.map(|t| (g.app_name, t)))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+        for install in gog.installed {
+            if install.is_dlc {
+                continue;
+            }
+            let title = match titles.get(&install.app_name) {
+                Some(t) => t.clone(),
+                None => match install.install_path.as_deref().and_then(folder_name) {
+                    Some(name) => name,
+                    None => continue,
+                },
+            };
+            let exe_path = join_existing(
+                install.install_path.as_deref(),
+                install.executable.as_deref(),
+            );
+            games.push(heroic_game(
+                "gog",
+                &install.app_name,
+                title,
+                install.install_path,
+                exe_path,
+                timestamps.get(&install.app_name),
+            ));
+        }
+    }
+
+    // Amazon via nile
+    let nile: Vec<NileInstall> =
+        read_json(&config_dir.join("nile_config/nile/installed.json"))?.unwrap_or_default();
+    if !nile.is_empty() {
+        let titles: HashMap<String, String> =
+            read_metadata::<Vec<NileLibraryEntry>>(&config_dir.join("nile_config/nile/library.json"))
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|e| e.product.and_then(|p| p.title).map(|t| (e.id, t)))
+                .collect();
+
+        for install in nile {
+            let title = match titles.get(&install.id) {
+                Some(t) => t.clone(),
+                None => match install.path.as_deref().and_then(folder_name) {
+                    Some(name) => name,
+                    None => continue,
+                },
+            };
+            games.push(heroic_game(
+                "nile",
+                &install.id,
+                title,
+                install.path,
+                None,
+                timestamps.get(&install.id),
+            ));
+        }
+    }
+
+    Ok(games)
+}
+
+fn heroic_game(
+    backend: &str,
+    app_name: &str,
+    title: String,
+    install_path: Option<String>,
+    exe_path: Option<String>,
+    timestamp: Option<&PlayTimestamp>,
+) -> DiscoveredGame {
+    DiscoveredGame {
+        title,
+        platform: "PC".to_string(),
+        exe_path,
+        install_path,
+        source: "heroic".to_string(),
+        source_id: format!("{}:{}", backend, app_name),
+        playtime_hours: timestamp
+            .and_then(|t| t.total_played)
+            .map(|minutes| (minutes / 60.0) as f32),
+        last_played: timestamp
+            .and_then(|t| t.last_played.as_deref())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| sqlite_timestamp(dt.with_timezone(&Utc))),
+    }
+}
+
+/// Parse a Heroic JSON file. A missing file is `Ok(None)`; one that can't
+/// be read or parsed (Heroic may be halfway through rewriting it) is an
+/// error, so a broken install list never reads as "nothing installed".
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>, IndexerError> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read Heroic file {:?}: {}", path, e).into()),
+    };
+    serde_json::from_str(&contents)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse Heroic file {:?}: {}", path, e).into())
+}
+
+/// [`read_json`] for titles and playtime, which only decorate the install
+/// lists: a broken file is logged and skipped.
+fn read_metadata<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    read_json(path).unwrap_or_else(|e| {
+        tracing::warn!("{}", e);
+        None
+    })
+}
+
+/// Join an install dir and relative executable, keeping it only if it exists.
+fn join_existing(install_path: Option<&str>, executable: Option<&str>) -> Option<String> {
+    match (install_path, executable) {
+        (Some(install), Some(exe)) if !exe.is_empty() => {
+            let full = PathBuf::from(install).join(exe);
+            full.exists().then(|| full.to_string_lossy().to_string())
+        }
+        _ => None,
+    }
+}
+
+fn folder_name(path: &str) -> Option<String> {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+}
@@ -0,0 +1,177 @@
+//! itch.io desktop app auto-indexer.
+//!
+//! The itch app's `butler` daemon records each install as a "cave" in
+//! `db/butler.db` under the itch config directory:
+//! - Windows: `%APPDATA%\itch\db\butler.db`
+//! - Linux: `~/.config/itch/db/butler.db`
+//! - macOS: `~/Library/Application Support/itch/db/butler.db`
+
+use crate::indexers::{sqlite_timestamp, IndexerError, LauncherIndexer};
+use crate::models::DiscoveredGame;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OpenFlags};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// [`LauncherIndexer`] for the itch.io desktop app.
+pub struct ItchIndexer {
+    /// Path to `butler.db`.
+    pub db_path: PathBuf,
+}
+
+impl Default for ItchIndexer {
+    fn default() -> Self {
+        Self {
+            db_path: default_db_path(),
+        }
+    }
+}
+
+impl LauncherIndexer for ItchIndexer {
+    fn id(&self) -> &'static str {
+        "itch"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "itch.io"
+    }
+
+    fn is_available(&self) -> bool {
+        self.db_path.is_file()
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        scan_itch_games_from(&self.db_path)
+    }
+}
+
+/// Platform-specific default `butler.db` location.
+fn default_db_path() -> PathBuf {
+    let config_dir = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|h| PathBuf::from(h).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+    };
+    config_dir
+        .unwrap_or_default()
+        .join("itch")
+        .join("db")
+        .join("butler.db")
+}
+
+/// Launch target analysis butler stores per cave.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Verdict {
+    base_path: Option<String>,
+    #[serde(default)]
+    candidates: Vec<VerdictCandidate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerdictCandidate {
+    path: String,
+}
+
+/// Scan the default itch database and return discovered games.
+pub fn scan_itch_games() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    scan_itch_games_from(&default_db_path())
+}
+
+/// Scan a specific `butler.db` file (useful for testing).
+pub fn scan_itch_games_from(db_path: &Path) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games = Vec::new();
+
+    if !db_path.is_file() {
+        tracing::warn!("itch database not found: {:?}", db_path);
+        return Ok(games);
+    }
+
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare(
+        "SELECT g.id, g.title, c.custom_install_folder, il.path, c.install_folder_name, \
+         c.verdict, c.seconds_run, c.last_touched_at \
+         FROM caves c \
+         JOIN games g ON g.id = c.game_id \
+         LEFT JOIN install_locations il ON il.id = c.install_location_id \
+         WHERE COALESCE(g.classification, 'game') = 'game'",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(CaveRow {
+            game_id: row.get(0)?,
+            title: row.get(1)?,
+            custom_install_folder: row.get(2)?,
+            location_path: row.get(3)?,
+            install_folder_name: row.get(4)?,
+            verdict: row.get(5)?,
+            seconds_run: row.get(6)?,
+            last_touched_at: row.get(7)?,
+        })
+    })?;
+
+    for row in rows {
+        let row = row?;
+        let title = match row.title {
+            Some(ref t) if !t.is_empty() => t.clone(),
+            _ => continue,
+        };
+
+        let install_path = row.custom_install_folder.clone().or_else(|| {
+            match (&row.location_path, &row.install_folder_name) {
+                (Some(base), Some(folder)) => {
+                    Some(Path::new(base).join(folder).to_string_lossy().to_string())
+                }
+                _ => None,
+            }
+        });
+
+        games.push(DiscoveredGame {
+            title,
+            platform: "PC".to_string(),
+            exe_path: row.verdict.as_deref().and_then(verdict_exe),
+            install_path,
+            source: "itch".to_string(),
+            source_id: row.game_id.to_string(),
+            playtime_hours: row
+                .seconds_run
+                .filter(|s| *s > 0)
+                .map(|s| s as f32 / 3600.0),
+            last_played: row.last_touched_at.as_deref().and_then(parse_butler_time),
+        });
+    }
+
+    Ok(games)
+}
+
+/// Columns read from butler's `caves` joined with `games`.
+struct CaveRow {
+    game_id: i64,
+    title: Option<String>,
+    custom_install_folder: Option<String>,
+    location_path: Option<String>,
+    install_folder_name: Option<String>,
+    verdict: Option<String>,
+    seconds_run: Option<i64>,
+    last_touched_at: Option<String>,
+}
+
+/// First launch candidate from butler's verdict, if it exists on disk.
+fn verdict_exe(verdict: &str) -> Option<String> {
+    let verdict: Verdict = serde_json::from_str(verdict).ok()?;
+    let base = verdict.base_path?;
+    let candidate = verdict.candidates.first()?;
+    let full = Path::new(&base).join(&candidate.path);
+    full.exists().then(|| full.to_string_lossy().to_string())
+}
+
+/// Butler writes RFC 3339 timestamps, older versions a space-separated form.
+fn parse_butler_time(value: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%:z"))
+        .ok()
+        .map(|dt| sqlite_timestamp(dt.with_timezone(&Utc)))
+}
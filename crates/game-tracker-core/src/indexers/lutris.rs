@@ -0,0 +1,280 @@
+//! Lutris auto-indexer.
+//!
+//! Reads installed games from Lutris' `pga.db` SQLite database and the
+//! per-game YAML configs in `games/`, both under the Lutris data directory
+//! (`~/.local/share/lutris`, or the Flatpak equivalent).
+//!
+//! Lutris tracks playtime itself, so discovered games carry it over.
+
+use crate::indexers::{sqlite_timestamp, IndexerError, LauncherIndexer};
+use crate::models::DiscoveredGame;
+use chrono::DateTime;
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags};
+use std::path::{Path, PathBuf};
+
+/// [`LauncherIndexer`] for Lutris' native and Flatpak data directories.
+pub struct LutrisIndexer {
+    /// Lutris data directories containing `pga.db`.
+    pub data_dirs: Vec<PathBuf>,
+}
+
+impl Default for LutrisIndexer {
+    fn default() -> Self {
+        Self {
+            data_dirs: default_data_dirs(),
+        }
+    }
+}
+
+impl LauncherIndexer for LutrisIndexer {
+    fn id(&self) -> &'static str {
+        "lutris"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Lutris"
+    }
+
+    fn is_available(&self) -> bool {
+        self.data_dirs.iter().any(|d| d.join("pga.db").is_file())
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        let mut games: Vec<DiscoveredGame> = Vec::new();
+        for dir in &self.data_dirs {
+            if !dir.join("pga.db").is_file() {
+                continue;
+            }
+            let mut config_dirs = vec![dir.join("games")];
+            config_dirs.extend(legacy_config_dir());
+            for game in scan_data_dir(dir, &config_dirs)? {
+                if !games.iter().any(|g| g.source_id == game.source_id) {
+                    games.push(game);
+                }
+            }
+        }
+        Ok(games)
+    }
+}
+
+/// Native and Flatpak Lutris data directories.
+fn default_data_dirs() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local/share"));
+
+    vec![
+        data_home.join("lutris"),
+        home.join(".var/app/net.lutris.Lutris/data/lutris"),
+    ]
+}
+
+/// Legacy location of per-game configs (Lutris < 0.5.13).
+fn legacy_config_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/lutris/games"))
+}
+
+/// Scan the default Lutris data directories and return discovered games.
+pub fn scan_lutris_games() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    LutrisIndexer::default().scan()
+}
+
+/// Scan a specific Lutris data directory (useful for testing).
+///
+/// Expects `pga.db` and an optional `games/` directory of YAML configs.
+pub fn scan_lutris_games_from(data_dir: &Path) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    scan_data_dir(data_dir, &[data_dir.join("games")])
+}
+
+fn scan_data_dir(
+    data_dir: &Path,
+    config_dirs: &[PathBuf],
+) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games = Vec::new();
+    let db_path = data_dir.join("pga.db");
+
+    if !db_path.is_file() {
+        tracing::warn!("Lutris database not found: {:?}", db_path);
+        return Ok(games);
+    }
+
+    let conn = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, name, platform, runner, directory, lastplayed, configpath, playtime \
+         FROM games WHERE installed = 1",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(LutrisRow {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            platform: row.get(2)?,
+            runner: row.get(3)?,
+            directory: row.get(4)?,
+            lastplayed: row.get(5)?,
+            configpath: row.get(6)?,
+            playtime: row.get(7)?,
+        })
+    })?;
+
+    for row in rows {
+        let row = row?;
+        let title = match row.name {
+            Some(ref n) if !n.is_empty() => n.clone(),
+            _ => continue,
+        };
+
+        let install_path = row.directory.clone().filter(|d| !d.is_empty());
+        let exe_path = row
+            .configpath
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            .and_then(|c| read_config_exe(config_dirs, c, install_path.as_deref()));
+
+        games.push(DiscoveredGame {
+            title,
+            platform: lutris_platform(row.runner.as_deref(), row.platform.as_deref()),
+            exe_path,
+            install_path,
+            source: "lutris".to_string(),
+            source_id: row.id.to_string(),
+            playtime_hours: playtime_hours(&row.playtime),
+            last_played: row
+                .lastplayed
+                .filter(|ts| *ts > 0)
+                .and_then(|ts| DateTime::from_timestamp(ts, 0))
+                .map(sqlite_timestamp),
+        });
+    }
+
+    Ok(games)
+}
+
+/// Columns read from `pga.db`'s `games` table.
+struct LutrisRow {
+    id: i64,
+    name: Option<String>,
+    platform: Option<String>,
+    runner: Option<String>,
+    directory: Option<String>,
+    lastplayed: Option<i64>,
+    configpath: Option<String>,
+    /// Hours as REAL in current Lutris; older versions stored free text.
+    playtime: Value,
+}
+
+fn playtime_hours(value: &Value) -> Option<f32> {
+    match value {
+        Value::Real(h) if *h > 0.0 => Some(*h as f32),
+        Value::Integer(h) if *h > 0 => Some(*h as f32),
+        _ => None,
+    }
+}
+
+/// Resolve the game executable from `<configpath>.yml`, if both exist.
+///
+/// Only `game.exe` is used: emulator configs point `game.main_file` at a ROM,
+/// which is not something we can launch directly.
+fn read_config_exe(
+    config_dirs: &[PathBuf],
+    configpath: &str,
+    install_path: Option<&str>,
+) -> Option<String> {
+    let file = config_dirs
+        .iter()
+        .map(|d| d.join(format!("{}.yml", configpath)))
+        .find(|p| p.is_file())?;
+
+    let contents = std::fs::read_to_string(&file).ok()?;
+    let config: serde_yaml::Value = match serde_yaml::from_str(&contents) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!("Failed to parse Lutris config {:?}: {}", file, e);
+            return None;
+        }
+    };
+
+    let exe = config.get("game")?.get("exe")?.as_str()?;
+    let exe = Path::new(exe);
+    let full = if exe.is_absolute() {
+        exe.to_path_buf()
+    } else {
+        let base = config
+            .get("game")
+            .and_then(|g| g.get("working_dir"))
+            .and_then(|w| w.as_str())
+            .or(install_path)?;
+        Path::new(base).join(exe)
+    };
+    full.exists().then(|| full.to_string_lossy().to_string())
+}
+
+/// Map a Lutris runner (falling back to its platform label) to our `platform`.
+pub fn lutris_platform(runner: Option<&str>, platform: Option<&str>) -> String {
+    let by_runner = match runner.unwrap_or_default() {
+        "linux" | "wine" | "steam" | "flatpak" | "scummvm" | "web" => Some("PC"),
+        "dosbox" => Some("DOS"),
+        "fceux" | "nestopia" => Some("NES"),
+        "snes9x" | "bsnes" => Some("SNES"),
+        "mupen64plus" => Some("N64"),
+        "dolphin" => Some("GameCube"),
+        "cemu" => Some("Wii U"),
+        "yuzu" | "ryujinx" => Some("Switch"),
+        "mgba" | "vbam" => Some("GBA"),
+        "desmume" | "melonds" => Some("DS"),
+        "citra" => Some("3DS"),
+        "pcsxr" | "duckstation" => Some("PS1"),
+        "pcsx2" => Some("PS2"),
+        "rpcs3" => Some("PS3"),
+        "ppsspp" => Some("PSP"),
+        "reicast" | "redream" | "flycast" => Some("Dreamcast"),
+        "dgen" => Some("Genesis"),
+        "xemu" => Some("Xbox"),
+        "mame" => Some("Arcade"),
+        _ => None,
+    };
+    if let Some(p) = by_runner {
+        return p.to_string();
+    }
+
+    // Multi-system runners (libretro, mednafen, ...) only tell us via the
+    // label. Whole words are matched so "Genesis" doesn't read as "nes".
+    let label = platform.unwrap_or_default().to_lowercase();
+    let words: Vec<&str> = label
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let by_label = [
+        ("linux", "PC"),
+        ("windows", "PC"),
+        ("super nintendo", "SNES"),
+        ("snes", "SNES"),
+        ("nintendo 64", "N64"),
+        ("gamecube", "GameCube"),
+        ("game boy advance", "GBA"),
+        ("nintendo ds", "DS"),
+        ("nintendo switch", "Switch"),
+        ("nintendo entertainment system", "NES"),
+        ("famicom", "NES"),
+        ("nes", "NES"),
+        ("playstation 2", "PS2"),
+        ("playstation 3", "PS3"),
+        ("playstation portable", "PSP"),
+        ("playstation", "PS1"),
+        ("genesis", "Genesis"),
+        ("mega drive", "Genesis"),
+        ("dreamcast", "Dreamcast"),
+        ("arcade", "Arcade"),
+    ]
+    .iter()
+    .find(|(needle, _)| {
+        let needle: Vec<&str> = needle.split(' ').collect();
+        words.windows(needle.len()).any(|w| w == needle.as_slice())
+    })
+    .map(|(_, p)| *p);
+
+    by_label.unwrap_or("Emulator").to_string()
+}
@@ -2,8 +2,220 @@
 //!
 //! Each indexer scans a source-specific installation database and returns
 //! normalized [`crate::models::DiscoveredGame`] entries for upsert.
+//!
+//! Indexers implement [`LauncherIndexer`] and are collected in an
+//! [`IndexerRegistry`], which the service layer iterates when indexing.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::{AppSettings, DiscoveredGame, IndexerInfo};
 
+/// Amazon Games app SQLite store indexer.
+pub mod amazon;
+/// Battle.net `product.db` indexer.
+pub mod battlenet;
+/// Standalone emulator ROM folders with per-system rules.
+pub mod emulator;
 /// Epic Games Store manifest indexer.
 pub mod epic;
+/// Scored main-executable detection shared by indexers.
+pub mod exe_detect;
+/// User-configured game folders (DRM-free/portable installs).
+pub mod folder;
+/// GOG Galaxy 2.0 database indexer.
+pub mod gog;
+/// Heroic Games Launcher (legendary/GOG/nile) indexer.
+pub mod heroic;
+/// itch.io app `butler.db` indexer.
+pub mod itch;
+/// Lutris `pga.db` indexer.
+pub mod lutris;
+/// RetroArch `.lpl` playlist indexer with runtime log import.
+pub mod retroarch;
+/// ROM identification against No-Intro/Redump DAT files.
+pub mod rom_id;
 /// Steam library indexer.
 pub mod steam;
+/// Non-Steam shortcuts (`shortcuts.vdf`) indexer.
+pub mod steam_shortcuts;
+/// Emulated system name to platform mapping.
+pub mod systems;
+/// Wine/Proton/Bottles prefix discovery and path translation.
+pub mod wine;
+
+/// Error type returned by launcher scans.
+pub type IndexerError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A launcher-specific game discovery backend.
+///
+/// The value returned by [`LauncherIndexer::id`] is also the `source` value
+/// written to every [`DiscoveredGame`] the indexer produces.
+pub trait LauncherIndexer: Send + Sync {
+    /// Stable identifier stored in `games.source` (e.g. `"steam"`).
+    fn id(&self) -> &'static str;
+
+    /// Human-readable launcher name shown in the UI.
+    fn display_name(&self) -> &'static str;
+
+    /// Whether the launcher appears to be installed on this machine.
+    fn is_available(&self) -> bool;
+
+    /// Scan the launcher and return every installed game it knows about.
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError>;
+
+    /// Directories whose contents change when games are installed or
+    /// removed. Watched to re-index this source automatically.
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Whether [`LauncherIndexer::watch_paths`] are watched with their
+    /// subdirectories, for indexers whose scan descends into them.
+    fn watch_recursive(&self) -> bool {
+        false
+    }
+
+    /// Games behind the launcher files at `changed` (under
+    /// [`LauncherIndexer::watch_paths`]), to re-index after a watched change
+    /// without a full scan. `None` means the change can't be narrowed down
+    /// (a removed manifest, an unknown file) and a full
+    /// [`LauncherIndexer::scan`] is needed.
+    fn scan_changed(
+        &self,
+        _changed: &[PathBuf],
+    ) -> Option<Result<Vec<DiscoveredGame>, IndexerError>> {
+        None
+    }
+}
+
+/// Ordered collection of indexers with per-source enable flags.
+pub struct IndexerRegistry {
+    indexers: Vec<Arc<dyn LauncherIndexer>>,
+    disabled: HashSet<String>,
+}
+
+impl IndexerRegistry {
+    /// Create a registry with no indexers.
+    pub fn empty() -> Self {
+        Self {
+            indexers: Vec::new(),
+            disabled: HashSet::new(),
+        }
+    }
+
+    /// Build the registry of built-in indexers configured from user settings.
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self::from_settings_cached(settings, None)
+    }
+
+    /// [`IndexerRegistry::from_settings`], with indexers that can keep
+    /// state between scans (ROM hashes) storing it under `cache_dir`.
+    pub fn from_settings_cached(settings: &AppSettings, cache_dir: Option<&Path>) -> Self {
+        let hash_cache = |id: &str| cache_dir.map(|d| d.join(format!("{}_rom_hashes.json", id)));
+        let mut registry = Self::empty();
+        registry.register(Box::new(steam::SteamIndexer {
+            account: settings.steam_account.clone(),
+        }));
+        registry.register(Box::new(steam_shortcuts::SteamShortcutIndexer));
+        registry.register(Box::new(epic::EpicIndexer::new(&settings.epic_manifest_dirs)));
+        registry.register(Box::new(gog::GogIndexer::default()));
+        registry.register(Box::new(heroic::HeroicIndexer::default()));
+        registry.register(Box::new(lutris::LutrisIndexer::default()));
+        registry.register(Box::new(itch::ItchIndexer::default()));
+        registry.register(Box::new(amazon::AmazonIndexer::default()));
+        registry.register(Box::new(battlenet::BattleNetIndexer::new(
+            &settings.battlenet_titles,
+        )));
+        registry.register(Box::new(retroarch::RetroArchIndexer {
+            dat_files: settings.dat_files.iter().map(PathBuf::from).collect(),
+            hash_cache: hash_cache("retroarch"),
+            ..Default::default()
+        }));
+        registry.register(Box::new(folder::FolderIndexer {
+            roots: settings.game_folders.iter().map(PathBuf::from).collect(),
+        }));
+        registry.register(Box::new(emulator::EmulatorIndexer {
+            rules: settings.rom_folders.clone(),
+            dat_files: settings.dat_files.iter().map(PathBuf::from).collect(),
+            hash_cache: hash_cache("emulator"),
+        }));
+
+        for id in &settings.disabled_indexers {
+            registry.set_enabled(id, false);
+        }
+        registry
+    }
+
+    /// Add an indexer, replacing any existing indexer with the same id.
+    pub fn register(&mut self, indexer: Box<dyn LauncherIndexer>) {
+        let indexer: Arc<dyn LauncherIndexer> = Arc::from(indexer);
+        match self.indexers.iter().position(|i| i.id() == indexer.id()) {
+            Some(pos) => self.indexers[pos] = indexer,
+            None => self.indexers.push(indexer),
+        }
+    }
+
+    /// Look up an indexer by id, regardless of whether it is enabled.
+    pub fn get(&self, id: &str) -> Option<&dyn LauncherIndexer> {
+        self.indexers
+            .iter()
+            .find(|i| i.id() == id)
+            .map(|i| i.as_ref())
+    }
+
+    /// [`IndexerRegistry::get`] as a shared handle that outlives the
+    /// registry borrow, for scanning without holding the service.
+    pub fn get_shared(&self, id: &str) -> Option<Arc<dyn LauncherIndexer>> {
+        self.indexers.iter().find(|i| i.id() == id).cloned()
+    }
+
+    /// Enable or disable an indexer by id.
+    pub fn set_enabled(&mut self, id: &str, enabled: bool) {
+        if enabled {
+            self.disabled.remove(id);
+        } else {
+            self.disabled.insert(id.to_string());
+        }
+    }
+
+    /// Whether the indexer with `id` takes part in full indexing runs.
+    pub fn is_enabled(&self, id: &str) -> bool {
+        !self.disabled.contains(id)
+    }
+
+    /// Iterate enabled indexers in registration order.
+    pub fn enabled(&self) -> impl Iterator<Item = &dyn LauncherIndexer> {
+        self.indexers
+            .iter()
+            .filter(|i| self.is_enabled(i.id()))
+            .map(|i| i.as_ref())
+    }
+
+    /// Describe all registered indexers for the frontend.
+    pub fn list(&self) -> Vec<IndexerInfo> {
+        self.indexers
+            .iter()
+            .map(|i| IndexerInfo {
+                id: i.id().to_string(),
+                display_name: i.display_name().to_string(),
+                enabled: self.is_enabled(i.id()),
+                available: i.is_available(),
+            })
+            .collect()
+    }
+}
+
+impl Default for IndexerRegistry {
+    fn default() -> Self {
+        Self::from_settings(&AppSettings::default())
+    }
+}
+
+/// Format a UTC timestamp the way SQLite's `CURRENT_TIMESTAMP` does.
+pub(crate) fn sqlite_timestamp(dt: DateTime<Utc>) -> String {
+    dt.format("%Y-%m-%d %H:%M:%S").to_string()
+}
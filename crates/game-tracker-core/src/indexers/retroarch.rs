@@ -0,0 +1,442 @@
+//! RetroArch playlist indexer.
+//!
+//! Reads `.lpl` playlists (JSON, or the six-lines-per-entry format of
+//! RetroArch before 1.7.6) from RetroArch's playlist directory (one per
+//! system, e.g. `Sony - PlayStation.lpl`) and the per-content runtime logs
+//! (`.lrtl`) RetroArch writes under `playlists/logs/` when runtime logging is
+//! enabled. Config directories:
+//! - Linux: `~/.config/retroarch` (or the Flatpak equivalent)
+//! - Windows: `%APPDATA%\RetroArch`
+//!
+//! When DAT files are configured, entries are identified by CRC32 (from the
+//! playlist, or by hashing the ROM) and take their title and platform from
+//! the DAT instead of the playlist label.
+
+use crate::indexers::rom_id::RomDatabase;
+use crate::indexers::{sqlite_timestamp, systems, IndexerError, LauncherIndexer};
+use crate::models::DiscoveredGame;
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// [`LauncherIndexer`] for RetroArch playlists.
+pub struct RetroArchIndexer {
+    /// RetroArch config directories (containing `retroarch.cfg`).
+    pub config_dirs: Vec<PathBuf>,
+    /// Logiqx DAT files used to identify playlist entries.
+    pub dat_files: Vec<PathBuf>,
+    /// Sidecar file caching ROM hashes between scans.
+    pub hash_cache: Option<PathBuf>,
+}
+
+impl Default for RetroArchIndexer {
+    fn default() -> Self {
+        Self {
+            config_dirs: default_config_dirs(),
+            dat_files: Vec::new(),
+            hash_cache: None,
+        }
+    }
+}
+
+impl LauncherIndexer for RetroArchIndexer {
+    fn id(&self) -> &'static str {
+        "retroarch"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "RetroArch"
+    }
+
+    fn is_available(&self) -> bool {
+        self.config_dirs.iter().any(|d| playlist_dir(d).is_dir())
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        let roms = RomDatabase::load_cached(&self.dat_files, self.hash_cache.as_deref());
+        let mut games: Vec<DiscoveredGame> = Vec::new();
+        for dir in &self.config_dirs {
+            for game in scan_retroarch_with(dir, &roms)? {
+                if !games.iter().any(|g| g.source_id == game.source_id) {
+                    games.push(game);
+                }
+            }
+        }
+        roms.save_hash_cache();
+        Ok(games)
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.config_dirs
+            .iter()
+            .map(|d| playlist_dir(d))
+            .filter(|d| d.is_dir())
+            .collect()
+    }
+}
+
+/// Native and Flatpak config directories on Linux, `%APPDATA%` on Windows.
+pub(crate) fn default_config_dirs() -> Vec<PathBuf> {
+    if cfg!(target_os = "windows") {
+        return std::env::var_os("APPDATA")
+            .map(|a| vec![PathBuf::from(a).join("RetroArch")])
+            .unwrap_or_default();
+    }
+
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"));
+
+    vec![
+        config_home.join("retroarch"),
+        home.join(".var/app/org.libretro.RetroArch/config/retroarch"),
+    ]
+}
+
+/// Top-level fields of a JSON playlist.
+#[derive(Debug, Deserialize)]
+struct Playlist {
+    #[serde(default)]
+    items: Vec<PlaylistEntry>,
+}
+
+/// One playlist entry.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlaylistEntry {
+    /// Content path; archives use `archive.zip#member.ext`.
+    pub path: Option<String>,
+    pub label: Option<String>,
+    /// Core assigned to the entry, or `DETECT`.
+    pub core_path: Option<String>,
+    /// Database playlist the entry was matched against (`<system>.lpl`).
+    pub db_name: Option<String>,
+    /// `<hex>|crc`, or `DETECT` when not computed.
+    pub crc32: Option<String>,
+}
+
+impl PlaylistEntry {
+    /// CRC32 of the content, when the scanner recorded one.
+    pub fn crc32(&self) -> Option<u32> {
+        let hex = self.crc32.as_deref()?.split('|').next()?;
+        u32::from_str_radix(hex, 16).ok().filter(|crc| *crc != 0)
+    }
+
+    /// Core the entry runs with, unless RetroArch is left to detect one.
+    pub fn core(&self) -> Option<&str> {
+        self.core_path
+            .as_deref()
+            .filter(|core| !core.is_empty() && *core != "DETECT")
+    }
+}
+
+/// Core a playlist under `config_dir` runs the content at `rom` with.
+pub fn playlist_core(config_dir: &Path, rom: &str) -> Option<String> {
+    let mut playlists: Vec<PathBuf> = std::fs::read_dir(playlist_dir(config_dir))
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "lpl"))
+        .collect();
+    playlists.sort();
+    playlists
+        .iter()
+        .filter_map(|p| read_playlist(p).ok())
+        .flatten()
+        .find(|entry| entry.path.as_deref() == Some(rom) && entry.core().is_some())
+        .and_then(|entry| entry.core().map(str::to_string))
+}
+
+/// Program and leading arguments that run the RetroArch owning
+/// `config_dir`: the Flatpak, a portable install keeping its executable
+/// next to `retroarch.cfg`, or `retroarch` from `PATH`.
+pub fn retroarch_program(config_dir: &Path) -> (PathBuf, Vec<String>) {
+    if config_dir.to_string_lossy().contains(".var/app/org.libretro.RetroArch") {
+        return (
+            PathBuf::from("flatpak"),
+            vec!["run".to_string(), "org.libretro.RetroArch".to_string()],
+        );
+    }
+    let exe = if cfg!(target_os = "windows") { "retroarch.exe" } else { "retroarch" };
+    let portable = config_dir.join(exe);
+    if portable.is_file() {
+        return (portable, Vec::new());
+    }
+    (PathBuf::from(exe), Vec::new())
+}
+
+/// Read the entries of one `.lpl` playlist, JSON or legacy.
+pub fn read_playlist(path: &Path) -> Result<Vec<PlaylistEntry>, IndexerError> {
+    let contents = std::fs::read_to_string(path)?;
+    if contents.trim_start().starts_with('{') {
+        let playlist: Playlist = serde_json::from_str(&contents)?;
+        return Ok(playlist.items);
+    }
+    parse_legacy_playlist(&contents)
+}
+
+/// Legacy playlist: six lines per entry (path, label, core path, core name,
+/// CRC32, database name).
+fn parse_legacy_playlist(contents: &str) -> Result<Vec<PlaylistEntry>, IndexerError> {
+    let lines: Vec<&str> = contents.trim_end_matches(['\r', '\n']).lines().collect();
+    if !lines.len().is_multiple_of(6) {
+        return Err(format!("legacy playlist has {} lines, not 6 per entry", lines.len()).into());
+    }
+    let field = |line: &str| Some(line.to_string()).filter(|l| !l.is_empty());
+    Ok(lines
+        .chunks(6)
+        .map(|entry| PlaylistEntry {
+            path: field(entry[0]),
+            label: field(entry[1]),
+            core_path: field(entry[2]),
+            crc32: field(entry[4]),
+            db_name: field(entry[5]),
+        })
+        .collect())
+}
+
+/// Scan the default RetroArch config directories.
+pub fn scan_retroarch_games() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    RetroArchIndexer::default().scan()
+}
+
+/// Scan playlists and runtime logs of one RetroArch config directory
+/// (useful for testing).
+pub fn scan_retroarch_from(config_dir: &Path) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    scan_retroarch_with(config_dir, &RomDatabase::default())
+}
+
+/// Like [`scan_retroarch_from`], identifying entries against `roms`.
+pub fn scan_retroarch_with(
+    config_dir: &Path,
+    roms: &RomDatabase,
+) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games = Vec::new();
+    let playlists = playlist_dir(config_dir);
+
+    if !playlists.is_dir() {
+        tracing::warn!("RetroArch playlist directory not found: {:?}", playlists);
+        return Ok(games);
+    }
+
+    let runtimes = read_runtime_logs(&runtime_log_dir(config_dir, &playlists));
+
+    for entry in std::fs::read_dir(&playlists)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("lpl") {
+            continue;
+        }
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // History and favourites repeat entries from the system playlists
+        if name.starts_with("content_") || name.starts_with("builtin_") {
+            continue;
+        }
+
+        // Skipping a playlist would read as its games being uninstalled
+        let entries = read_playlist(&path)
+            .map_err(|e| format!("Failed to parse RetroArch playlist {:?}: {}", path, e))?;
+
+        for entry in entries {
+            if let Some(game) = playlist_entry_to_game(&name, entry, &runtimes, roms) {
+                games.push(game);
+            }
+        }
+    }
+
+    Ok(games)
+}
+
+fn playlist_entry_to_game(
+    playlist_name: &str,
+    entry: PlaylistEntry,
+    runtimes: &HashMap<String, Runtime>,
+    roms: &RomDatabase,
+) -> Option<DiscoveredGame> {
+    let rom = entry.path.clone().filter(|p| !p.is_empty())?;
+    let content = content_name(&rom);
+
+    let title = entry
+        .label
+        .as_deref()
+        .map(strip_tags)
+        .filter(|l| !l.is_empty())
+        .unwrap_or_else(|| strip_tags(&content));
+    if title.is_empty() {
+        return None;
+    }
+
+    // Per-system playlists name the system; mixed ones rely on db_name
+    let platform = systems::platform_for_system(playlist_name)
+        .or_else(|| {
+            entry
+                .db_name
+                .as_deref()
+                .and_then(systems::platform_for_system)
+        })
+        .unwrap_or("Emulator");
+
+    let runtime = runtimes.get(&content);
+    let mut game = DiscoveredGame {
+        title,
+        platform: platform.to_string(),
+        exe_path: None,
+        install_path: Some(rom.clone()),
+        source: "retroarch".to_string(),
+        source_id: rom.clone(),
+        playtime_hours: runtime
+            .map(|r| r.seconds as f32 / 3600.0)
+            .filter(|h| *h > 0.0),
+        last_played: runtime.and_then(|r| r.last_played.clone()),
+    };
+
+    // Archive members (`game.zip#rom.sfc`) can only be matched by the
+    // playlist CRC; plain files are hashed when the playlist has none
+    let identity = entry.crc32().and_then(|crc| roms.identify_crc(crc)).or_else(|| {
+        (!rom.contains('#'))
+            .then(|| roms.identify(Path::new(&rom)))
+            .flatten()
+    });
+    if let Some(identity) = identity {
+        identity.apply_to(&mut game);
+    }
+    Some(game)
+}
+
+/// Content name RetroArch uses for runtime logs: the file name without
+/// extension, taken from the archive member for `archive.zip#rom.sfc` paths.
+fn content_name(rom: &str) -> String {
+    let file = rom.rsplit('#').next().unwrap_or(rom);
+    let file = file.rsplit(['/', '\\']).next().unwrap_or(file);
+    match file.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem.to_string(),
+        _ => file.to_string(),
+    }
+}
+
+/// Drop region/revision tags: `Chrono Trigger (USA) [!]` -> `Chrono Trigger`.
+fn strip_tags(label: &str) -> String {
+    let end = [label.find(" ("), label.find(" [")]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(label.len());
+    label[..end].trim().to_string()
+}
+
+/// Playtime recorded in `.lrtl` logs for one content name.
+#[derive(Debug, Default)]
+struct Runtime {
+    seconds: u64,
+    last_played: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeLog {
+    runtime: Option<String>,
+    last_played: Option<String>,
+}
+
+/// Read runtime logs, keyed by content name.
+///
+/// The aggregate log (`logs/<content>.lrtl`) wins when present; otherwise
+/// per-core logs (`logs/<core>/<content>.lrtl`) are summed.
+fn read_runtime_logs(logs_dir: &Path) -> HashMap<String, Runtime> {
+    let mut aggregate = HashMap::new();
+    let mut per_core: HashMap<String, Runtime> = HashMap::new();
+
+    let Ok(entries) = std::fs::read_dir(logs_dir) else {
+        return aggregate;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let Ok(core_logs) = std::fs::read_dir(&path) else {
+                continue;
+            };
+            for log in core_logs.flatten() {
+                if let Some((content, runtime)) = read_runtime_log(&log.path()) {
+                    let total = per_core.entry(content).or_default();
+                    total.seconds += runtime.seconds;
+                    total.last_played = total.last_played.take().max(runtime.last_played);
+                }
+            }
+        } else if let Some((content, runtime)) = read_runtime_log(&path) {
+            aggregate.insert(content, runtime);
+        }
+    }
+
+    for (content, runtime) in per_core {
+        aggregate.entry(content).or_insert(runtime);
+    }
+    aggregate
+}
+
+fn read_runtime_log(path: &Path) -> Option<(String, Runtime)> {
+    if path.extension().and_then(|e| e.to_str()) != Some("lrtl") {
+        return None;
+    }
+    let content = path.file_stem()?.to_string_lossy().to_string();
+    let log: RuntimeLog = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+
+    let seconds = log.runtime.as_deref().map(parse_runtime).unwrap_or(0);
+    // RetroArch writes local time
+    let last_played = log
+        .last_played
+        .as_deref()
+        .and_then(|lp| NaiveDateTime::parse_from_str(lp, "%Y-%m-%d %H:%M:%S").ok())
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+        .map(|dt| sqlite_timestamp(dt.with_timezone(&Utc)));
+
+    Some((
+        content,
+        Runtime {
+            seconds,
+            last_played,
+        },
+    ))
+}
+
+/// `H:MM:SS` to seconds.
+fn parse_runtime(value: &str) -> u64 {
+    value
+        .split(':')
+        .map(|part| part.trim().parse::<u64>().unwrap_or(0))
+        .fold(0, |acc, part| acc * 60 + part)
+}
+
+/// Playlist directory from `retroarch.cfg`, defaulting to `<config>/playlists`.
+fn playlist_dir(config_dir: &Path) -> PathBuf {
+    cfg_dir(config_dir, "playlist_directory").unwrap_or_else(|| config_dir.join("playlists"))
+}
+
+/// Runtime log directory from `retroarch.cfg`, defaulting to `<playlists>/logs`.
+fn runtime_log_dir(config_dir: &Path, playlists: &Path) -> PathBuf {
+    cfg_dir(config_dir, "runtime_log_directory").unwrap_or_else(|| playlists.join("logs"))
+}
+
+/// Read a directory setting from `retroarch.cfg`. `default` means unset,
+/// `~` is the home directory and a leading `:` is the config directory.
+fn cfg_dir(config_dir: &Path, key: &str) -> Option<PathBuf> {
+    let cfg = std::fs::read_to_string(config_dir.join("retroarch.cfg")).ok()?;
+    let value = cfg.lines().find_map(|line| {
+        let (k, v) = line.split_once('=')?;
+        (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+    })?;
+
+    if value.is_empty() || value == "default" {
+        None
+    } else if let Some(rest) = value.strip_prefix('~') {
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+        Some(PathBuf::from(home).join(rest.trim_start_matches(['/', '\\'])))
+    } else if let Some(rest) = value.strip_prefix(':') {
+        Some(config_dir.join(rest.trim_start_matches(['/', '\\'])))
+    } else {
+        Some(PathBuf::from(value))
+    }
+}
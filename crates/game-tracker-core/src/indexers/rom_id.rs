@@ -0,0 +1,409 @@
+//! ROM identification against No-Intro / Redump DAT files.
+//!
+//! Loads Logiqx XML DAT files supplied by the user, hashes ROM files (CRC32
+//! and SHA1) and resolves the canonical title, region and system of a dump.
+//! Cartridge dumps with a copier/emulator header (iNES, FDS, Lynx, SNES
+//! copier) are also hashed without it, since No-Intro lists headerless data.
+//! Hashes are cached by path, size and modification time, optionally in a
+//! JSON sidecar file, so unchanged ROMs are only read once.
+
+use crate::indexers::{systems, IndexerError};
+use crate::models::DiscoveredGame;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Canonical identity of a dump, as listed in a DAT file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RomIdentity {
+    /// Title without tags, articles moved to the front:
+    /// `The Legend of Zelda - A Link to the Past`.
+    pub title: String,
+    /// Region tag such as `USA` or `Japan, USA`.
+    pub region: Option<String>,
+    /// DAT system name, e.g. `Nintendo - Super Nintendo Entertainment System`.
+    pub system: String,
+    /// Our `platform` value for `system`, when known.
+    pub platform: Option<&'static str>,
+    /// Full DAT entry name, tags included.
+    pub dat_name: String,
+}
+
+impl RomIdentity {
+    /// Use the canonical title, and the platform when the system is known.
+    pub fn apply_to(&self, game: &mut DiscoveredGame) {
+        game.title = self.title.clone();
+        if let Some(platform) = self.platform {
+            game.platform = platform.to_string();
+        }
+    }
+}
+
+/// CRC32 and SHA1 of a file's contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RomHashes {
+    pub crc32: u32,
+    pub sha1: [u8; 20],
+}
+
+/// Identity index and SHA1 (when listed) of a ROM with a given CRC32.
+type CrcEntry = (usize, Option<[u8; 20]>);
+
+/// In-memory index of one or more DAT files.
+#[derive(Debug, Default)]
+pub struct RomDatabase {
+    identities: Vec<RomIdentity>,
+    by_crc: HashMap<u32, Vec<CrcEntry>>,
+    by_sha1: HashMap<[u8; 20], usize>,
+    sizes: HashSet<u64>,
+    hashes: RomHashCache,
+}
+
+impl RomDatabase {
+    /// Load every DAT in `paths`; unreadable files are logged and skipped.
+    pub fn load(paths: &[PathBuf]) -> Self {
+        let mut db = Self::default();
+        for path in paths {
+            if let Err(e) = db.load_dat(path) {
+                tracing::warn!("Failed to load DAT file {:?}: {}", path, e);
+            }
+        }
+        db
+    }
+
+    /// [`RomDatabase::load`], reusing ROM hashes stored in `hash_cache`.
+    pub fn load_cached(paths: &[PathBuf], hash_cache: Option<&Path>) -> Self {
+        let db = Self::load(paths);
+        match hash_cache {
+            // Nothing gets hashed without DAT entries
+            Some(file) if !db.is_empty() => db.with_hash_cache(RomHashCache::open(file)),
+            _ => db,
+        }
+    }
+
+    /// Reuse (and record) ROM hashes in `cache`.
+    pub fn with_hash_cache(mut self, cache: RomHashCache) -> Self {
+        self.hashes = cache;
+        self
+    }
+
+    /// The hashes computed or reused by [`RomDatabase::identify`].
+    pub fn hash_cache(&self) -> &RomHashCache {
+        &self.hashes
+    }
+
+    /// Persist the hash cache after a scan; failures only cost a re-hash.
+    pub fn save_hash_cache(&self) {
+        if let Err(e) = self.hashes.save() {
+            tracing::warn!("Failed to save ROM hash cache: {}", e);
+        }
+    }
+
+    /// Add the entries of one Logiqx XML DAT file. Returns the number of ROMs added.
+    pub fn load_dat(&mut self, path: &Path) -> Result<usize, IndexerError> {
+        let xml = std::fs::read_to_string(path)?;
+        // No-Intro DATs carry a Logiqx DOCTYPE
+        let options = roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        };
+        let doc = roxmltree::Document::parse_with_options(&xml, options)?;
+        let root = doc.root_element();
+
+        let system = root
+            .children()
+            .find(|n| n.has_tag_name("header"))
+            .and_then(|h| h.children().find(|n| n.has_tag_name("name")))
+            .and_then(|n| n.text())
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                path.file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            });
+        let platform = systems::platform_for_system(&system);
+
+        let mut added = 0;
+        // MAME-style DATs use <machine> instead of <game>
+        for game in root
+            .children()
+            .filter(|n| n.has_tag_name("game") || n.has_tag_name("machine"))
+        {
+            let Some(name) = game.attribute("name") else {
+                continue;
+            };
+            let index = self.identities.len();
+            self.identities.push(RomIdentity {
+                title: canonical_title(name),
+                region: region_tag(name),
+                system: system.clone(),
+                platform,
+                dat_name: name.to_string(),
+            });
+
+            for rom in game.children().filter(|n| n.has_tag_name("rom")) {
+                let sha1 = rom.attribute("sha1").and_then(parse_sha1);
+                if let Some(crc) = rom
+                    .attribute("crc")
+                    .and_then(|c| u32::from_str_radix(c, 16).ok())
+                {
+                    self.by_crc.entry(crc).or_default().push((index, sha1));
+                }
+                if let Some(sha1) = sha1 {
+                    self.by_sha1.entry(sha1).or_insert(index);
+                }
+                if let Some(size) = rom.attribute("size").and_then(|s| s.parse().ok()) {
+                    self.sizes.insert(size);
+                }
+                added += 1;
+            }
+        }
+
+        Ok(added)
+    }
+
+    /// Whether no DAT entries are loaded.
+    pub fn is_empty(&self) -> bool {
+        self.identities.is_empty()
+    }
+
+    /// Look up a CRC32 (e.g. from a RetroArch playlist) without hashing.
+    pub fn identify_crc(&self, crc32: u32) -> Option<&RomIdentity> {
+        let (index, _) = self.by_crc.get(&crc32)?.first()?;
+        self.identities.get(*index)
+    }
+
+    /// Look up precomputed hashes; SHA1 wins, CRC32 is the fallback.
+    pub fn identify_hashes(&self, hashes: &RomHashes) -> Option<&RomIdentity> {
+        if let Some(index) = self.by_sha1.get(&hashes.sha1) {
+            return self.identities.get(*index);
+        }
+        let candidates = self.by_crc.get(&hashes.crc32)?;
+        // Prefer an entry whose SHA1 agrees, else any entry without one
+        let (index, _) = candidates
+            .iter()
+            .find(|(_, sha1)| *sha1 == Some(hashes.sha1))
+            .or_else(|| candidates.iter().find(|(_, sha1)| sha1.is_none()))?;
+        self.identities.get(*index)
+    }
+
+    /// Hash a ROM file and look it up, with and without a known header.
+    ///
+    /// Files whose size matches no DAT entry are not hashed at all.
+    pub fn identify(&self, rom: &Path) -> Option<&RomIdentity> {
+        if self.is_empty() {
+            return None;
+        }
+        let size = std::fs::metadata(rom).ok()?.len();
+        let header = header_len(rom, size);
+        let sizes = [Some(size), header.and_then(|h| size.checked_sub(h))];
+        if !sizes.iter().flatten().any(|s| self.sizes.contains(s)) && !self.sizes.is_empty() {
+            return None;
+        }
+
+        let (full, headerless) = self.hashes.hash(rom).ok()?;
+        headerless
+            .and_then(|h| self.identify_hashes(&h))
+            .or_else(|| self.identify_hashes(&full))
+    }
+}
+
+/// Hashes of one ROM file as of its size and modification time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedHashes {
+    size: u64,
+    modified: SystemTime,
+    full: RomHashes,
+    headerless: Option<RomHashes>,
+}
+
+/// ROM hashes from earlier scans, keyed by path.
+///
+/// An entry is reused only while the file's size and modification time are
+/// unchanged. Without a file the cache lives for one scan.
+#[derive(Debug, Default)]
+pub struct RomHashCache {
+    file: Option<PathBuf>,
+    entries: Mutex<HashMap<PathBuf, CachedHashes>>,
+}
+
+impl RomHashCache {
+    /// Load the cache stored in `file`; a missing or unreadable file starts empty.
+    pub fn open(file: &Path) -> Self {
+        let entries = match std::fs::read_to_string(file) {
+            Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring corrupt ROM hash cache {:?}: {}", file, e);
+                HashMap::new()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => {
+                tracing::warn!("Failed to read ROM hash cache {:?}: {}", file, e);
+                HashMap::new()
+            }
+        };
+        Self {
+            file: Some(file.to_path_buf()),
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Number of cached ROMs.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Whether no ROM hashes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// [`hash_rom`], reusing the cached result while the file is unchanged.
+    pub fn hash(&self, rom: &Path) -> std::io::Result<(RomHashes, Option<RomHashes>)> {
+        let meta = std::fs::metadata(rom)?;
+        let modified = meta.modified()?;
+        if let Some(cached) = self.entries.lock().unwrap().get(rom) {
+            if cached.size == meta.len() && cached.modified == modified {
+                return Ok((cached.full, cached.headerless));
+            }
+        }
+
+        let (full, headerless) = hash_rom(rom)?;
+        self.entries.lock().unwrap().insert(
+            rom.to_path_buf(),
+            CachedHashes {
+                size: meta.len(),
+                modified,
+                full,
+                headerless,
+            },
+        );
+        Ok((full, headerless))
+    }
+
+    /// Write the cache back to its file, dropping ROMs that no longer exist.
+    pub fn save(&self) -> Result<(), IndexerError> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        let mut entries = self.entries.lock().unwrap();
+        // JSON keys must be strings
+        entries.retain(|rom, _| rom.to_str().is_some() && rom.is_file());
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(file, serde_json::to_string(&*entries)?)?;
+        Ok(())
+    }
+}
+
+/// Hash a ROM: the whole file, plus the data after its header if it has one.
+pub fn hash_rom(rom: &Path) -> std::io::Result<(RomHashes, Option<RomHashes>)> {
+    let size = std::fs::metadata(rom)?.len();
+    let header = header_len(rom, size).unwrap_or(0) as usize;
+
+    let mut file = std::fs::File::open(rom)?;
+    let mut full = (crc32fast::Hasher::new(), sha1_smol::Sha1::new());
+    let mut body = (crc32fast::Hasher::new(), sha1_smol::Sha1::new());
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut offset = 0usize;
+
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        let chunk = &buf[..read];
+        full.0.update(chunk);
+        full.1.update(chunk);
+
+        if header > 0 && offset + read > header {
+            let start = header.saturating_sub(offset);
+            body.0.update(&chunk[start..]);
+            body.1.update(&chunk[start..]);
+        }
+        offset += read;
+    }
+
+    let full = RomHashes {
+        crc32: full.0.finalize(),
+        sha1: full.1.digest().bytes(),
+    };
+    let headerless = (header > 0).then(|| RomHashes {
+        crc32: body.0.finalize(),
+        sha1: body.1.digest().bytes(),
+    });
+    Ok((full, headerless))
+}
+
+/// Length of a copier/emulator header on a cartridge dump, if present.
+fn header_len(rom: &Path, size: u64) -> Option<u64> {
+    let mut magic = [0u8; 4];
+    std::fs::File::open(rom).ok()?.read_exact(&mut magic).ok()?;
+
+    let ext = rom
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let header = match &magic {
+        b"NES\x1A" | b"FDS\x1A" => 16,
+        b"LYNX" => 64,
+        // SNES copier headers pad the image to 512 bytes over a multiple of 1 KiB
+        _ if matches!(ext.as_str(), "sfc" | "smc" | "swc" | "fig") && size % 1024 == 512 => 512,
+        _ => return None,
+    };
+    // Magic bytes on a file too short for the header are a coincidence
+    size.checked_sub(header).filter(|body| *body > 0)?;
+    Some(header)
+}
+
+fn parse_sha1(hex: &str) -> Option<[u8; 20]> {
+    if hex.len() != 40 {
+        return None;
+    }
+    let mut out = [0u8; 20];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(out)
+}
+
+/// `Legend of Zelda, The - A Link to the Past (USA) (Rev 1)` ->
+/// `The Legend of Zelda - A Link to the Past`.
+pub fn canonical_title(dat_name: &str) -> String {
+    let end = [dat_name.find(" ("), dat_name.find(" [")]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(dat_name.len());
+    let base = dat_name[..end].trim();
+
+    // No-Intro moves leading articles behind the main title
+    let (main, subtitle) = match base.split_once(" - ") {
+        Some((main, sub)) => (main, Some(sub)),
+        None => (base, None),
+    };
+    let main = ["The", "A", "An"]
+        .iter()
+        .find_map(|article| {
+            main.strip_suffix(&format!(", {}", article))
+                .map(|rest| format!("{} {}", article, rest))
+        })
+        .unwrap_or_else(|| main.to_string());
+
+    match subtitle {
+        Some(sub) => format!("{} - {}", main, sub),
+        None => main,
+    }
+}
+
+/// First parenthesised tag of a DAT name, which No-Intro and Redump use for
+/// the region.
+fn region_tag(dat_name: &str) -> Option<String> {
+    let start = dat_name.find(" (")? + 2;
+    let end = dat_name[start..].find(')')? + start;
+    Some(dat_name[start..end].to_string()).filter(|r| !r.is_empty())
+}
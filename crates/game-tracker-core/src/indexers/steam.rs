@@ -1,64 +1,232 @@
 //! Steam game auto-indexer using the `steamlocate` crate.
+//!
+//! Playtime and last-played come from each account's
+//! `userdata/<user id>/config/localconfig.vdf`.
 
+use crate::indexers::{exe_detect, sqlite_timestamp, IndexerError, LauncherIndexer};
 use crate::models::DiscoveredGame;
+use chrono::DateTime;
+use keyvalues_parser::{Obj, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-/// Scan all Steam library folders and return discovered games.
-pub fn scan_steam_games() -> Result<Vec<DiscoveredGame>, Box<dyn std::error::Error + Send + Sync>> {
-    let steam_dir = steamlocate::SteamDir::locate()?;
+/// [`LauncherIndexer`] for the local Steam installation.
+#[derive(Default)]
+pub struct SteamIndexer {
+    /// Steam account (`userdata` folder id) to read playtime from.
+    /// `None` sums playtime across every account on the machine.
+    pub account: Option<String>,
+}
 
-    let mut games = Vec::new();
+impl LauncherIndexer for SteamIndexer {
+    fn id(&self) -> &'static str {
+        "steam"
+    }
 
-    for library in steam_dir.libraries()?.filter_map(|l| l.ok()) {
-        for app in library.apps().filter_map(|a| a.ok()) {
-            let name = match &app.name {
-                Some(n) if !n.is_empty() => n.clone(),
-                _ => continue,
-            };
+    fn display_name(&self) -> &'static str {
+        "Steam"
+    }
 
-            let app_dir = library.resolve_app_dir(&app);
-            let install_path = app_dir.to_string_lossy().to_string();
+    fn is_available(&self) -> bool {
+        steamlocate::SteamDir::locate().is_ok()
+    }
 
-            // Try to find a main .exe in the install directory
-            let exe_path = find_main_exe(&install_path);
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        let steam_dir = steamlocate::SteamDir::locate()?;
+        let playtime = scan_steam_playtime_from(steam_dir.path(), self.account.as_deref());
 
-            games.push(DiscoveredGame {
-                title: name,
-                platform: "PC".to_string(),
-                exe_path,
-                install_path: Some(install_path),
-                source: "steam".to_string(),
-                source_id: app.app_id.to_string(),
-            });
+        let mut games = Vec::new();
+
+        for library in steam_dir.libraries()?.filter_map(|l| l.ok()) {
+            for app in library.apps().filter_map(|a| a.ok()) {
+                games.extend(discovered_app(&library, &app, &playtime));
+            }
         }
+
+        Ok(games)
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        let Ok(steam_dir) = steamlocate::SteamDir::locate() else {
+            return Vec::new();
+        };
+        // `appmanifest_<appid>.acf` files live in each library's `steamapps`
+        steam_dir
+            .library_paths()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| p.join("steamapps"))
+            .filter(|p| p.is_dir())
+            .collect()
     }
 
-    Ok(games)
+    /// Steam rewrites an app's manifest while it downloads or updates; only
+    /// those apps are re-read. Other files in `steamapps` are ignored.
+    fn scan_changed(
+        &self,
+        changed: &[PathBuf],
+    ) -> Option<Result<Vec<DiscoveredGame>, IndexerError>> {
+        let mut manifests = Vec::new();
+        for path in changed {
+            let Some(app_id) = manifest_app_id(path) else {
+                continue;
+            };
+            // A removed manifest is an uninstall, which takes a full scan
+            if !path.is_file() {
+                return None;
+            }
+            manifests.push((path.parent()?.parent()?.to_path_buf(), app_id));
+        }
+
+        Some(self.scan_manifests(manifests))
+    }
 }
 
-/// Best-effort: look for a common exe in the root of the install dir.
-fn find_main_exe(install_path: &str) -> Option<String> {
-    let path = std::path::Path::new(install_path);
-    if !path.is_dir() {
-        return None;
+impl SteamIndexer {
+    /// Read the apps of `(library folder, app id)` manifests.
+    fn scan_manifests(
+        &self,
+        manifests: Vec<(PathBuf, u32)>,
+    ) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        let steam_dir = steamlocate::SteamDir::locate()?;
+        let playtime = scan_steam_playtime_from(steam_dir.path(), self.account.as_deref());
+        let mut games = Vec::new();
+        for (library_path, app_id) in manifests {
+            let library = steamlocate::Library::from_dir(&library_path)?;
+            if let Some(app) = library.app(app_id).and_then(Result::ok) {
+                games.extend(discovered_app(&library, &app, &playtime));
+            }
+        }
+        Ok(games)
     }
+}
+
+/// App id of an `appmanifest_<appid>.acf` path.
+fn manifest_app_id(path: &Path) -> Option<u32> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix("appmanifest_")?
+        .strip_suffix(".acf")?
+        .parse()
+        .ok()
+}
 
-    let entries = std::fs::read_dir(path).ok()?;
+/// [`DiscoveredGame`] for an installed app; `None` for nameless manifests.
+fn discovered_app(
+    library: &steamlocate::Library,
+    app: &steamlocate::App,
+    playtime: &HashMap<String, SteamPlaytime>,
+) -> Option<DiscoveredGame> {
+    let name = app.name.clone().filter(|n| !n.is_empty())?;
+    let app_dir = library.resolve_app_dir(app);
+    let exe_path = exe_detect::find_main_exe(&app_dir, &name);
+    let played = playtime.get(&app.app_id.to_string());
+
+    Some(DiscoveredGame {
+        title: name,
+        platform: "PC".to_string(),
+        exe_path,
+        install_path: Some(app_dir.to_string_lossy().to_string()),
+        source: "steam".to_string(),
+        source_id: app.app_id.to_string(),
+        playtime_hours: played.map(|p| p.playtime_hours),
+        last_played: played.and_then(|p| p.last_played.clone()),
+    })
+}
+
+/// Scan all Steam library folders and return discovered games.
+pub fn scan_steam_games() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    SteamIndexer::default().scan()
+}
+
+/// Playtime Steam recorded for one app.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SteamPlaytime {
+    pub playtime_hours: f32,
+    /// `YYYY-MM-DD HH:MM:SS` (UTC).
+    pub last_played: Option<String>,
+}
+
+/// Read per-app playtime from `localconfig.vdf` under a Steam root, keyed by app id.
+///
+/// With `account` set only that user's file is read; otherwise playtime is
+/// summed over all accounts and the most recent last-played wins.
+pub fn scan_steam_playtime_from(
+    steam_root: &Path,
+    account: Option<&str>,
+) -> HashMap<String, SteamPlaytime> {
+    // app id -> (minutes, last played unix time)
+    let mut totals: HashMap<String, (u64, i64)> = HashMap::new();
+
+    let Ok(entries) = std::fs::read_dir(steam_root.join("userdata")) else {
+        return HashMap::new();
+    };
     for entry in entries.flatten() {
-        let p = entry.path();
-        if p.extension().and_then(|e| e.to_str()) == Some("exe") {
-            let name = p.file_name()?.to_string_lossy().to_lowercase();
-            // Skip common non-game executables
-            if name.contains("unins")
-                || name.contains("redist")
-                || name.contains("setup")
-                || name.contains("crash")
-                || name.contains("ue4prereq")
-                || name.contains("dxsetup")
-            {
+        if account.is_some_and(|a| entry.file_name().to_string_lossy() != a) {
+            continue;
+        }
+        let path = entry.path().join("config").join("localconfig.vdf");
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let vdf = match keyvalues_parser::parse(&contents) {
+            Ok(vdf) => vdf,
+            Err(e) => {
+                tracing::warn!("Failed to parse Steam localconfig {:?}: {}", path, e);
                 continue;
             }
-            return Some(p.to_string_lossy().to_string());
+        };
+
+        let apps = ["Software", "Valve", "Steam", "apps"]
+            .iter()
+            .try_fold(&vdf.value, |value, key| obj_get(value.get_obj()?, key));
+        let Some(apps) = apps.and_then(Value::get_obj) else {
+            continue;
+        };
+
+        for (app_id, values) in apps.iter() {
+            let Some(app) = values.first().and_then(Value::get_obj) else {
+                continue;
+            };
+            let number = |key| {
+                obj_get(app, key)
+                    .and_then(Value::get_str)
+                    .and_then(|s| s.parse::<i64>().ok())
+                    .unwrap_or(0)
+            };
+            let minutes = number("Playtime").max(0) as u64;
+            let last_played = number("LastPlayed");
+            if minutes == 0 && last_played == 0 {
+                continue;
+            }
+
+            let total = totals.entry(app_id.to_string()).or_default();
+            total.0 += minutes;
+            total.1 = total.1.max(last_played);
         }
     }
-    None
+
+    totals
+        .into_iter()
+        .map(|(app_id, (minutes, last_played))| {
+            let last_played = (last_played > 0)
+                .then(|| DateTime::from_timestamp(last_played, 0))
+                .flatten()
+                .map(sqlite_timestamp);
+            (
+                app_id,
+                SteamPlaytime {
+                    playtime_hours: minutes as f32 / 60.0,
+                    last_played,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Case-insensitive key lookup; Steam writes both `apps` and `Apps`.
+fn obj_get<'a>(obj: &'a Obj<'_>, key: &str) -> Option<&'a Value<'a>> {
+    obj.iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .and_then(|(_, values)| values.first())
 }
@@ -0,0 +1,247 @@
+//! Non-Steam shortcut indexer.
+//!
+//! Games and emulators added to Steam via "Add a Non-Steam Game" live in
+//! `userdata/<user id>/config/shortcuts.vdf` as binary VDF, one file per
+//! Steam account on the machine.
+
+use crate::indexers::{sqlite_timestamp, IndexerError, LauncherIndexer};
+use crate::models::DiscoveredGame;
+use chrono::DateTime;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// [`LauncherIndexer`] for non-Steam shortcuts of every local Steam user.
+pub struct SteamShortcutIndexer;
+
+impl LauncherIndexer for SteamShortcutIndexer {
+    fn id(&self) -> &'static str {
+        "steam-shortcut"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Steam shortcuts"
+    }
+
+    fn is_available(&self) -> bool {
+        steamlocate::SteamDir::locate().is_ok()
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        scan_steam_shortcuts()
+    }
+}
+
+/// Scan shortcuts for the local Steam installation.
+pub fn scan_steam_shortcuts() -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let steam_dir = steamlocate::SteamDir::locate()?;
+    scan_steam_shortcuts_from(steam_dir.path())
+}
+
+/// Scan shortcuts under a specific Steam root directory (useful for testing).
+pub fn scan_steam_shortcuts_from(steam_root: &Path) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let mut games: Vec<DiscoveredGame> = Vec::new();
+    let userdata = steam_root.join("userdata");
+
+    if !userdata.is_dir() {
+        tracing::warn!("Steam userdata directory not found: {:?}", userdata);
+        return Ok(games);
+    }
+
+    for entry in std::fs::read_dir(&userdata)? {
+        let path = entry?.path().join("config").join("shortcuts.vdf");
+        if !path.is_file() {
+            continue;
+        }
+
+        let shortcuts = match std::fs::read(&path)
+            .map_err(IndexerError::from)
+            .and_then(|bytes| parse_shortcuts(&bytes))
+        {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::warn!("Failed to parse Steam shortcuts {:?}: {}", path, e);
+                continue;
+            }
+        };
+
+        for game in shortcuts {
+            // The same shortcut can exist for several accounts
+            if !games.iter().any(|g| g.source_id == game.source_id) {
+                games.push(game);
+            }
+        }
+    }
+
+    Ok(games)
+}
+
+fn parse_shortcuts(bytes: &[u8]) -> Result<Vec<DiscoveredGame>, IndexerError> {
+    let root = BinaryVdfReader { bytes, pos: 0 }.read_root()?;
+    let shortcuts = match root.get("shortcuts") {
+        Some(VdfValue::Map(m)) => m,
+        _ => return Err("missing `shortcuts` map".into()),
+    };
+
+    let mut games = Vec::new();
+    for shortcut in shortcuts.values() {
+        let VdfValue::Map(fields) = shortcut else {
+            continue;
+        };
+
+        let app_name = fields
+            .get("appname")
+            .and_then(VdfValue::as_str)
+            .unwrap_or_default();
+        let exe = fields
+            .get("exe")
+            .and_then(VdfValue::as_str)
+            .unwrap_or_default();
+        if app_name.is_empty() || exe.is_empty() {
+            continue;
+        }
+
+        // Older shortcuts have no stored appid; Steam derives it the same way
+        let app_id = match fields.get("appid") {
+            Some(VdfValue::Int(id)) => *id as u32,
+            _ => legacy_app_id(exe, app_name),
+        };
+
+        let start_dir = fields
+            .get("startdir")
+            .and_then(VdfValue::as_str)
+            .map(unquote)
+            .filter(|s| !s.is_empty());
+
+        let last_played = match fields.get("lastplaytime") {
+            Some(VdfValue::Int(ts)) if *ts > 0 => {
+                DateTime::from_timestamp(i64::from(*ts), 0).map(sqlite_timestamp)
+            }
+            _ => None,
+        };
+
+        games.push(DiscoveredGame {
+            title: app_name.to_string(),
+            platform: "PC".to_string(),
+            exe_path: Some(unquote(exe)),
+            install_path: start_dir,
+            source: "steam-shortcut".to_string(),
+            source_id: app_id.to_string(),
+            playtime_hours: None,
+            last_played,
+        });
+    }
+
+    Ok(games)
+}
+
+/// Steam's appid for shortcuts that predate the stored `appid` field.
+fn legacy_app_id(exe: &str, app_name: &str) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(exe.as_bytes());
+    hasher.update(app_name.as_bytes());
+    hasher.finalize() | 0x8000_0000
+}
+
+/// Steam stores paths wrapped in double quotes.
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches('"').to_string()
+}
+
+/// Value in a binary VDF document. Keys are lowercased while reading since
+/// Steam has written both `AppName` and `appname` over the years.
+enum VdfValue {
+    Map(HashMap<String, VdfValue>),
+    Str(String),
+    Int(i32),
+    Other,
+}
+
+impl VdfValue {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            VdfValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+struct BinaryVdfReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl BinaryVdfReader<'_> {
+    const MAP: u8 = 0x00;
+    const STRING: u8 = 0x01;
+    const INT32: u8 = 0x02;
+    const FLOAT32: u8 = 0x03;
+    const POINTER: u8 = 0x04;
+    const COLOR: u8 = 0x06;
+    const UINT64: u8 = 0x07;
+    const MAP_END: u8 = 0x08;
+    const INT64: u8 = 0x0A;
+
+    /// Read the top-level map; a trailing end marker is optional.
+    fn read_root(mut self) -> Result<HashMap<String, VdfValue>, IndexerError> {
+        self.read_map(true)
+    }
+
+    fn read_map(&mut self, root: bool) -> Result<HashMap<String, VdfValue>, IndexerError> {
+        let mut map = HashMap::new();
+        loop {
+            let Some(kind) = self.next_byte() else {
+                return if root {
+                    Ok(map)
+                } else {
+                    Err("unexpected end of binary VDF".into())
+                };
+            };
+            if kind == Self::MAP_END {
+                return Ok(map);
+            }
+
+            let key = self.read_cstr()?.to_lowercase();
+            let value = match kind {
+                Self::MAP => VdfValue::Map(self.read_map(false)?),
+                Self::STRING => VdfValue::Str(self.read_cstr()?),
+                Self::INT32 => VdfValue::Int(i32::from_le_bytes(self.take::<4>()?)),
+                Self::FLOAT32 | Self::POINTER | Self::COLOR => {
+                    self.take::<4>()?;
+                    VdfValue::Other
+                }
+                Self::UINT64 | Self::INT64 => {
+                    self.take::<8>()?;
+                    VdfValue::Other
+                }
+                other => return Err(format!("unsupported binary VDF type {:#04x}", other).into()),
+            };
+            map.insert(key, value);
+        }
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        let b = *self.bytes.get(self.pos)?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], IndexerError> {
+        let slice = self
+            .bytes
+            .get(self.pos..self.pos + N)
+            .ok_or("unexpected end of binary VDF")?;
+        self.pos += N;
+        Ok(slice.try_into()?)
+    }
+
+    fn read_cstr(&mut self) -> Result<String, IndexerError> {
+        let rest = &self.bytes[self.pos..];
+        let len = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or("unterminated string in binary VDF")?;
+        let s = String::from_utf8_lossy(&rest[..len]).to_string();
+        self.pos += len + 1;
+        Ok(s)
+    }
+}
@@ -0,0 +1,70 @@
+//! Emulated system names shared by emulator-related indexers.
+//!
+//! RetroArch playlists, libretro databases and No-Intro/Redump DAT files all
+//! name systems the same way (`"Nintendo - Super Nintendo Entertainment
+//! System"`). This maps those names to our short `platform` values.
+
+/// Map a libretro/No-Intro system name to our `platform` value.
+///
+/// Matching ignores case, a trailing `.lpl`/`.rdb` extension and trailing
+/// parenthesised qualifiers such as `(Parent-Clone)`.
+pub fn platform_for_system(system: &str) -> Option<&'static str> {
+    let lower = system.trim().to_lowercase();
+    let mut name = lower.as_str();
+    for ext in [".lpl", ".rdb", ".dat"] {
+        name = name.strip_suffix(ext).unwrap_or(name);
+    }
+    let name = match name.find(" (") {
+        Some(pos) => &name[..pos],
+        None => name,
+    };
+
+    SYSTEMS
+        .iter()
+        .find(|(system, _)| system.eq_ignore_ascii_case(name.trim()))
+        .map(|(_, platform)| *platform)
+}
+
+const SYSTEMS: &[(&str, &str)] = &[
+    ("Nintendo - Nintendo Entertainment System", "NES"),
+    ("Nintendo - Family Computer Disk System", "NES"),
+    ("Nintendo - Super Nintendo Entertainment System", "SNES"),
+    ("Nintendo - Satellaview", "SNES"),
+    ("Nintendo - Nintendo 64", "N64"),
+    ("Nintendo - Nintendo 64DD", "N64"),
+    ("Nintendo - GameCube", "GameCube"),
+    ("Nintendo - Wii", "Wii"),
+    ("Nintendo - Game Boy", "GB"),
+    ("Nintendo - Game Boy Color", "GBC"),
+    ("Nintendo - Game Boy Advance", "GBA"),
+    ("Nintendo - Nintendo DS", "DS"),
+    ("Nintendo - Nintendo 3DS", "3DS"),
+    ("Nintendo - Virtual Boy", "Virtual Boy"),
+    ("Sony - PlayStation", "PS1"),
+    ("Sony - PlayStation 2", "PS2"),
+    ("Sony - PlayStation 3", "PS3"),
+    ("Sony - PlayStation Portable", "PSP"),
+    ("Sega - Mega Drive - Genesis", "Genesis"),
+    ("Sega - Mega-CD - Sega CD", "Sega CD"),
+    ("Sega - 32X", "32X"),
+    ("Sega - Master System - Mark III", "Master System"),
+    ("Sega - Game Gear", "Game Gear"),
+    ("Sega - Saturn", "Saturn"),
+    ("Sega - Dreamcast", "Dreamcast"),
+    ("NEC - PC Engine - TurboGrafx 16", "TurboGrafx-16"),
+    ("NEC - PC Engine CD - TurboGrafx-CD", "TurboGrafx-16"),
+    ("SNK - Neo Geo Pocket Color", "Neo Geo Pocket"),
+    ("SNK - Neo Geo CD", "Neo Geo"),
+    ("Atari - 2600", "Atari 2600"),
+    ("Atari - 7800", "Atari 7800"),
+    ("Atari - Lynx", "Lynx"),
+    ("Bandai - WonderSwan Color", "WonderSwan"),
+    ("Microsoft - MSX", "MSX"),
+    ("Microsoft - MSX2", "MSX"),
+    ("Commodore - Amiga", "Amiga"),
+    ("Commodore - 64", "C64"),
+    ("DOS", "DOS"),
+    ("MAME", "Arcade"),
+    ("FBNeo - Arcade Games", "Arcade"),
+    ("FB Alpha - Arcade Games", "Arcade"),
+];
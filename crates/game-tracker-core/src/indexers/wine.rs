@@ -0,0 +1,107 @@
+//! Wine prefix helpers.
+//!
+//! Windows launchers run under Wine, Proton or Bottles keep their data inside
+//! a prefix (`<prefix>/drive_c/...`) and record install paths as `C:\...`.
+//! These helpers find prefixes on disk and map such paths back to host paths.
+
+use std::path::{Component, Path, PathBuf};
+
+/// Prefix directories from the usual Wine, Proton, Bottles, Heroic and
+/// Lutris locations. Only directories that contain `drive_c` are returned.
+pub fn discover_prefixes() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local/share"));
+
+    let mut candidates = vec![home.join(".wine")];
+
+    // Each child is a prefix
+    let containers = [
+        home.join("Games"),
+        home.join("Games/Heroic/Prefixes"),
+        home.join("Games/Heroic/Prefixes/default"),
+        data_home.join("bottles/bottles"),
+        home.join(".var/app/com.usebottles.bottles/data/bottles/bottles"),
+    ];
+    for dir in &containers {
+        candidates.extend(child_dirs(dir));
+    }
+
+    // Proton keeps one prefix per app id under `compatdata/<appid>/pfx`
+    let steam_roots = [
+        home.join(".steam/steam"),
+        data_home.join("Steam"),
+        home.join(".var/app/com.valvesoftware.Steam/data/Steam"),
+    ];
+    for root in &steam_roots {
+        candidates.extend(
+            child_dirs(&root.join("steamapps/compatdata"))
+                .into_iter()
+                .map(|d| d.join("pfx")),
+        );
+    }
+
+    let mut prefixes: Vec<PathBuf> = Vec::new();
+    for dir in candidates {
+        if !dir.join("drive_c").is_dir() {
+            continue;
+        }
+        // `~/.steam/steam` is usually a symlink to the XDG Steam directory
+        let dir = dir.canonicalize().unwrap_or(dir);
+        if !prefixes.contains(&dir) {
+            prefixes.push(dir);
+        }
+    }
+    prefixes
+}
+
+/// The prefix a host path lives in, i.e. the parent of its `drive_c`.
+pub fn prefix_of(path: &Path) -> Option<PathBuf> {
+    let mut prefix = PathBuf::new();
+    for component in path.components() {
+        if component == Component::Normal("drive_c".as_ref()) {
+            return Some(prefix);
+        }
+        prefix.push(component);
+    }
+    None
+}
+
+/// Map a Windows path such as `C:\Games\Foo` to its location inside `prefix`.
+///
+/// `C:` resolves to `drive_c`; other drive letters go through the prefix's
+/// `dosdevices` links. Returns `None` for paths that aren't drive-absolute.
+pub fn windows_to_host(prefix: &Path, windows_path: &str) -> Option<PathBuf> {
+    let mut chars = windows_path.chars();
+    let drive = chars.next()?.to_ascii_lowercase();
+    if !drive.is_ascii_alphabetic() || chars.next()? != ':' {
+        return None;
+    }
+
+    let mut host = if drive == 'c' {
+        prefix.join("drive_c")
+    } else {
+        prefix.join("dosdevices").join(format!("{}:", drive))
+    };
+    for part in windows_path[2..]
+        .split(['\\', '/'])
+        .filter(|p| !p.is_empty())
+    {
+        host.push(part);
+    }
+    Some(host)
+}
+
+fn child_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect()
+}
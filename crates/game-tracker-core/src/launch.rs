@@ -0,0 +1,621 @@
+//! Launching games and tracking how long they run.
+//!
+//! A game is started by the first [`LaunchStrategy`] that can: its
+//! launch override, then its launcher (Steam and Epic URIs keep DRM and
+//! overlays working, ROMs open in their emulator), then its executable.
+//! While it runs, [`track_session`] polls the process list: the game counts
+//! as running while any process of the spawned tree, or any process running
+//! from the game's install directory, is alive. The second rule catches
+//! launchers that fork the real game and exit, and games started by a URI
+//! handler.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+
+use sqlx::SqlitePool;
+
+use crate::db;
+use crate::indexers::retroarch;
+use crate::models::{AppSettings, Game, PlaySession, RomFolderRule};
+use crate::process::{self, GameLocation, ProcessInfo};
+
+/// How often the process list is checked.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How often the session's duration is written to the database.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+/// How long to wait for the game's first process after launching.
+const STARTUP_GRACE: Duration = Duration::from_secs(120);
+/// Longest gap between two polls still counted as play time; anything
+/// longer means the machine was suspended.
+const MAX_POLL_GAP: Duration = Duration::from_secs(30);
+
+/// How to start a game.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LaunchCommand {
+    /// Run the game's own executable; its process tree is the game.
+    Exe {
+        path: PathBuf,
+        args: Vec<String>,
+        working_dir: Option<PathBuf>,
+    },
+    /// Ask a launcher client to start the game. The client keeps running,
+    /// so only processes in the install directory count as the game.
+    Client {
+        path: PathBuf,
+        args: Vec<String>,
+    },
+    /// Open a launcher URI (`steam://...`) with the system handler.
+    Uri(String),
+}
+
+impl LaunchCommand {
+    /// Start the command.
+    pub fn spawn(&self) -> std::io::Result<Child> {
+        match self {
+            LaunchCommand::Exe {
+                path,
+                args,
+                working_dir,
+            } => {
+                let mut command = Command::new(path);
+                command.args(args);
+                if let Some(dir) = working_dir {
+                    command.current_dir(dir);
+                }
+                command.spawn()
+            }
+            LaunchCommand::Client { path, args } => Command::new(path).args(args).spawn(),
+            LaunchCommand::Uri(uri) => uri_opener(uri).spawn(),
+        }
+    }
+
+    /// Whether the spawned process is the game itself.
+    pub fn is_game_process(&self) -> bool {
+        matches!(self, LaunchCommand::Exe { .. })
+    }
+}
+
+fn uri_opener(uri: &str) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", "", uri]);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg(uri);
+        command
+    } else {
+        let mut command = Command::new("xdg-open");
+        command.arg(uri);
+        command
+    }
+}
+
+/// One way of starting games, usually through the launcher that owns them.
+pub trait LaunchStrategy: Send + Sync {
+    /// Stable id, also accepted as a per-game override (e.g. `"steam"`).
+    fn id(&self) -> &'static str;
+
+    /// Game sources this strategy is the preferred way to launch.
+    fn sources(&self) -> &'static [&'static str];
+
+    /// Command that starts `game`, or `None` if this strategy can't.
+    fn command(&self, game: &Game) -> Option<LaunchCommand>;
+}
+
+/// Run `exe_path` directly from its own directory.
+pub struct ExeStrategy;
+
+impl LaunchStrategy for ExeStrategy {
+    fn id(&self) -> &'static str {
+        "exe"
+    }
+
+    fn sources(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn command(&self, game: &Game) -> Option<LaunchCommand> {
+        let exe = Path::new(game.exe_path.as_deref()?);
+        exe.is_file().then(|| LaunchCommand::Exe {
+            path: exe.to_path_buf(),
+            args: Vec::new(),
+            working_dir: exe.parent().map(Path::to_path_buf),
+        })
+    }
+}
+
+/// `steam://rungameid/<appid>`, which keeps the overlay and DRM working.
+/// Non-Steam shortcuts use their 64-bit game id.
+pub struct SteamStrategy;
+
+impl LaunchStrategy for SteamStrategy {
+    fn id(&self) -> &'static str {
+        "steam"
+    }
+
+    fn sources(&self) -> &'static [&'static str] {
+        &["steam", "steam-shortcut"]
+    }
+
+    fn command(&self, game: &Game) -> Option<LaunchCommand> {
+        let source_id = game.source_id.as_deref()?;
+        let game_id = match game.source.as_deref()? {
+            "steam" => source_id.parse::<u32>().ok()?.to_string(),
+            "steam-shortcut" => {
+                let app_id = u64::from(source_id.parse::<u32>().ok()?);
+                ((app_id << 32) | 0x0200_0000).to_string()
+            }
+            _ => return None,
+        };
+        Some(LaunchCommand::Uri(format!("steam://rungameid/{}", game_id)))
+    }
+}
+
+/// `com.epicgames.launcher://apps/<AppName>?action=launch`.
+pub struct EpicStrategy;
+
+impl LaunchStrategy for EpicStrategy {
+    fn id(&self) -> &'static str {
+        "epic"
+    }
+
+    fn sources(&self) -> &'static [&'static str] {
+        &["epic"]
+    }
+
+    fn command(&self, game: &Game) -> Option<LaunchCommand> {
+        if game.source.as_deref()? != "epic" {
+            return None;
+        }
+        Some(LaunchCommand::Uri(format!(
+            "com.epicgames.launcher://apps/{}?action=launch&silent=true",
+            urlencoding::encode(game.source_id.as_deref()?)
+        )))
+    }
+}
+
+/// GOG Galaxy's `runGame` command, when the Galaxy client is installed.
+pub struct GogGalaxyStrategy {
+    /// `GalaxyClient.exe`
+    pub client: PathBuf,
+}
+
+impl Default for GogGalaxyStrategy {
+    fn default() -> Self {
+        Self {
+            client: PathBuf::from(r"C:\Program Files (x86)\GOG Galaxy\GalaxyClient.exe"),
+        }
+    }
+}
+
+impl LaunchStrategy for GogGalaxyStrategy {
+    fn id(&self) -> &'static str {
+        "gog"
+    }
+
+    fn sources(&self) -> &'static [&'static str] {
+        &["gog"]
+    }
+
+    fn command(&self, game: &Game) -> Option<LaunchCommand> {
+        if game.source.as_deref()? != "gog" || !self.client.is_file() {
+            return None;
+        }
+        let mut args = vec![
+            "/command=runGame".to_string(),
+            format!("/gameId={}", game.source_id.as_deref()?),
+        ];
+        if let Some(install) = &game.install_path {
+            args.push(format!("/path={}", install));
+        }
+        Some(LaunchCommand::Client {
+            path: self.client.clone(),
+            args,
+        })
+    }
+}
+
+/// `heroic://launch?appName=<app>&runner=<backend>`; Heroic source ids are
+/// `<backend>:<app name>`.
+pub struct HeroicStrategy;
+
+impl LaunchStrategy for HeroicStrategy {
+    fn id(&self) -> &'static str {
+        "heroic"
+    }
+
+    fn sources(&self) -> &'static [&'static str] {
+        &["heroic"]
+    }
+
+    fn command(&self, game: &Game) -> Option<LaunchCommand> {
+        if game.source.as_deref()? != "heroic" {
+            return None;
+        }
+        let (runner, app_name) = game.source_id.as_deref()?.split_once(':')?;
+        Some(LaunchCommand::Uri(format!(
+            "heroic://launch?appName={}&runner={}",
+            urlencoding::encode(app_name),
+            runner
+        )))
+    }
+}
+
+/// `lutris:rungameid/<id>`.
+pub struct LutrisStrategy;
+
+impl LaunchStrategy for LutrisStrategy {
+    fn id(&self) -> &'static str {
+        "lutris"
+    }
+
+    fn sources(&self) -> &'static [&'static str] {
+        &["lutris"]
+    }
+
+    fn command(&self, game: &Game) -> Option<LaunchCommand> {
+        if game.source.as_deref()? != "lutris" {
+            return None;
+        }
+        let id: u32 = game.source_id.as_deref()?.parse().ok()?;
+        Some(LaunchCommand::Uri(format!("lutris:rungameid/{}", id)))
+    }
+}
+
+/// RetroArch with the core its playlist runs the ROM with:
+/// `retroarch -L <core> <rom>`.
+pub struct RetroArchStrategy {
+    /// RetroArch config directories whose playlists are searched.
+    pub config_dirs: Vec<PathBuf>,
+}
+
+impl Default for RetroArchStrategy {
+    fn default() -> Self {
+        Self {
+            config_dirs: retroarch::default_config_dirs(),
+        }
+    }
+}
+
+impl LaunchStrategy for RetroArchStrategy {
+    fn id(&self) -> &'static str {
+        "retroarch"
+    }
+
+    fn sources(&self) -> &'static [&'static str] {
+        &["retroarch"]
+    }
+
+    fn command(&self, game: &Game) -> Option<LaunchCommand> {
+        if game.source.as_deref()? != "retroarch" {
+            return None;
+        }
+        let rom = game.source_id.as_deref()?;
+        let (config_dir, core) = self
+            .config_dirs
+            .iter()
+            .find_map(|dir| Some((dir, retroarch::playlist_core(dir, rom)?)))?;
+        let (path, mut args) = retroarch::retroarch_program(config_dir);
+        args.extend(["-L".to_string(), core, rom.to_string()]);
+        Some(LaunchCommand::Exe {
+            path,
+            args,
+            working_dir: None,
+        })
+    }
+}
+
+/// The emulator of the ROM folder rule a ROM was found under, with the ROM
+/// as its last argument.
+pub struct EmulatorStrategy {
+    pub rules: Vec<RomFolderRule>,
+}
+
+impl LaunchStrategy for EmulatorStrategy {
+    fn id(&self) -> &'static str {
+        "emulator"
+    }
+
+    fn sources(&self) -> &'static [&'static str] {
+        &["emulator"]
+    }
+
+    fn command(&self, game: &Game) -> Option<LaunchCommand> {
+        let rom = game.install_path.as_deref()?;
+        // Nested rule folders: the innermost one wins
+        let rule = self
+            .rules
+            .iter()
+            .filter(|r| !r.emulator.trim().is_empty() && Path::new(rom).starts_with(&r.folder))
+            .max_by_key(|r| Path::new(&r.folder).components().count())?;
+        match parse_custom_command(rule.emulator.trim())? {
+            LaunchCommand::Exe { path, mut args, .. } => {
+                args.push(rom.to_string());
+                Some(LaunchCommand::Exe {
+                    path,
+                    args,
+                    working_dir: None,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Launch strategies and the order they are tried in.
+pub struct LaunchPlanner {
+    strategies: Vec<Box<dyn LaunchStrategy>>,
+}
+
+impl Default for LaunchPlanner {
+    fn default() -> Self {
+        Self::from_settings(&AppSettings::default())
+    }
+}
+
+impl LaunchPlanner {
+    /// Planner over `strategies`.
+    pub fn new(strategies: Vec<Box<dyn LaunchStrategy>>) -> Self {
+        Self { strategies }
+    }
+
+    /// The built-in strategies, with emulators from the ROM folder rules.
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self::new(vec![
+            Box::new(SteamStrategy),
+            Box::new(EpicStrategy),
+            Box::new(GogGalaxyStrategy::default()),
+            Box::new(HeroicStrategy),
+            Box::new(LutrisStrategy),
+            Box::new(RetroArchStrategy::default()),
+            Box::new(EmulatorStrategy {
+                rules: settings.rom_folders.clone(),
+            }),
+            Box::new(ExeStrategy),
+        ])
+    }
+
+    /// Ids of all strategies, valid as per-game overrides.
+    pub fn strategy_ids(&self) -> Vec<&'static str> {
+        self.strategies.iter().map(|s| s.id()).collect()
+    }
+
+    /// Every way to start `game`, best first, as `(strategy id, command)`.
+    ///
+    /// The chain is: the game's launch override, the strategy for the
+    /// game's source, then the executable. An override naming a strategy
+    /// moves it to the front; any other override is a custom command line
+    /// or URI, tagged `"custom"`.
+    pub fn plan(&self, game: &Game) -> Vec<(&'static str, LaunchCommand)> {
+        let source = game.source.as_deref().unwrap_or_default();
+        let override_id = game.launch_override.as_deref().map(str::trim);
+
+        let mut chain: Vec<&dyn LaunchStrategy> = Vec::new();
+        let mut plans = Vec::new();
+        match override_id {
+            Some(id) if self.strategy(id).is_some() => chain.extend(self.strategy(id)),
+            Some(custom) if !custom.is_empty() => {
+                plans.extend(parse_custom_command(custom).map(|c| ("custom", c)))
+            }
+            _ => {}
+        }
+        chain.extend(
+            self.strategies
+                .iter()
+                .filter(|s| s.sources().contains(&source))
+                .map(Box::as_ref),
+        );
+        chain.extend(self.strategy("exe"));
+
+        let mut tried = Vec::new();
+        for strategy in chain {
+            if tried.contains(&strategy.id()) {
+                continue;
+            }
+            tried.push(strategy.id());
+            if let Some(command) = strategy.command(game) {
+                plans.push((strategy.id(), command));
+            }
+        }
+        plans
+    }
+
+    fn strategy(&self, id: &str) -> Option<&dyn LaunchStrategy> {
+        self.strategies.iter().find(|s| s.id() == id).map(Box::as_ref)
+    }
+}
+
+/// Preferred command to start `game` with the default strategies.
+pub fn launch_command(game: &Game) -> Option<LaunchCommand> {
+    LaunchPlanner::default()
+        .plan(game)
+        .into_iter()
+        .next()
+        .map(|(_, command)| command)
+}
+
+/// A custom override: a URI when it starts with a scheme, otherwise a
+/// command line split on whitespace, with double quotes grouping words.
+pub fn parse_custom_command(custom: &str) -> Option<LaunchCommand> {
+    if is_uri(custom) {
+        return Some(LaunchCommand::Uri(custom.to_string()));
+    }
+
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut in_word = false;
+    for c in custom.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+
+    let mut words = words.into_iter();
+    Some(LaunchCommand::Exe {
+        path: PathBuf::from(words.next()?),
+        args: words.collect(),
+        working_dir: None,
+    })
+}
+
+/// `scheme:...` with a scheme longer than one letter, so `C:\...` is a path.
+fn is_uri(s: &str) -> bool {
+    match s.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.len() > 1
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+/// Tracks which processes belong to a launched game.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessWatch {
+    tracked: HashSet<u32>,
+    location: GameLocation,
+    started: bool,
+    spawned_directly: bool,
+}
+
+impl ProcessWatch {
+    /// Watch the tree of `root_pid` (if the game was spawned directly) plus
+    /// anything running from the game's install directory, or running its
+    /// executable when that directory is shared (see [`GameLocation`]).
+    pub fn new(root_pid: Option<u32>, game: &Game) -> Self {
+        Self {
+            tracked: root_pid.into_iter().collect(),
+            location: GameLocation::of(game),
+            started: false,
+            spawned_directly: root_pid.is_some(),
+        }
+    }
+
+    /// Update from a process snapshot; returns whether the game is running.
+    pub fn update(&mut self, processes: &[ProcessInfo]) -> bool {
+        let live: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+        self.tracked.retain(|pid| live.contains(pid));
+
+        for process in processes {
+            if self.location.matches(&process.paths()).is_some() {
+                self.tracked.insert(process.pid);
+            }
+        }
+        // Follow the tree down until no new children turn up
+        loop {
+            let children: Vec<u32> = processes
+                .iter()
+                .filter(|p| self.tracked.contains(&p.ppid) && !self.tracked.contains(&p.pid))
+                .map(|p| p.pid)
+                .collect();
+            if children.is_empty() {
+                break;
+            }
+            self.tracked.extend(children);
+        }
+
+        let running = !self.tracked.is_empty();
+        self.started |= running;
+        running
+    }
+
+    /// Whether any game process has been seen yet.
+    pub fn has_started(&self) -> bool {
+        self.started
+    }
+
+    /// Whether the game's own executable was spawned, as opposed to a URI
+    /// handler that hands off to a launcher.
+    pub fn spawned_directly(&self) -> bool {
+        self.spawned_directly
+    }
+}
+
+/// A game started by [`crate::service::GameService::launch_game`].
+pub struct LaunchedGame {
+    pub game_id: i32,
+    pub session_id: i64,
+    /// The spawned process; waited on so it doesn't linger as a zombie.
+    pub child: Option<Child>,
+    pub watch: ProcessWatch,
+}
+
+/// Follow a launched game until it exits, recording the session's duration
+/// every [`HEARTBEAT_INTERVAL`] and closing it at the end. Returns the
+/// closed session, or `None` when it lasted under a minute or the game
+/// never showed up.
+pub async fn track_session(
+    pool: &SqlitePool,
+    mut launched: LaunchedGame,
+) -> Result<Option<PlaySession>, sqlx::Error> {
+    let launched_at = Instant::now();
+    let mut last_poll = launched_at;
+    let mut last_heartbeat = launched_at;
+    let mut played = Duration::ZERO;
+
+    loop {
+        let now = Instant::now();
+        let gap = now - last_poll;
+        last_poll = now;
+
+        let child_alive = match launched.child.as_mut().map(Child::try_wait) {
+            Some(Ok(None)) => true,
+            Some(_) => {
+                launched.child = None;
+                false
+            }
+            None => false,
+        };
+        let processes = tokio::task::spawn_blocking(process::snapshot)
+            .await
+            .unwrap_or_default();
+        // Without a process list (not Linux or Windows) the spawned process is all we know
+        let running = launched.watch.update(&processes) || child_alive;
+
+        if running {
+            if gap <= MAX_POLL_GAP {
+                played += gap;
+            }
+        } else if launched.watch.has_started()
+            || (launched.watch.spawned_directly() && launched.child.is_none())
+            || launched_at.elapsed() > STARTUP_GRACE
+        {
+            break;
+        }
+
+        if now - last_heartbeat >= HEARTBEAT_INTERVAL {
+            last_heartbeat = now;
+            db::record_session_progress(pool, launched.session_id, whole_minutes(played)).await?;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+
+    db::finish_play_session(pool, launched.session_id, whole_minutes(played)).await
+}
+
+fn whole_minutes(duration: Duration) -> i32 {
+    (duration.as_secs_f64() / 60.0).round() as i32
+}
@@ -5,18 +5,30 @@
 //! - SQLx database access helpers
 //! - RAWG metadata client
 //! - icon extraction and cover download utilities
-//! - launcher indexers (Steam/Epic)
+//! - pluggable launcher indexers behind the `LauncherIndexer` trait
+//!   (Steam, Epic, GOG, Heroic, Lutris, itch.io, RetroArch, game folders, ...)
 //! - `GameService`, the orchestration layer used by Tauri commands
+//! - a filesystem watcher that triggers per-source re-indexing
+//! - game launching with automatic play session tracking
+//! - a process monitor recording sessions of games started elsewhere
 
-/// Database access helpers for the `games` table and statistics queries.
+/// Database access helpers for the `games`/`settings` tables and statistics queries.
 pub mod db;
 /// Image/icon helpers for local executable icons and remote cover downloads.
 pub mod icon_extract;
 /// Launcher-specific game discovery modules.
 pub mod indexers;
+/// Game launching and play session tracking.
+pub mod launch;
 /// Shared DTOs and persisted model types.
 pub mod models;
+/// Passive detection of running games.
+pub mod monitor;
+/// Running-process snapshots from `/proc`.
+pub mod process;
 /// RAWG API client and response types.
 pub mod rawg;
 /// High-level service layer that coordinates CRUD, enrichment, and indexing.
 pub mod service;
+/// Debounced watcher over launcher manifest directories.
+pub mod watcher;
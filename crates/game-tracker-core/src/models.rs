@@ -7,6 +7,7 @@
 
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::HashMap;
 
 /// Persisted game record stored in SQLite and returned to the frontend.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -26,12 +27,62 @@ pub struct Game {
     pub rating: Option<i32>,
     pub added_date: String,
     pub last_played: Option<String>,
-    /// Source launcher: "manual", "steam", "epic"
+    /// Primary source launcher: "manual", "steam", "epic". Every source
+    /// the game is owned on is listed in `game_sources`.
     pub source: Option<String>,
-    /// External ID from launcher (Steam AppID, Epic AppName)
+    /// External ID from the primary launcher (Steam AppID, Epic AppName)
     pub source_id: Option<String>,
-    /// Path where the game is installed
+    /// Path where the game is installed by its primary source
     pub install_path: Option<String>,
+    /// Set when the user picked `exe_path`; indexing then leaves it alone
+    pub exe_path_locked: bool,
+    /// False once the game's launcher stops reporting it
+    pub installed: bool,
+    /// When the game was last found missing from its launcher
+    pub uninstalled_at: Option<String>,
+    /// Launch strategy id to try first, or a custom command line / URI
+    pub launch_override: Option<String>,
+}
+
+/// One launcher a game is owned on (a `game_sources` row).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GameSource {
+    pub id: i32,
+    pub game_id: i32,
+    pub source: String,
+    pub source_id: String,
+    pub install_path: Option<String>,
+    pub exe_path: Option<String>,
+    pub installed: bool,
+    /// Playtime this launcher last reported. The game's total only grows by
+    /// increases of this value, so sources add up instead of overwriting.
+    pub playtime_hours: f32,
+    pub added_date: String,
+}
+
+/// One logged play session (a `play_sessions` row).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PlaySession {
+    pub id: i32,
+    pub game_id: i32,
+    /// Session start (`YYYY-MM-DD HH:MM:SS`, UTC)
+    pub session_date: String,
+    pub duration_minutes: i32,
+    pub notes: Option<String>,
+    /// True while a launched game is still running
+    pub active: bool,
+    /// Derived from growth in launcher-reported playtime rather than seen
+    /// by the app; `session_date` is then the launcher's last-played time
+    pub inferred: bool,
+}
+
+/// Input payload for logging or editing a play session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaySessionInput {
+    /// Session start; defaults to now when logging
+    pub session_date: Option<String>,
+    pub duration_minutes: i32,
+    pub notes: Option<String>,
 }
 
 /// Input payload used when creating a new game entry.
@@ -68,6 +119,59 @@ pub struct DiscoveredGame {
     pub install_path: Option<String>,
     pub source: String,
     pub source_id: String,
+    /// Total playtime reported by the launcher, if it tracks one.
+    pub playtime_hours: Option<f32>,
+    /// Last-played timestamp reported by the launcher (`YYYY-MM-DD HH:MM:SS`, UTC).
+    pub last_played: Option<String>,
+}
+
+/// Kind of change an index run would make.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexChangeKind {
+    /// Launcher entry with no matching source and no game to attach to.
+    New,
+    /// New source for a game already in the library (same normalized
+    /// title and platform).
+    Attach,
+    /// Existing row whose title, paths or install state would change.
+    Updated,
+    /// Installed source its launcher no longer reports.
+    Vanished,
+}
+
+/// One entry of an index preview; sent back as-is to apply it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexChange {
+    pub kind: IndexChangeKind,
+    /// Game as reported by the launcher (all kinds except `Vanished`).
+    pub discovered: Option<DiscoveredGame>,
+    /// Game the entry refers to: the one being updated or attached to, or
+    /// for `Vanished` the game as seen through the vanishing source
+    /// (`source`, `source_id` and paths are that source's). An `Attach` to
+    /// a game that an earlier `New` entry would create carries that game
+    /// with id 0.
+    pub existing: Option<Game>,
+    /// Changed fields for `Updated` entries (`title`, `install_path`,
+    /// `exe_path`, `installed`).
+    pub fields: Vec<String>,
+}
+
+/// Executable found under a game's install directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExeCandidate {
+    pub path: String,
+    /// Heuristic likelihood of being the main game binary (higher is better).
+    pub score: i32,
+}
+
+/// Ranked executable search result for one install directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExeDetection {
+    /// Highest-scoring candidate, if any executable was found.
+    pub best: Option<ExeCandidate>,
+    /// Remaining candidates, best first.
+    pub alternatives: Vec<ExeCandidate>,
 }
 
 /// Aggregated library metrics shown in the stats view.
@@ -78,3 +182,74 @@ pub struct GameStats {
     pub by_status: Vec<(String, i64)>,
     pub total_playtime: f64,
 }
+
+/// Registered launcher indexer as shown in the indexing view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerInfo {
+    /// Source id written to `games.source` (e.g. "steam").
+    pub id: String,
+    pub display_name: String,
+    /// Whether the indexer runs as part of `index_all`.
+    pub enabled: bool,
+    /// Whether the launcher was detected on this machine.
+    pub available: bool,
+}
+
+/// User-editable application settings persisted in the `settings` table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Indexer ids excluded from full indexing runs.
+    pub disabled_indexers: Vec<String>,
+    /// Steam account (`userdata` folder id) whose playtime is imported.
+    /// `None` sums playtime across all accounts on the machine.
+    pub steam_account: Option<String>,
+    /// Extra Epic manifest directories, scanned before the default and
+    /// auto-discovered Wine prefix locations.
+    pub epic_manifest_dirs: Vec<String>,
+    /// Re-index a launcher automatically when its manifest folders change.
+    pub watch_launchers: bool,
+    /// Root folders whose subfolders are DRM-free or portable games.
+    pub game_folders: Vec<String>,
+    /// No-Intro/Redump Logiqx DAT files used to identify ROMs.
+    pub dat_files: Vec<String>,
+    /// Standalone emulator ROM folders.
+    pub rom_folders: Vec<RomFolderRule>,
+    /// Battle.net product code to title, on top of the built-in table.
+    pub battlenet_titles: HashMap<String, String>,
+    /// Record sessions for games started outside the app, by watching
+    /// running processes.
+    pub monitor_processes: bool,
+    /// Shortest run the process monitor records as a session.
+    pub min_session_minutes: u32,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            disabled_indexers: Vec::new(),
+            steam_account: None,
+            epic_manifest_dirs: Vec::new(),
+            watch_launchers: true,
+            game_folders: Vec::new(),
+            dat_files: Vec::new(),
+            rom_folders: Vec::new(),
+            battlenet_titles: HashMap::new(),
+            monitor_processes: true,
+            min_session_minutes: 2,
+        }
+    }
+}
+
+/// ROM folder scanned for one system and played with one emulator,
+/// e.g. `~/roms/gc` with `iso,rvz,gcz` for Dolphin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RomFolderRule {
+    pub folder: String,
+    /// Platform (`GameCube`) or DAT system name (`Nintendo - GameCube`).
+    pub system: String,
+    /// File extensions without the dot, matched case-insensitively.
+    pub extensions: Vec<String>,
+    /// Emulator executable or command line; the ROM path is appended.
+    pub emulator: String,
+}
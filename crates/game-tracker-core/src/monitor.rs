@@ -0,0 +1,208 @@
+//! Passive detection of games started outside the app.
+//!
+//! [`ProcessMonitor`] is polled with process snapshots. A process belongs to
+//! an installed game when its executable or an argument lies in the game's
+//! install directory (or is its `exe_path`). Shared directories such as
+//! `/usr/bin` only match through `exe_path`. Once a game has run for the
+//! minimum session length, a tracked `play_sessions` row is opened from the
+//! moment it was first seen; it is updated while the game runs and closed
+//! when its last process exits. Shorter runs leave no trace.
+//!
+//! Games that already have an active session (started with
+//! [`crate::launch`]) are left to that tracker.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use crate::db;
+use crate::indexers::sqlite_timestamp;
+use crate::models::{Game, PlaySession};
+use crate::process::{GameLocation, ProcessInfo};
+
+/// Longest gap between two polls still counted as play time; anything
+/// longer means the machine was suspended.
+const MAX_POLL_GAP: Duration = Duration::from_secs(120);
+
+/// Change reported by [`ProcessMonitor::poll`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MonitorEvent {
+    /// A game has run long enough to be recorded and its session opened.
+    NowPlaying { game_id: i32, title: String },
+    /// A recorded game exited and its session was closed.
+    Stopped { game_id: i32, title: String, session: PlaySession },
+}
+
+/// A game seen running.
+#[derive(Debug, Clone)]
+struct RunningGame {
+    title: String,
+    first_seen: DateTime<Utc>,
+    played: Duration,
+    session_id: Option<i64>,
+    recorded_minutes: i32,
+}
+
+/// Records sessions for games whose processes show up in snapshots.
+#[derive(Debug, Default)]
+pub struct ProcessMonitor {
+    min_session: Duration,
+    running: HashMap<i32, RunningGame>,
+    last_poll: Option<DateTime<Utc>>,
+}
+
+impl ProcessMonitor {
+    /// Monitor that ignores runs shorter than `min_session`.
+    pub fn new(min_session: Duration) -> Self {
+        Self {
+            min_session,
+            ..Default::default()
+        }
+    }
+
+    /// Change the minimum session length for runs not yet recorded.
+    pub fn set_min_session(&mut self, min_session: Duration) {
+        self.min_session = min_session;
+    }
+
+    /// Ids of the games currently seen running.
+    pub fn running_games(&self) -> Vec<i32> {
+        self.running.keys().copied().collect()
+    }
+
+    /// Process one snapshot taken at `now`.
+    pub async fn poll(
+        &mut self,
+        pool: &SqlitePool,
+        processes: &[ProcessInfo],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<MonitorEvent>, sqlx::Error> {
+        let gap = self
+            .last_poll
+            .and_then(|last| (now - last).to_std().ok())
+            .filter(|gap| *gap <= MAX_POLL_GAP)
+            .unwrap_or_default();
+        self.last_poll = Some(now);
+
+        let own_sessions: HashSet<i64> =
+            self.running.values().filter_map(|g| g.session_id).collect();
+        let tracked_elsewhere: HashSet<i32> = db::get_active_sessions(pool)
+            .await?
+            .into_iter()
+            .filter(|(session_id, _)| !own_sessions.contains(session_id))
+            .map(|(_, game_id)| game_id)
+            .collect();
+        let games: Vec<Game> = db::get_all_games(pool)
+            .await?
+            .into_iter()
+            .filter(|g| g.installed && !tracked_elsewhere.contains(&g.id))
+            .collect();
+        let running_now = match_running_games(&games, processes);
+
+        let mut events = Vec::new();
+        let exited: Vec<i32> = self
+            .running
+            .keys()
+            .filter(|id| !running_now.contains(id))
+            .copied()
+            .collect();
+        for game_id in exited {
+            if let Some(game) = self.running.remove(&game_id) {
+                events.extend(close(pool, game_id, game).await?);
+            }
+        }
+
+        for game in games.iter().filter(|g| running_now.contains(&g.id)) {
+            let running = self.running.entry(game.id).or_insert_with(|| RunningGame {
+                title: game.title.clone(),
+                first_seen: now,
+                played: Duration::ZERO,
+                session_id: None,
+                recorded_minutes: 0,
+            });
+            running.played += gap;
+            let minutes = whole_minutes(running.played);
+
+            match running.session_id {
+                None if running.played >= self.min_session => {
+                    let started_at = sqlite_timestamp(running.first_seen);
+                    let session_id =
+                        db::start_play_session_at(pool, game.id, Some(&started_at)).await?;
+                    db::record_session_progress(pool, session_id, minutes).await?;
+                    running.session_id = Some(session_id);
+                    running.recorded_minutes = minutes;
+                    tracing::info!("Now playing {} (session {})", game.title, session_id);
+                    events.push(MonitorEvent::NowPlaying {
+                        game_id: game.id,
+                        title: game.title.clone(),
+                    });
+                }
+                Some(session_id) if minutes != running.recorded_minutes => {
+                    db::record_session_progress(pool, session_id, minutes).await?;
+                    running.recorded_minutes = minutes;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Close every open session, e.g. when monitoring is turned off.
+    pub async fn stop_all(&mut self, pool: &SqlitePool) -> Result<Vec<MonitorEvent>, sqlx::Error> {
+        let mut events = Vec::new();
+        for (game_id, game) in std::mem::take(&mut self.running) {
+            events.extend(close(pool, game_id, game).await?);
+        }
+        self.last_poll = None;
+        Ok(events)
+    }
+}
+
+async fn close(
+    pool: &SqlitePool,
+    game_id: i32,
+    game: RunningGame,
+) -> Result<Option<MonitorEvent>, sqlx::Error> {
+    let Some(session_id) = game.session_id else {
+        return Ok(None);
+    };
+    let session = db::finish_play_session(pool, session_id, whole_minutes(game.played)).await?;
+    tracing::info!("Stopped playing {}", game.title);
+    Ok(session.map(|session| MonitorEvent::Stopped {
+        game_id,
+        title: game.title,
+        session,
+    }))
+}
+
+fn whole_minutes(duration: Duration) -> i32 {
+    (duration.as_secs_f64() / 60.0).round() as i32
+}
+
+/// Ids of `games` that have a process in `processes`.
+///
+/// A process that matches several games (a shared emulator directory, a
+/// game inside another game's folder) goes to the most specific path; on a
+/// tie, to the game whose `exe_path` it runs.
+pub fn match_running_games(games: &[Game], processes: &[ProcessInfo]) -> HashSet<i32> {
+    let candidates: Vec<(i32, GameLocation)> =
+        games.iter().map(|g| (g.id, GameLocation::of(g))).collect();
+
+    let mut running = HashSet::new();
+    for process in processes {
+        let paths = process.paths();
+        let best = candidates
+            .iter()
+            .filter_map(|(id, location)| Some((id, location.matches(&paths)?)))
+            .max_by_key(|(_, specificity)| *specificity);
+        if let Some((id, _)) = best {
+            running.insert(*id);
+        }
+    }
+    running
+}
@@ -0,0 +1,286 @@
+//! Running-process snapshots used to tell whether a game is running.
+//!
+//! On Linux processes are read from `/proc`: the parent pid and state from
+//! `stat`, the executable from the `exe` link, arguments from `cmdline` and
+//! the Wine prefix from `environ`. On Windows a Toolhelp32 snapshot gives
+//! the parent pid and the executable (no arguments). Other platforms yield
+//! an empty snapshot, so only the spawned process itself can be waited on
+//! there.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::indexers::wine;
+use crate::models::Game;
+
+/// Whether [`snapshot`] can see processes on this platform.
+pub const SNAPSHOTS_SUPPORTED: bool = cfg!(any(target_os = "linux", windows));
+
+/// One running process.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub ppid: u32,
+    /// Resolved executable; `None` when it can't be read (other users).
+    pub exe: Option<PathBuf>,
+    pub cmdline: Vec<String>,
+    /// `WINEPREFIX` of a Wine process, used to map Windows paths.
+    pub wine_prefix: Option<PathBuf>,
+}
+
+impl ProcessInfo {
+    /// Host paths this process runs from: its executable plus any argument
+    /// naming an absolute path. Windows-style arguments (Wine runs games as
+    /// `C:\Games\Foo\foo.exe`) are mapped to the host through the prefix.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.exe.iter().cloned().collect();
+        for arg in &self.cmdline {
+            if arg.starts_with('/') {
+                paths.push(PathBuf::from(arg));
+            } else if let Some(host) = self.windows_arg_to_host(arg) {
+                paths.push(host);
+            }
+        }
+        paths
+    }
+
+    /// Whether the executable or an argument lies under `dir` (or is `dir`).
+    pub fn runs_from(&self, dir: &Path) -> bool {
+        self.paths().iter().any(|p| p.starts_with(dir))
+    }
+
+    fn windows_arg_to_host(&self, arg: &str) -> Option<PathBuf> {
+        let rest = arg
+            .strip_prefix("Z:")
+            .or_else(|| arg.strip_prefix("z:"));
+        match rest {
+            // Wine maps Z: to the host root
+            Some(rest) => Some(PathBuf::from(rest.replace('\\', "/"))),
+            None => wine::windows_to_host(self.wine_prefix.as_deref()?, arg),
+        }
+    }
+}
+
+/// Where a game's processes run from, for picking them out of a snapshot.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameLocation {
+    /// The game's `exe_path`.
+    pub exe: Option<PathBuf>,
+    /// Its install directory (or the executable's), when [`is_game_dir`].
+    pub dir: Option<PathBuf>,
+}
+
+impl GameLocation {
+    /// Location of `game`. A directory too broad to be the game's own is
+    /// dropped, leaving only the executable to match on.
+    pub fn of(game: &Game) -> Self {
+        let exe = game.exe_path.as_deref().map(PathBuf::from);
+        let dir = game
+            .install_path
+            .as_deref()
+            .map(PathBuf::from)
+            .or_else(|| exe.as_deref().and_then(Path::parent).map(Path::to_path_buf))
+            .filter(|dir| is_game_dir(dir));
+        Self { exe, dir }
+    }
+
+    /// How specifically a process running from `paths` (see
+    /// [`ProcessInfo::paths`]) matches: the depth of the matched directory
+    /// or executable, and whether it runs the executable. `None` when it
+    /// doesn't match.
+    pub fn matches(&self, paths: &[PathBuf]) -> Option<(usize, bool)> {
+        let runs_exe = self.exe.as_ref().is_some_and(|exe| paths.contains(exe));
+        let matched = match &self.dir {
+            Some(dir) if paths.iter().any(|p| p.starts_with(dir)) => dir,
+            _ if runs_exe => self.exe.as_ref()?,
+            _ => return None,
+        };
+        Some((matched.components().count(), runs_exe))
+    }
+}
+
+/// Rejects directories too broad to stand for one game: anything shallower
+/// than `/games/Foo`, home directories, system trees (`/usr`, `/bin`, ...)
+/// and shared Windows folders (`Program Files`, `Users\<name>`, a Wine
+/// prefix's `drive_c` root).
+pub fn is_game_dir(dir: &Path) -> bool {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    if dir.components().count() < 3 || home.as_deref() == Some(dir) {
+        return false;
+    }
+
+    let parts: Vec<String> = dir
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .collect();
+    let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+
+    // Paths on a Windows drive, natively or inside a Wine prefix
+    let drive = if matches!(dir.components().next(), Some(Component::Prefix(_))) {
+        Some(&parts[..])
+    } else {
+        parts
+            .iter()
+            .position(|p| *p == "drive_c")
+            .map(|i| &parts[i + 1..])
+    };
+    match drive {
+        Some(rest) => !is_shared_windows_dir(rest),
+        None => !matches!(
+            parts.as_slice(),
+            ["usr" | "bin" | "sbin" | "lib" | "lib32" | "lib64" | "etc" | "proc" | "sys", ..]
+                | ["dev" | "boot", ..]
+                | ["home", _]
+        ),
+    }
+}
+
+/// Folders on a Windows drive shared by many programs, given the lowercase
+/// path below the drive root.
+fn is_shared_windows_dir(rest: &[&str]) -> bool {
+    matches!(
+        rest,
+        [] | [_]
+            | ["windows", ..]
+            | ["programdata", ..]
+            | ["users", _]
+            | ["users", _, "appdata" | "documents" | "desktop" | "downloads" | "saved games"]
+            | ["users", _, "appdata", _]
+    )
+}
+
+/// Snapshot of all live processes on this machine.
+#[cfg(target_os = "linux")]
+pub fn snapshot() -> Vec<ProcessInfo> {
+    snapshot_from(Path::new("/proc"))
+}
+
+/// Snapshot of all live processes on this machine.
+#[cfg(windows)]
+pub fn snapshot() -> Vec<ProcessInfo> {
+    toolhelp::snapshot()
+}
+
+/// Snapshot of all live processes on this machine.
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn snapshot() -> Vec<ProcessInfo> {
+    Vec::new()
+}
+
+/// Snapshot of the live processes under a `/proc`-style directory (useful
+/// for testing). Zombies are left out, since they have already exited.
+pub fn snapshot_from(proc_root: &Path) -> Vec<ProcessInfo> {
+    let Ok(entries) = std::fs::read_dir(proc_root) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse().ok()?;
+            read_process(&entry.path(), pid)
+        })
+        .collect()
+}
+
+fn read_process(dir: &Path, pid: u32) -> Option<ProcessInfo> {
+    // `stat` is "pid (comm) state ppid ..."; comm may contain spaces and
+    // parentheses, so fields are counted from the last ')'
+    let stat = std::fs::read_to_string(dir.join("stat")).ok()?;
+    let mut fields = stat[stat.rfind(')')? + 1..].split_whitespace();
+    let state = fields.next()?;
+    if state == "Z" || state == "X" {
+        return None;
+    }
+    let ppid = fields.next()?.parse().ok()?;
+
+    let cmdline = std::fs::read(dir.join("cmdline"))
+        .map(|bytes| split_nul(&bytes))
+        .unwrap_or_default();
+    let wine_prefix = std::fs::read(dir.join("environ")).ok().and_then(|bytes| {
+        split_nul(&bytes)
+            .into_iter()
+            .find_map(|var| var.strip_prefix("WINEPREFIX=").map(PathBuf::from))
+    });
+
+    Some(ProcessInfo {
+        pid,
+        ppid,
+        exe: std::fs::read_link(dir.join("exe")).ok(),
+        cmdline,
+        wine_prefix,
+    })
+}
+
+fn split_nul(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .split(|&b| b == 0)
+        .filter(|part| !part.is_empty())
+        .map(|part| String::from_utf8_lossy(part).to_string())
+        .collect()
+}
+
+#[cfg(windows)]
+mod toolhelp {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStringExt;
+    use std::path::PathBuf;
+
+    use winapi::shared::minwindef::{DWORD, FALSE};
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::processthreadsapi::OpenProcess;
+    use winapi::um::tlhelp32::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+    use winapi::um::winbase::QueryFullProcessImageNameW;
+    use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+
+    use super::ProcessInfo;
+
+    /// Every process with its parent and, where access allows, its
+    /// executable. Command lines can't be read without debugging rights.
+    pub fn snapshot() -> Vec<ProcessInfo> {
+        let mut processes = Vec::new();
+        // SAFETY: the snapshot handle is checked and closed, and the entry
+        // is a zeroed struct with `dwSize` set as the API requires.
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+            if snapshot == INVALID_HANDLE_VALUE {
+                return processes;
+            }
+            let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+            entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as DWORD;
+            let mut more = Process32FirstW(snapshot, &mut entry) != 0;
+            while more {
+                processes.push(ProcessInfo {
+                    pid: entry.th32ProcessID,
+                    ppid: entry.th32ParentProcessID,
+                    exe: image_path(entry.th32ProcessID),
+                    cmdline: Vec::new(),
+                    wine_prefix: None,
+                });
+                more = Process32NextW(snapshot, &mut entry) != 0;
+            }
+            CloseHandle(snapshot);
+        }
+        processes
+    }
+
+    fn image_path(pid: u32) -> Option<PathBuf> {
+        let mut buf = vec![0u16; 32 * 1024];
+        let mut len = buf.len() as DWORD;
+        // SAFETY: the process handle is checked and closed, and `len` holds
+        // the buffer's size in characters.
+        unsafe {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid);
+            if process.is_null() {
+                return None;
+            }
+            let ok = QueryFullProcessImageNameW(process, 0, buf.as_mut_ptr(), &mut len) != 0;
+            CloseHandle(process);
+            ok.then(|| PathBuf::from(OsString::from_wide(&buf[..len as usize])))
+        }
+    }
+}
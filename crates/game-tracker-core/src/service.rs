@@ -1,12 +1,19 @@
 //! GameService — orchestrates business logic for game CRUD, indexing, and enrichment.
 
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use sqlx::SqlitePool;
 
+use crate::indexers::{exe_detect, IndexerRegistry, LauncherIndexer};
+use crate::launch::{LaunchPlanner, LaunchedGame, ProcessWatch};
 use crate::models::*;
-use crate::{db, icon_extract, indexers, rawg::RawgClient};
+use crate::watcher::WatchTarget;
+use crate::{db, icon_extract, process, rawg::RawgClient};
+
+/// `settings` table key holding the JSON-encoded [`AppSettings`].
+const APP_SETTINGS_KEY: &str = "app_settings";
 
 /// High-level coordinator for library operations used by Tauri commands.
 pub struct GameService {
@@ -16,19 +23,68 @@ pub struct GameService {
     pub rawg_client: Arc<RawgClient>,
     /// Directory where downloaded covers and extracted icons are stored.
     pub icons_dir: PathBuf,
+    /// Current user settings (defaults until [`GameService::load_settings`] runs).
+    pub settings: AppSettings,
+    /// Launcher indexers built from `settings`.
+    pub indexers: IndexerRegistry,
+    /// Where indexers keep state between scans; `None` keeps none.
+    pub cache_dir: Option<PathBuf>,
 }
 
 impl GameService {
     /// Create a new service and ensure icon storage exists.
     pub fn new(pool: SqlitePool, rawg_client: Arc<RawgClient>, icons_dir: PathBuf) -> Self {
         std::fs::create_dir_all(&icons_dir).ok();
+        let settings = AppSettings::default();
+        let indexers = IndexerRegistry::from_settings(&settings);
         Self {
             pool,
             rawg_client,
             icons_dir,
+            settings,
+            indexers,
+            cache_dir: None,
         }
     }
 
+    /// Let indexers cache state (such as ROM hashes) under `cache_dir`.
+    pub fn set_cache_dir(&mut self, cache_dir: PathBuf) {
+        self.cache_dir = Some(cache_dir);
+        self.indexers =
+            IndexerRegistry::from_settings_cached(&self.settings, self.cache_dir.as_deref());
+    }
+
+    // ---- Settings -----------------------------------------------------------
+
+    /// Load persisted settings and rebuild the indexer registry from them.
+    pub async fn load_settings(&mut self) -> Result<(), String> {
+        let raw = db::get_setting(&self.pool, APP_SETTINGS_KEY)
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Some(raw) = raw {
+            self.settings = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
+        }
+        self.indexers =
+            IndexerRegistry::from_settings_cached(&self.settings, self.cache_dir.as_deref());
+        Ok(())
+    }
+
+    /// Return the current settings.
+    pub fn get_settings(&self) -> AppSettings {
+        self.settings.clone()
+    }
+
+    /// Persist new settings and apply them to the indexer registry.
+    pub async fn update_settings(&mut self, settings: AppSettings) -> Result<(), String> {
+        let raw = serde_json::to_string(&settings).map_err(|e| e.to_string())?;
+        db::set_setting(&self.pool, APP_SETTINGS_KEY, &raw)
+            .await
+            .map_err(|e| e.to_string())?;
+        self.indexers = IndexerRegistry::from_settings_cached(&settings, self.cache_dir.as_deref());
+        self.settings = settings;
+        Ok(())
+    }
+
     // ---- CRUD ---------------------------------------------------------------
 
     /// Return the full game library.
@@ -73,6 +129,10 @@ impl GameService {
             source: input.source,
             source_id: input.source_id,
             install_path: input.install_path,
+            exe_path_locked: false,
+            installed: true,
+            uninstalled_at: None,
+            launch_override: None,
         };
 
         // Enrich from RAWG if a match was selected
@@ -129,6 +189,237 @@ impl GameService {
             .map_err(|e| e.to_string())
     }
 
+    /// Rank executables under a game's install directory so the user can pick one.
+    pub async fn exe_candidates(&self, id: i32) -> Result<ExeDetection, String> {
+        let game = db::get_game_by_id(&self.pool, id)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(match game.install_path {
+            Some(ref install) => exe_detect::detect_main_exe(Path::new(install), &game.title),
+            None => ExeDetection::default(),
+        })
+    }
+
+    /// Set a game's executable; indexing will no longer replace it.
+    pub async fn set_game_exe(&self, id: i32, exe_path: &str) -> Result<(), String> {
+        if !Path::new(exe_path).is_file() {
+            return Err(format!("Executable not found: {}", exe_path));
+        }
+        db::set_game_exe(&self.pool, id, exe_path)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// List the launchers a game is owned on.
+    pub async fn game_sources(&self, id: i32) -> Result<Vec<GameSource>, String> {
+        db::get_game_sources(&self.pool, id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Merge duplicate game `from` into `into`; sources, sessions and
+    /// playtime move over and `from` is deleted.
+    pub async fn merge_games(&self, into: i32, from: i32) -> Result<Game, String> {
+        if into == from {
+            return Err("Cannot merge a game into itself".to_string());
+        }
+        db::get_game_by_id(&self.pool, from)
+            .await
+            .map_err(|e| e.to_string())?;
+        db::merge_games(&self.pool, into, from)
+            .await
+            .map_err(|e| e.to_string())?;
+        db::get_game_by_id(&self.pool, into)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Split one source off its game into a new game and return that game.
+    pub async fn split_game_source(&self, source_row_id: i32) -> Result<Game, String> {
+        let sources = db::get_all_game_sources(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        let source = sources
+            .iter()
+            .find(|s| s.id == source_row_id)
+            .ok_or_else(|| format!("Unknown game source: {}", source_row_id))?;
+        if sources.iter().filter(|s| s.game_id == source.game_id).count() < 2 {
+            return Err("A game's only source can't be split off".to_string());
+        }
+
+        let id = db::split_game_source(&self.pool, source_row_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        db::get_game_by_id(&self.pool, id as i32)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    // ---- Play sessions --------------------------------------------------------
+
+    /// List a game's play sessions, most recent first.
+    pub async fn list_play_sessions(&self, game_id: i32) -> Result<Vec<PlaySession>, String> {
+        db::get_play_sessions(&self.pool, game_id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Log a play session; the game's playtime and last-played follow.
+    pub async fn log_play_session(
+        &self,
+        game_id: i32,
+        input: PlaySessionInput,
+    ) -> Result<PlaySession, String> {
+        validate_duration(input.duration_minutes)?;
+        let date = input.session_date.as_deref().map(parse_session_date).transpose()?;
+        db::get_game_by_id(&self.pool, game_id)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let id = db::insert_play_session(
+            &self.pool,
+            game_id,
+            date.as_deref(),
+            input.duration_minutes,
+            input.notes.as_deref(),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        db::get_play_session(&self.pool, id as i32)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Edit a play session; the game's playtime moves by the difference.
+    pub async fn update_play_session(
+        &self,
+        id: i32,
+        input: PlaySessionInput,
+    ) -> Result<PlaySession, String> {
+        validate_duration(input.duration_minutes)?;
+        let existing = db::get_play_session(&self.pool, id)
+            .await
+            .map_err(|e| e.to_string())?;
+        let date = match input.session_date.as_deref() {
+            Some(date) => parse_session_date(date)?,
+            None => existing.session_date,
+        };
+
+        db::update_play_session(
+            &self.pool,
+            id,
+            &date,
+            input.duration_minutes,
+            input.notes.as_deref(),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        db::get_play_session(&self.pool, id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Delete a play session and take its time off the game's playtime.
+    pub async fn delete_play_session(&self, id: i32) -> Result<(), String> {
+        db::delete_play_session(&self.pool, id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Start a game and open a tracked session for it. Launch plans are
+    /// tried in order until one starts. The caller follows the returned
+    /// game with [`crate::launch::track_session`] until it exits.
+    ///
+    /// Returns `None` when the game was started through a URI or launcher
+    /// client on a platform without process snapshots: nothing could tell
+    /// when it exits, so no session is opened.
+    pub async fn launch_game(&self, id: i32) -> Result<Option<LaunchedGame>, String> {
+        let game = db::get_game_by_id(&self.pool, id)
+            .await
+            .map_err(|e| e.to_string())?;
+        let plans = LaunchPlanner::from_settings(&self.settings).plan(&game);
+        if plans.is_empty() {
+            return Err(format!("No executable or launcher to start {}", game.title));
+        }
+
+        let mut errors = Vec::new();
+        let mut started = None;
+        for (strategy, command) in plans {
+            match command.spawn() {
+                Ok(child) => {
+                    started = Some((strategy, command, child));
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!("Launching {} via {} failed: {}", game.title, strategy, e);
+                    errors.push(format!("{}: {}", strategy, e));
+                }
+            }
+        }
+        let Some((strategy, command, mut child)) = started else {
+            return Err(format!("Failed to launch {}: {}", game.title, errors.join("; ")));
+        };
+
+        // A URI handler or launcher client may outlive the game or start the
+        // launcher itself, so only the game's own process roots the tree
+        let root_pid = command.is_game_process().then(|| child.id());
+        if root_pid.is_none() && !process::SNAPSHOTS_SUPPORTED {
+            tracing::info!(
+                "Launched {} via {}; its play time can't be tracked here",
+                game.title,
+                strategy
+            );
+            // Reap the handler so it doesn't linger as a zombie
+            std::thread::spawn(move || child.wait());
+            return Ok(None);
+        }
+        let session_id = db::start_play_session(&self.pool, id)
+            .await
+            .map_err(|e| e.to_string())?;
+        tracing::info!("Launched {} via {} (session {})", game.title, strategy, session_id);
+
+        Ok(Some(LaunchedGame {
+            game_id: id,
+            session_id,
+            child: Some(child),
+            watch: ProcessWatch::new(root_pid, &game),
+        }))
+    }
+
+    /// Set or clear (`None` or blank) a game's launch override: a strategy
+    /// id such as `"exe"` or `"steam"`, or a custom command line or URI.
+    pub async fn set_launch_override(
+        &self,
+        id: i32,
+        launch_override: Option<String>,
+    ) -> Result<(), String> {
+        let launch_override = launch_override
+            .map(|o| o.trim().to_string())
+            .filter(|o| !o.is_empty());
+        db::set_launch_override(&self.pool, id, launch_override.as_deref())
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Ids of the built-in launch strategies, for the override picker.
+    pub fn launch_strategies(&self) -> Vec<&'static str> {
+        LaunchPlanner::from_settings(&self.settings).strategy_ids()
+    }
+
+    /// Close sessions left open by a crash or shutdown; call at startup.
+    pub async fn close_stale_sessions(&self) -> Result<u64, String> {
+        db::close_stale_sessions(&self.pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Fill in normalized titles missing from older games; call at startup.
+    pub async fn fill_normalized_titles(&self) -> Result<u64, String> {
+        db::fill_normalized_titles(&self.pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
     /// Compute aggregate statistics for the stats view.
     pub async fn get_stats(&self) -> Result<GameStats, String> {
         let total_games = db::count_games(&self.pool).await.map_err(|e| e.to_string())?;
@@ -156,57 +447,509 @@ impl GameService {
 
     // ---- Indexing ------------------------------------------------------------
 
-    /// Run indexing for all supported launchers.
+    /// List registered indexers with their enabled/available state.
+    pub fn list_indexers(&self) -> Vec<IndexerInfo> {
+        self.indexers.list()
+    }
+
+    /// Enable or disable an indexer and persist the choice.
+    pub async fn set_indexer_enabled(&mut self, id: &str, enabled: bool) -> Result<(), String> {
+        if self.indexers.get(id).is_none() {
+            return Err(format!("Unknown indexer: {}", id));
+        }
+        let mut settings = self.settings.clone();
+        settings.disabled_indexers.retain(|d| d != id);
+        if !enabled {
+            settings.disabled_indexers.push(id.to_string());
+        }
+        self.update_settings(settings).await
+    }
+
+    /// Launcher directories to watch for enabled indexers, or none when
+    /// watching is switched off in settings.
+    pub fn watch_targets(&self) -> Vec<WatchTarget> {
+        if !self.settings.watch_launchers {
+            return Vec::new();
+        }
+        self.indexers
+            .enabled()
+            .flat_map(|indexer| {
+                indexer.watch_paths().into_iter().map(|path| WatchTarget {
+                    source: indexer.id().to_string(),
+                    path,
+                    recursive: indexer.watch_recursive(),
+                })
+            })
+            .collect()
+    }
+
+    /// Run indexing for all enabled launchers.
     ///
     /// `upserted` counts successful upsert operations (both inserts and updates).
+    /// A failing launcher is logged and skipped so the others still run, and
+    /// its games keep their install state.
     pub async fn index_all(&self) -> Result<IndexResult, String> {
-        let mut total_discovered = 0u32;
-        let mut total_new = 0u32;
-
-        // Steam
-        match indexers::steam::scan_steam_games() {
-            Ok(steam_games) => {
-                total_discovered += steam_games.len() as u32;
-                for dg in steam_games {
-                    let game = discovered_to_game(&dg);
-                    match db::upsert_game_by_source(&self.pool, &game).await {
-                        Ok(_id) => total_new += 1,
-                        Err(e) => tracing::warn!("Failed to upsert Steam game {}: {}", dg.title, e),
+        self.index_all_with_progress(&|_| {}).await
+    }
+
+    /// [`GameService::index_all`], reporting each step to `progress` as it
+    /// happens.
+    pub async fn index_all_with_progress(
+        &self,
+        progress: &(dyn Fn(IndexProgress) + Sync),
+    ) -> Result<IndexResult, String> {
+        self.library_indexer().index_with_progress(progress).await
+    }
+
+    /// Handle for indexing all enabled launchers without borrowing the
+    /// service, as [`GameService::source_indexer`] does for one.
+    pub fn library_indexer(&self) -> LibraryIndexer {
+        LibraryIndexer {
+            sources: self
+                .indexers
+                .enabled()
+                .filter_map(|indexer| self.source_indexer(indexer.id()).ok())
+                .collect(),
+            pool: self.pool.clone(),
+        }
+    }
+
+    /// Run indexing for a single launcher by id, even if it is disabled.
+    pub async fn index_source(&self, source: &str) -> Result<IndexResult, String> {
+        self.source_indexer(source)?.index().await
+    }
+
+    /// Handle for indexing one launcher by id (even if it is disabled)
+    /// without borrowing the service, so callers can release a lock on it
+    /// for the duration of the scan.
+    pub fn source_indexer(&self, source: &str) -> Result<SourceIndexer, String> {
+        let indexer = self
+            .indexers
+            .get_shared(source)
+            .ok_or_else(|| format!("Unknown indexer: {}", source))?;
+        Ok(SourceIndexer {
+            indexer,
+            pool: self.pool.clone(),
+        })
+    }
+
+    /// Run all enabled indexers and report what [`GameService::index_all`]
+    /// would change, without writing anything.
+    pub async fn preview_index(&self) -> Result<Vec<IndexChange>, String> {
+        self.library_indexer().preview().await
+    }
+
+    /// Apply entries picked from [`GameService::preview_index`].
+    ///
+    /// New, attached and updated entries are upserted, vanished sources
+    /// marked uninstalled. An entry that is malformed or fails to write is
+    /// logged and counted in [`IndexResult::failed`]; the rest still apply.
+    pub async fn apply_index_changes(
+        &self,
+        changes: Vec<IndexChange>,
+    ) -> Result<IndexResult, String> {
+        let mut result = IndexResult::default();
+
+        for change in changes {
+            match (change.kind, change.discovered, change.existing) {
+                (
+                    IndexChangeKind::New | IndexChangeKind::Attach | IndexChangeKind::Updated,
+                    Some(dg),
+                    _,
+                ) => {
+                    result.discovered += 1;
+                    match db::upsert_game_by_source(&self.pool, &discovered_to_game(&dg)).await {
+                        Ok(_id) => result.upserted += 1,
+                        Err(e) => {
+                            tracing::warn!("Failed to upsert {}: {}", dg.title, e);
+                            result.failed += 1;
+                        }
+                    }
+                }
+                (
+                    IndexChangeKind::Vanished,
+                    _,
+                    Some(Game {
+                        source: Some(source),
+                        source_id: Some(source_id),
+                        ..
+                    }),
+                ) => match db::mark_source_uninstalled(&self.pool, &source, &source_id).await {
+                    Ok(true) => result.uninstalled += 1,
+                    Ok(false) => {}
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to mark {} {} uninstalled: {}",
+                            source,
+                            source_id,
+                            e
+                        );
+                        result.failed += 1;
                     }
+                },
+                (kind, ..) => {
+                    tracing::warn!("Skipping malformed {:?} index change", kind);
+                    result.failed += 1;
                 }
             }
-            Err(e) => tracing::warn!("Steam indexing failed: {}", e),
-        }
-
-        // Epic
-        match indexers::epic::scan_epic_games() {
-            Ok(epic_games) => {
-                total_discovered += epic_games.len() as u32;
-                for dg in epic_games {
-                    let game = discovered_to_game(&dg);
-                    match db::upsert_game_by_source(&self.pool, &game).await {
-                        Ok(_id) => total_new += 1,
-                        Err(e) => tracing::warn!("Failed to upsert Epic game {}: {}", dg.title, e),
+        }
+
+        Ok(result)
+    }
+}
+
+/// All enabled launchers detached from the [`GameService`], from
+/// [`GameService::library_indexer`].
+#[derive(Clone)]
+pub struct LibraryIndexer {
+    sources: Vec<SourceIndexer>,
+    pool: SqlitePool,
+}
+
+impl LibraryIndexer {
+    /// Full scan of every launcher, as [`GameService::index_all_with_progress`].
+    pub async fn index_with_progress(
+        &self,
+        progress: &(dyn Fn(IndexProgress) + Sync),
+    ) -> Result<IndexResult, String> {
+        let mut total = IndexResult::default();
+
+        for source in &self.sources {
+            match source.index_with_progress(progress).await {
+                Ok(result) => {
+                    total.discovered += result.discovered;
+                    total.upserted += result.upserted;
+                    total.uninstalled += result.uninstalled;
+                    total.failed += result.failed;
+                }
+                Err(e) => tracing::warn!(
+                    "{} indexing failed: {}",
+                    source.indexer.display_name(),
+                    e
+                ),
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// Dry run, as [`GameService::preview_index`].
+    pub async fn preview(&self) -> Result<Vec<IndexChange>, String> {
+        let games = db::get_all_games(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        let sources = db::get_all_game_sources(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        let by_id: HashMap<i32, &Game> = games.iter().map(|g| (g.id, g)).collect();
+        let by_source: HashMap<(&str, &str), &GameSource> = sources
+            .iter()
+            .map(|s| ((s.source.as_str(), s.source_id.as_str()), s))
+            .collect();
+        // First game per normalized title, as `db::find_game_for_source`
+        // picks. Games previewed as New join it so later sources attach to
+        // them, as they would when applied.
+        let mut by_title: HashMap<(String, String), Game> = HashMap::new();
+        for game in games.iter().rev() {
+            by_title.insert(
+                (db::normalize_title(&game.title), game.platform.clone()),
+                game.clone(),
+            );
+        }
+
+        let mut changes = Vec::new();
+
+        for source_indexer in &self.sources {
+            let indexer = source_indexer.indexer.clone();
+            let (scanned, available) =
+                tokio::task::spawn_blocking(move || (indexer.scan(), indexer.is_available()))
+                    .await
+                    .map_err(|e| e.to_string())?;
+            let indexer = source_indexer.indexer.as_ref();
+            let discovered = match scanned {
+                Ok(d) => d,
+                Err(e) => {
+                    tracing::warn!("{} preview failed: {}", indexer.display_name(), e);
+                    continue;
+                }
+            };
+            let mut present = HashSet::new();
+
+            for dg in discovered {
+                present.insert(dg.source_id.clone());
+                let known = by_source
+                    .get(&(dg.source.as_str(), dg.source_id.as_str()))
+                    .and_then(|s| Some((*s, *by_id.get(&s.game_id)?)));
+
+                match known {
+                    Some((source, game)) => {
+                        let fields = changed_fields(game, source, &dg);
+                        if !fields.is_empty() {
+                            changes.push(IndexChange {
+                                kind: IndexChangeKind::Updated,
+                                discovered: Some(dg),
+                                existing: Some(game.clone()),
+                                fields,
+                            });
+                        }
+                    }
+                    None => {
+                        let key = (db::normalize_title(&dg.title), dg.platform.clone());
+                        let owner = by_title.get(&key).cloned();
+                        if owner.is_none() {
+                            by_title.insert(key, discovered_to_game(&dg));
+                        }
+                        changes.push(IndexChange {
+                            kind: match owner {
+                                Some(_) => IndexChangeKind::Attach,
+                                None => IndexChangeKind::New,
+                            },
+                            discovered: Some(dg),
+                            existing: owner,
+                            fields: Vec::new(),
+                        });
                     }
                 }
             }
-            Err(e) => tracing::warn!("Epic indexing failed: {}", e),
+
+            // Same rule as `store_scan`: no verdict on unavailable launchers
+            if !available {
+                continue;
+            }
+            for source in &sources {
+                let missing = source.installed
+                    && source.source == indexer.id()
+                    && !present.contains(&source.source_id);
+                let Some(game) = by_id.get(&source.game_id).filter(|_| missing) else {
+                    continue;
+                };
+                changes.push(IndexChange {
+                    kind: IndexChangeKind::Vanished,
+                    discovered: None,
+                    existing: Some(Game {
+                        source: Some(source.source.clone()),
+                        source_id: Some(source.source_id.clone()),
+                        install_path: source.install_path.clone(),
+                        exe_path: source.exe_path.clone(),
+                        ..(*game).clone()
+                    }),
+                    fields: Vec::new(),
+                });
+            }
         }
 
-        Ok(IndexResult {
-            discovered: total_discovered,
-            upserted: total_new,
-        })
+        Ok(changes)
+    }
+}
+
+/// One launcher detached from the [`GameService`], from
+/// [`GameService::source_indexer`]. Scans run on a blocking thread.
+#[derive(Clone)]
+pub struct SourceIndexer {
+    indexer: Arc<dyn LauncherIndexer>,
+    pool: SqlitePool,
+}
+
+impl SourceIndexer {
+    /// Full scan, as [`GameService::index_source`].
+    pub async fn index(&self) -> Result<IndexResult, String> {
+        self.index_with_progress(&|_| {}).await
+    }
+
+    /// Scan the launcher, upsert everything it reports and mark its games
+    /// that were not reported as uninstalled, reporting each step to
+    /// `progress`.
+    pub async fn index_with_progress(
+        &self,
+        progress: &(dyn Fn(IndexProgress) + Sync),
+    ) -> Result<IndexResult, String> {
+        progress(IndexProgress::SourceStarted {
+            source: self.indexer.id().to_string(),
+            display_name: self.indexer.display_name().to_string(),
+        });
+        let indexer = self.indexer.clone();
+        let scanned = tokio::task::spawn_blocking(move || indexer.scan())
+            .await
+            .map_err(|e| e.to_string())?;
+        store_scan(&self.pool, self.indexer.as_ref(), scanned, true, progress).await
+    }
+
+    /// Re-index after the launcher files at `changed` were written: only
+    /// the games behind them when the indexer can tell
+    /// ([`LauncherIndexer::scan_changed`]), otherwise a full scan. A partial
+    /// scan marks nothing uninstalled.
+    pub async fn index_changes(&self, changed: Vec<PathBuf>) -> Result<IndexResult, String> {
+        let indexer = self.indexer.clone();
+        let (scanned, complete) =
+            tokio::task::spawn_blocking(move || match indexer.scan_changed(&changed) {
+                Some(scanned) => (scanned, false),
+                None => (indexer.scan(), true),
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+        store_scan(&self.pool, self.indexer.as_ref(), scanned, complete, &|_| {}).await
+    }
+}
+
+/// Upsert what `indexer` reported. When the scan was `complete`, the
+/// source's games it did not report are marked uninstalled.
+async fn store_scan(
+    pool: &SqlitePool,
+    indexer: &dyn LauncherIndexer,
+    scanned: Result<Vec<DiscoveredGame>, crate::indexers::IndexerError>,
+    complete: bool,
+    progress: &(dyn Fn(IndexProgress) + Sync),
+) -> Result<IndexResult, String> {
+    let source = indexer.id().to_string();
+    let discovered = match scanned {
+        Ok(d) => d,
+        Err(e) => {
+            progress(IndexProgress::SourceFailed {
+                source,
+                error: e.to_string(),
+            });
+            return Err(e.to_string());
+        }
+    };
+    let mut result = IndexResult {
+        discovered: discovered.len() as u32,
+        ..Default::default()
+    };
+    let present: Vec<String> = discovered.iter().map(|dg| dg.source_id.clone()).collect();
+
+    for dg in discovered {
+        progress(IndexProgress::GameDiscovered {
+            source: source.clone(),
+            title: dg.title.clone(),
+        });
+        let game = discovered_to_game(&dg);
+        match db::upsert_game_by_source(pool, &game).await {
+            Ok(id) => {
+                result.upserted += 1;
+                progress(IndexProgress::GameUpserted {
+                    source: source.clone(),
+                    title: dg.title,
+                    game_id: id,
+                });
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to upsert {} game {}: {}",
+                    indexer.display_name(),
+                    dg.title,
+                    e
+                );
+                result.failed += 1;
+                progress(IndexProgress::GameFailed {
+                    source: source.clone(),
+                    title: dg.title,
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    // An unavailable launcher scans as empty; that says nothing about
+    // whether its games are still installed
+    if complete && indexer.is_available() {
+        result.uninstalled = db::mark_missing_uninstalled(pool, indexer.id(), &present)
+            .await
+            .map_err(|e| e.to_string())? as u32;
     }
+
+    progress(IndexProgress::SourceFinished {
+        source,
+        result: result.clone(),
+    });
+    Ok(result)
 }
 
 /// Summary of an indexing pass.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct IndexResult {
     /// Number of launcher entries discovered by indexers.
     pub discovered: u32,
     /// Number of rows successfully upserted into the database.
     pub upserted: u32,
+    /// Number of games newly marked uninstalled because their launcher no
+    /// longer reports them.
+    pub uninstalled: u32,
+    /// Number of entries that could not be written.
+    pub failed: u32,
+}
+
+/// Step of an indexing run, reported while it is in progress.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IndexProgress {
+    /// A launcher scan began.
+    SourceStarted { source: String, display_name: String },
+    /// A launcher reported a game; its upsert follows.
+    GameDiscovered { source: String, title: String },
+    /// A discovered game was inserted or updated.
+    GameUpserted {
+        source: String,
+        title: String,
+        game_id: i64,
+    },
+    /// A discovered game could not be written.
+    GameFailed {
+        source: String,
+        title: String,
+        error: String,
+    },
+    /// A launcher finished; `result` covers that launcher only.
+    SourceFinished { source: String, result: IndexResult },
+    /// A launcher scan failed; the run continues with the next one.
+    SourceFailed { source: String, error: String },
+}
+
+/// Reject empty or negative session lengths.
+fn validate_duration(minutes: i32) -> Result<(), String> {
+    if minutes <= 0 {
+        return Err("Session duration must be positive".to_string());
+    }
+    Ok(())
+}
+
+/// Normalize a user-entered session date to `YYYY-MM-DD HH:MM:SS`. Accepts
+/// that form, ISO `T`-separated date-times (as `<input type="datetime-local">`
+/// sends them) and bare dates.
+fn parse_session_date(date: &str) -> Result<String, String> {
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    let date = date.trim();
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(date, fmt).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .ok_or_else(|| format!("Invalid session date: {}", date))
+}
+
+/// Fields an upsert of `dg` would change on `source` of `game`. The title
+/// only follows the game's primary source.
+fn changed_fields(game: &Game, source: &GameSource, dg: &DiscoveredGame) -> Vec<String> {
+    let is_primary = game.source.as_deref() == Some(source.source.as_str())
+        && game.source_id.as_deref() == Some(source.source_id.as_str());
+    let mut fields = Vec::new();
+    if is_primary && game.title != dg.title {
+        fields.push("title".to_string());
+    }
+    if source.install_path != dg.install_path {
+        fields.push("install_path".to_string());
+    }
+    if !(is_primary && game.exe_path_locked) && source.exe_path != dg.exe_path {
+        fields.push("exe_path".to_string());
+    }
+    if !source.installed {
+        fields.push("installed".to_string());
+    }
+    fields
 }
 
 fn discovered_to_game(dg: &DiscoveredGame) -> Game {
@@ -222,12 +965,16 @@ fn discovered_to_game(dg: &DiscoveredGame) -> Game {
         cover_url: None,
         rawg_id: None,
         exe_path: dg.exe_path.clone(),
-        playtime_hours: 0.0,
+        playtime_hours: dg.playtime_hours.unwrap_or(0.0),
         rating: None,
         added_date: String::new(),
-        last_played: None,
+        last_played: dg.last_played.clone(),
         source: Some(dg.source.clone()),
         source_id: Some(dg.source_id.clone()),
         install_path: dg.install_path.clone(),
+        exe_path_locked: false,
+        installed: true,
+        uninstalled_at: None,
+        launch_override: None,
     }
 }
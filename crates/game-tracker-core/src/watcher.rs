@@ -0,0 +1,106 @@
+//! Filesystem watcher that triggers per-source re-indexing.
+//!
+//! Launchers write small manifest files when a game is installed, moved or
+//! removed (Steam's `appmanifest_*.acf`, Epic's `.item`). Watching those
+//! directories tells us which source changed, so only that source needs to be
+//! re-indexed, and which files changed, so an indexer that supports it can
+//! re-read just those games.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use notify_debouncer_mini::notify::{self, RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+
+/// Directory to watch and the indexer id that owns it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchTarget {
+    pub source: String,
+    pub path: PathBuf,
+    /// Watch subdirectories too, not just the directory's own entries.
+    pub recursive: bool,
+}
+
+/// Launcher scratch directories rewritten continuously while downloading
+/// (Steam's `steamapps/downloading` and `steamapps/temp`); changes there say
+/// nothing about what is installed.
+const CHURN_DIRS: &[&str] = &["downloading", "temp"];
+
+/// Running watcher; dropping it stops watching.
+pub struct LibraryWatcher {
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+impl LibraryWatcher {
+    /// Watch every target directory and call `on_change` with the id of each
+    /// source whose files changed and those files, once per quiet period of
+    /// `debounce`.
+    ///
+    /// Targets that can't be watched (missing, permissions) are logged and
+    /// skipped.
+    pub fn start<F>(
+        targets: Vec<WatchTarget>,
+        debounce: Duration,
+        on_change: F,
+    ) -> Result<Self, notify::Error>
+    where
+        F: Fn(&str, &[PathBuf]) + Send + 'static,
+    {
+        let watched = targets.clone();
+        let mut debouncer = new_debouncer(debounce, move |res: DebounceEventResult| match res {
+            Ok(events) => {
+                let paths: Vec<&Path> = events.iter().map(|e| e.path.as_path()).collect();
+                for (source, changed) in changed_sources(&watched, &paths) {
+                    on_change(&source, &changed);
+                }
+            }
+            Err(e) => tracing::warn!("Library watcher error: {}", e),
+        })?;
+
+        for target in &targets {
+            // Manifests sit directly in most watched directories
+            let mode = if target.recursive {
+                RecursiveMode::Recursive
+            } else {
+                RecursiveMode::NonRecursive
+            };
+            if let Err(e) = debouncer.watcher().watch(&target.path, mode) {
+                tracing::warn!(
+                    "Cannot watch {:?} for {}: {}",
+                    target.path,
+                    target.source,
+                    e
+                );
+            }
+        }
+
+        Ok(Self {
+            _debouncer: debouncer,
+        })
+    }
+}
+
+/// Changed `paths` grouped by the source owning them. Paths inside launcher
+/// scratch directories are dropped.
+pub fn changed_sources(targets: &[WatchTarget], paths: &[&Path]) -> BTreeMap<String, Vec<PathBuf>> {
+    let mut changes: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for path in paths {
+        for target in targets {
+            let Ok(relative) = path.strip_prefix(&target.path) else {
+                continue;
+            };
+            let churn = relative
+                .components()
+                .next()
+                .is_some_and(|c| CHURN_DIRS.iter().any(|d| c.as_os_str() == *d));
+            if !churn {
+                changes
+                    .entry(target.source.clone())
+                    .or_default()
+                    .push(path.to_path_buf());
+            }
+        }
+    }
+    changes
+}
@@ -0,0 +1,56 @@
+//! Tests for Amazon Games store parsing.
+
+use game_tracker_core::indexers::amazon::{main_exe, scan_amazon_games_from};
+use std::path::PathBuf;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/amazon")
+}
+
+#[test]
+fn test_scan_amazon_discovers_installed_games_only() {
+    let games = scan_amazon_games_from(&fixtures_dir().join("Sql")).expect("scan should succeed");
+
+    assert_eq!(games.len(), 2, "got {:?}", games);
+    assert!(games.iter().all(|g| g.source == "amazon" && g.platform == "PC"));
+    assert!(!games.iter().any(|g| g.title == "Removed Game"));
+}
+
+#[test]
+fn test_amazon_game_fields() {
+    let games = scan_amazon_games_from(&fixtures_dir().join("Sql")).expect("scan should succeed");
+
+    // Title comes from the product store, not the install store's copy
+    let starfall = games
+        .iter()
+        .find(|g| g.source_id == "amzn1.adg.product.1b2c3d4e-0001")
+        .unwrap();
+    assert_eq!(starfall.title, "Starfall Tactics");
+    assert_eq!(
+        starfall.install_path.as_deref(),
+        Some(r"C:\Amazon Games\Library\Starfall Tactics")
+    );
+    // The install directory doesn't exist in test
+    assert!(starfall.exe_path.is_none());
+
+    // Missing from the product store: falls back to the install store title
+    let neon = games
+        .iter()
+        .find(|g| g.source_id == "amzn1.adg.product.1b2c3d4e-0002")
+        .unwrap();
+    assert_eq!(neon.title, "Neon Drift");
+}
+
+#[test]
+fn test_amazon_main_exe_from_fuel_json() {
+    let install_dir = fixtures_dir().join("Library/Tiny Quest");
+    let exe = main_exe(&install_dir, "Tiny Quest").expect("fuel.json command should resolve");
+    assert!(exe.ends_with("TinyQuest.exe"), "got {}", exe);
+}
+
+#[test]
+fn test_amazon_missing_db_returns_empty() {
+    let games = scan_amazon_games_from(&PathBuf::from("/nonexistent/Amazon Games/Sql"))
+        .expect("scan should succeed even for missing db");
+    assert!(games.is_empty());
+}
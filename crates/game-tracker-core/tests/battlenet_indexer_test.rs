@@ -0,0 +1,77 @@
+//! Tests for Battle.net `product.db` decoding.
+
+use game_tracker_core::indexers::battlenet::{
+    parse_product_db, product_titles, scan_battlenet_games_from,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn fixture_db() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/battlenet/product.db")
+}
+
+#[test]
+fn test_parse_product_db_reads_installs() {
+    let installs = parse_product_db(&std::fs::read(fixture_db()).unwrap()).expect("valid db");
+    assert_eq!(installs.len(), 7);
+
+    let d3 = installs.iter().find(|i| i.uid == "diablo3").unwrap();
+    assert_eq!(d3.product_code, "d3");
+    assert_eq!(d3.install_path, "D:/Games/Diablo III");
+    assert!(d3.installed);
+
+    let fenris = installs.iter().find(|i| i.uid == "fenris").unwrap();
+    assert!(!fenris.installed);
+}
+
+#[test]
+fn test_scan_battlenet_maps_product_codes() {
+    let games = scan_battlenet_games_from(&fixture_db(), &product_titles(&HashMap::new()))
+        .expect("scan should succeed");
+
+    // Agent and app entries are skipped, as is the uninstalled Diablo IV
+    let mut ids: Vec<&str> = games.iter().map(|g| g.source_id.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, ["diablo3", "s2", "wow", "wow_ptr"]);
+
+    let d3 = games.iter().find(|g| g.source_id == "diablo3").unwrap();
+    assert_eq!(d3.title, "Diablo III");
+    assert_eq!(d3.source, "battlenet");
+    assert_eq!(d3.platform, "PC");
+    assert_eq!(d3.install_path.as_deref(), Some("D:/Games/Diablo III"));
+    assert!(d3.exe_path.is_none());
+}
+
+#[test]
+fn test_battlenet_title_overrides() {
+    let overrides = HashMap::from([
+        ("WOW".to_string(), "WoW Retail".to_string()),
+        ("bna".to_string(), "Battle.net App".to_string()),
+    ]);
+    let games = scan_battlenet_games_from(&fixture_db(), &product_titles(&overrides))
+        .expect("scan should succeed");
+
+    let wow = games.iter().find(|g| g.source_id == "wow").unwrap();
+    assert_eq!(wow.title, "WoW Retail");
+    // Overrides can also add codes the built-in table skips
+    assert!(games.iter().any(|g| g.title == "Battle.net App"));
+}
+
+#[test]
+fn test_battlenet_wine_prefix_paths_are_translated() {
+    let prefix = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/wine-prefix");
+    let db = prefix.join("drive_c/ProgramData/Battle.net/Agent/product.db");
+    let games = scan_battlenet_games_from(&db, &product_titles(&HashMap::new()))
+        .expect("scan should succeed");
+
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].title, "Diablo IV");
+    let expected = prefix.join("drive_c/Program Files (x86)/Diablo IV");
+    assert_eq!(games[0].install_path.as_deref(), Some(&*expected.to_string_lossy()));
+}
+
+#[test]
+fn test_battlenet_rejects_truncated_db() {
+    let bytes = std::fs::read(fixture_db()).unwrap();
+    assert!(parse_product_db(&bytes[..bytes.len() / 2]).is_err());
+}
@@ -0,0 +1,50 @@
+//! Helpers shared by the integration tests. Each test crate uses a subset.
+#![allow(dead_code)]
+
+use game_tracker_core::models::Game;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+
+/// Migrated in-memory database.
+pub async fn test_pool() -> SqlitePool {
+    // A single connection keeps every query on the same in-memory database
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("in-memory database should open");
+    sqlx::migrate!("../../migrations")
+        .run(&pool)
+        .await
+        .expect("migrations should apply");
+    pool
+}
+
+/// An installed PC game in the backlog with nothing else set; tests fill in
+/// what they need with `Game { .., ..game(title) }`.
+pub fn game(title: &str) -> Game {
+    Game {
+        id: 0,
+        title: title.to_string(),
+        platform: "PC".to_string(),
+        status: "Backlog".to_string(),
+        description: None,
+        genre: None,
+        release_year: None,
+        icon_path: None,
+        cover_url: None,
+        rawg_id: None,
+        exe_path: None,
+        playtime_hours: 0.0,
+        rating: None,
+        added_date: String::new(),
+        last_played: None,
+        source: None,
+        source_id: None,
+        install_path: None,
+        exe_path_locked: false,
+        installed: true,
+        uninstalled_at: None,
+        launch_override: None,
+    }
+}
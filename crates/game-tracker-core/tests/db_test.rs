@@ -0,0 +1,94 @@
+//! Tests for SQLx query helpers against an in-memory database.
+
+mod common;
+
+use common::test_pool;
+use game_tracker_core::db;
+use game_tracker_core::models::Game;
+
+fn indexed_game(playtime_hours: f32, last_played: Option<&str>) -> Game {
+    Game {
+        playtime_hours,
+        last_played: last_played.map(str::to_string),
+        source: Some("steam".to_string()),
+        source_id: Some("440".to_string()),
+        install_path: Some("/games/tf2".to_string()),
+        ..common::game("Team Fortress 2")
+    }
+}
+
+#[tokio::test]
+async fn test_upsert_never_moves_playtime_backwards() {
+    let pool = test_pool().await;
+
+    let id = db::upsert_game_by_source(&pool, &indexed_game(10.0, Some("2024-03-01 10:00:00")))
+        .await
+        .unwrap();
+
+    // Lower playtime and older last-played must not overwrite stored values
+    db::upsert_game_by_source(&pool, &indexed_game(4.0, Some("2024-01-01 10:00:00")))
+        .await
+        .unwrap();
+    let game = db::get_game_by_id(&pool, id as i32).await.unwrap();
+    assert_eq!(game.playtime_hours, 10.0);
+    assert_eq!(game.last_played.as_deref(), Some("2024-03-01 10:00:00"));
+
+    // Unknown last-played keeps the stored one
+    db::upsert_game_by_source(&pool, &indexed_game(0.0, None))
+        .await
+        .unwrap();
+    let game = db::get_game_by_id(&pool, id as i32).await.unwrap();
+    assert_eq!(game.last_played.as_deref(), Some("2024-03-01 10:00:00"));
+
+    // Newer values move forward
+    let same_id =
+        db::upsert_game_by_source(&pool, &indexed_game(12.5, Some("2024-04-01 10:00:00")))
+            .await
+            .unwrap();
+    assert_eq!(same_id, id);
+    let game = db::get_game_by_id(&pool, id as i32).await.unwrap();
+    assert_eq!(game.playtime_hours, 12.5);
+    assert_eq!(game.last_played.as_deref(), Some("2024-04-01 10:00:00"));
+}
+
+#[tokio::test]
+async fn test_missing_games_are_marked_uninstalled_and_restored() {
+    let pool = test_pool().await;
+
+    let id = db::upsert_game_by_source(&pool, &indexed_game(10.0, None))
+        .await
+        .unwrap();
+    db::update_game_status(&pool, id as i32, "Completed")
+        .await
+        .unwrap();
+
+    // Still reported: nothing changes
+    let marked = db::mark_missing_uninstalled(&pool, "steam", &["440".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(marked, 0);
+
+    // Gone from the scan: uninstalled, but status and playtime stay
+    let marked = db::mark_missing_uninstalled(&pool, "steam", &[]).await.unwrap();
+    assert_eq!(marked, 1);
+    let game = db::get_game_by_id(&pool, id as i32).await.unwrap();
+    assert!(!game.installed);
+    assert!(game.uninstalled_at.is_some());
+    assert_eq!(game.status, "Completed");
+    assert_eq!(game.playtime_hours, 10.0);
+
+    // Other sources are untouched
+    assert_eq!(
+        db::mark_missing_uninstalled(&pool, "epic", &[]).await.unwrap(),
+        0
+    );
+
+    // Found again: installed
+    db::upsert_game_by_source(&pool, &indexed_game(11.0, None))
+        .await
+        .unwrap();
+    let game = db::get_game_by_id(&pool, id as i32).await.unwrap();
+    assert!(game.installed);
+    assert!(game.uninstalled_at.is_none());
+    assert_eq!(game.status, "Completed");
+}
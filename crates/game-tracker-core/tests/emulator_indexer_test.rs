@@ -0,0 +1,163 @@
+//! Tests for the standalone emulator ROM folder indexer.
+
+use game_tracker_core::indexers::emulator::{scan_rom_folder, strip_disc_tag};
+use game_tracker_core::indexers::rom_id::RomDatabase;
+use game_tracker_core::models::RomFolderRule;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fresh scratch directory under the system temp dir.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gt-emulator-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn touch(root: &Path, rel: &str, contents: &str) {
+    let path = root.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+fn rule(folder: &Path, system: &str, extensions: &[&str]) -> RomFolderRule {
+    RomFolderRule {
+        folder: folder.to_string_lossy().to_string(),
+        system: system.to_string(),
+        extensions: extensions.iter().map(|e| e.to_string()).collect(),
+        emulator: "emu".to_string(),
+    }
+}
+
+#[test]
+fn test_scan_rom_folder_filters_extensions() {
+    let root = scratch_dir("gc");
+    touch(&root, "Metroid Prime (USA).rvz", "");
+    touch(&root, "Legend of Zelda, The - The Wind Waker (USA).ISO", "");
+    touch(&root, "Pikmin (USA)/Pikmin (USA).gcz", "");
+    touch(&root, "readme.txt", "");
+    touch(&root, ".hidden/Secret.iso", "");
+
+    let rule = rule(&root, "Nintendo - GameCube", &["iso", ".rvz", "gcz"]);
+    let games = scan_rom_folder(&rule, &RomDatabase::default()).expect("scan should succeed");
+
+    let mut titles: Vec<&str> = games.iter().map(|g| g.title.as_str()).collect();
+    titles.sort();
+    assert_eq!(
+        titles,
+        [
+            "Metroid Prime",
+            "Pikmin",
+            "The Legend of Zelda - The Wind Waker"
+        ]
+    );
+
+    let prime = games.iter().find(|g| g.title == "Metroid Prime").unwrap();
+    assert_eq!(prime.platform, "GameCube");
+    assert_eq!(prime.source, "emulator");
+    let path = root.join("Metroid Prime (USA).rvz");
+    assert_eq!(prime.source_id, path.to_string_lossy());
+    assert_eq!(prime.install_path.as_deref(), Some(&*path.to_string_lossy()));
+}
+
+#[test]
+fn test_scan_rom_folder_groups_multi_disc_sets() {
+    let root = scratch_dir("ps1");
+    // Cue sheets absorb their tracks, the playlist absorbs the cue sheets
+    touch(
+        &root,
+        "Final Fantasy VII (USA)/Final Fantasy VII (USA) (Disc 1).cue",
+        "FILE \"Final Fantasy VII (USA) (Disc 1).bin\" BINARY\n  TRACK 01 MODE2/2352\n",
+    );
+    touch(&root, "Final Fantasy VII (USA)/Final Fantasy VII (USA) (Disc 1).bin", "");
+    touch(
+        &root,
+        "Final Fantasy VII (USA)/Final Fantasy VII (USA) (Disc 2).cue",
+        "FILE \"Final Fantasy VII (USA) (Disc 2).bin\" BINARY\n",
+    );
+    touch(&root, "Final Fantasy VII (USA)/Final Fantasy VII (USA) (Disc 2).bin", "");
+    touch(
+        &root,
+        "Final Fantasy VII (USA)/Final Fantasy VII (USA).m3u",
+        "#EXTM3U\nFinal Fantasy VII (USA) (Disc 1).cue\n./Final Fantasy VII (USA) (Disc 2).cue\n",
+    );
+
+    // Discs without a playlist are grouped by name
+    touch(&root, "Metal Gear Solid (USA) (Disc 1).chd", "");
+    touch(&root, "Metal Gear Solid (USA) (Disc 2).chd", "");
+
+    // A single cue+bin game
+    touch(&root, "Spyro (USA).cue", "FILE \"Spyro (USA) (Track 1).bin\" BINARY\n");
+    touch(&root, "Spyro (USA) (Track 1).bin", "");
+
+    let rule = rule(&root, "PS1", &["bin", "chd"]);
+    let games = scan_rom_folder(&rule, &RomDatabase::default()).expect("scan should succeed");
+    assert_eq!(games.len(), 3, "got {:?}", games);
+    assert!(games.iter().all(|g| g.platform == "PS1"));
+
+    let ff7 = games.iter().find(|g| g.title == "Final Fantasy VII").unwrap();
+    assert!(ff7.source_id.ends_with("Final Fantasy VII (USA).m3u"));
+
+    let mgs = games.iter().find(|g| g.title == "Metal Gear Solid").unwrap();
+    assert!(mgs.source_id.ends_with("Metal Gear Solid (USA) (Disc 1).chd"));
+
+    let spyro = games.iter().find(|g| g.title == "Spyro").unwrap();
+    assert!(spyro.source_id.ends_with("Spyro (USA).cue"));
+}
+
+#[test]
+fn test_scan_rom_folder_identifies_roms_with_dats() {
+    let root = scratch_dir("snes");
+    let rom: Vec<u8> = (0..1024u32).map(|i| (i * 7 % 256) as u8).collect();
+    fs::write(root.join("zelda3.sfc"), rom).unwrap();
+
+    let dats = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/dats");
+    let dat_files: Vec<PathBuf> = fs::read_dir(dats)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+
+    let rule = rule(&root, "Super Nintendo", &["sfc"]);
+    let games =
+        scan_rom_folder(&rule, &RomDatabase::load(&dat_files)).expect("scan should succeed");
+
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].title, "The Legend of Zelda - A Link to the Past");
+    // The DAT system overrides the rule's free-form system name
+    assert_eq!(games[0].platform, "SNES");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scan_rom_folder_does_not_follow_directory_links() {
+    let root = scratch_dir("links");
+    touch(&root, "snes/Chrono Trigger (USA).sfc", "");
+    // A link back up the tree would recurse forever if followed
+    std::os::unix::fs::symlink(&root, root.join("snes/loop")).unwrap();
+    std::os::unix::fs::symlink(
+        root.join("snes/Chrono Trigger (USA).sfc"),
+        root.join("Chrono Trigger Link.sfc"),
+    )
+    .unwrap();
+
+    let rule = rule(&root, "SNES", &["sfc"]);
+    let games = scan_rom_folder(&rule, &RomDatabase::default()).expect("scan should succeed");
+
+    let mut titles: Vec<&str> = games.iter().map(|g| g.title.as_str()).collect();
+    titles.sort();
+    assert_eq!(titles, ["Chrono Trigger", "Chrono Trigger Link"]);
+}
+
+#[test]
+fn test_strip_disc_tag() {
+    assert_eq!(
+        strip_disc_tag("Final Fantasy VII (USA) (Disc 2)").as_deref(),
+        Some("Final Fantasy VII (USA)")
+    );
+    assert_eq!(
+        strip_disc_tag("Riven (Disc 3 of 5) (USA)").as_deref(),
+        Some("Riven (USA)")
+    );
+    assert_eq!(strip_disc_tag("Policenauts (CD1)").as_deref(), Some("Policenauts"));
+    assert_eq!(strip_disc_tag("Discworld (USA)"), None);
+}
@@ -1,6 +1,8 @@
 //! Tests for Epic Games indexer manifest parsing.
 
-use game_tracker_core::indexers::epic::scan_epic_games_from;
+use game_tracker_core::indexers::epic::{scan_epic_games_from, EpicIndexer};
+use game_tracker_core::indexers::wine::{prefix_of, windows_to_host};
+use game_tracker_core::indexers::LauncherIndexer;
 use std::path::PathBuf;
 
 fn fixtures_dir() -> PathBuf {
@@ -42,3 +44,54 @@ fn test_epic_nonexistent_dir_returns_empty() {
             .expect("scan should succeed even for missing dir");
     assert!(games.is_empty());
 }
+
+#[test]
+fn test_epic_in_wine_prefix_translates_paths() {
+    let prefix = fixtures_dir().join("wine-prefix");
+    let manifests =
+        prefix.join("drive_c/ProgramData/Epic/EpicGamesLauncher/Data/Manifests");
+    let games = scan_epic_games_from(&manifests).expect("scan should succeed");
+    assert_eq!(games.len(), 1);
+
+    let game = &games[0];
+    let install = prefix.join("drive_c/Games/PrefixedGame");
+    assert_eq!(
+        game.install_path.as_deref(),
+        Some(install.to_string_lossy().as_ref())
+    );
+    let exe = install.join("Binaries/Win64/Prefixed.exe");
+    assert_eq!(game.exe_path.as_deref(), Some(exe.to_string_lossy().as_ref()));
+}
+
+#[test]
+fn test_epic_indexer_merges_configured_dirs() {
+    let prefix_manifests = fixtures_dir()
+        .join("wine-prefix/drive_c/ProgramData/Epic/EpicGamesLauncher/Data/Manifests");
+    let indexer = EpicIndexer::new(&[
+        fixtures_dir().to_string_lossy().to_string(),
+        prefix_manifests.to_string_lossy().to_string(),
+    ]);
+    let games = indexer.scan().expect("scan should succeed");
+
+    let ids: Vec<&str> = games.iter().map(|g| g.source_id.as_str()).collect();
+    assert!(ids.contains(&"TestGameOne123"));
+    assert!(ids.contains(&"PrefixedGame456"));
+}
+
+#[test]
+fn test_windows_to_host_drive_mapping() {
+    let prefix = PathBuf::from("/home/user/.wine");
+    assert_eq!(
+        windows_to_host(&prefix, "C:\\Games\\Foo"),
+        Some(PathBuf::from("/home/user/.wine/drive_c/Games/Foo"))
+    );
+    assert_eq!(
+        windows_to_host(&prefix, "d:/Library"),
+        Some(PathBuf::from("/home/user/.wine/dosdevices/d:/Library"))
+    );
+    assert_eq!(windows_to_host(&prefix, "relative\\path"), None);
+    assert_eq!(
+        prefix_of(&prefix.join("drive_c/ProgramData")),
+        Some(prefix.clone())
+    );
+}
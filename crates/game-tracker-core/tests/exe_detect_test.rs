@@ -0,0 +1,81 @@
+//! Tests for scored main-executable detection.
+
+use game_tracker_core::indexers::exe_detect::detect_main_exe;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const ELF: &[u8] = &[0x7F, b'E', b'L', b'F', 2, 1, 1, 0];
+const PE: &[u8] = b"MZ\x90\x00\x03\x00\x00\x00";
+
+/// Fresh scratch directory under the system temp dir.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gt-exe-detect-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write(root: &Path, rel: &str, header: &[u8], padding: usize) {
+    let path = root.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut bytes = header.to_vec();
+    bytes.resize(header.len() + padding, 0);
+    fs::write(path, bytes).unwrap();
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path).file_name().unwrap().to_str().unwrap()
+}
+
+#[test]
+fn test_prefers_title_match_in_nested_bin_folder() {
+    let root = scratch_dir("nested");
+    write(&root, "unins000.exe", PE, 1024);
+    write(&root, "Launcher.exe", PE, 4096);
+    write(&root, "_CommonRedist/vcredist_x64.exe", PE, 4096);
+    write(&root, "bin/Win64/HollowKnight.exe", PE, 2 * 1024 * 1024);
+
+    let detection = detect_main_exe(&root, "Hollow Knight");
+    let best = detection.best.expect("a candidate should be found");
+    assert_eq!(file_name(&best.path), "HollowKnight.exe");
+
+    // The launcher stays available as an alternative; excluded files don't
+    let alternatives: Vec<&str> = detection
+        .alternatives
+        .iter()
+        .map(|c| file_name(&c.path))
+        .collect();
+    assert_eq!(alternatives, vec!["Launcher.exe"]);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_detects_native_linux_builds() {
+    let root = scratch_dir("linux");
+    write(&root, "Celeste.bin.x86_64", ELF, 1024 * 1024);
+    write(&root, "start.sh", b"#!/bin/sh\n", 0);
+    write(&root, "libSDL2.so.0", ELF, 1024 * 1024);
+    // Extension says exe but the header doesn't: not an executable
+    write(&root, "fake.exe", b"nope", 16);
+
+    let detection = detect_main_exe(&root, "Celeste");
+    let best = detection.best.expect("a candidate should be found");
+    assert_eq!(file_name(&best.path), "Celeste.bin.x86_64");
+
+    let alternatives: Vec<&str> = detection
+        .alternatives
+        .iter()
+        .map(|c| file_name(&c.path))
+        .collect();
+    assert_eq!(alternatives, vec!["start.sh"]);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_missing_dir_has_no_candidates() {
+    let detection = detect_main_exe(Path::new("/nonexistent/exe-detect/12345"), "Anything");
+    assert!(detection.best.is_none());
+    assert!(detection.alternatives.is_empty());
+}
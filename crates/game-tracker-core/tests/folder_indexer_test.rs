@@ -0,0 +1,102 @@
+//! Tests for the custom game folder indexer.
+
+use game_tracker_core::indexers::folder::{clean_folder_title, FolderIndexer};
+use game_tracker_core::indexers::LauncherIndexer;
+use std::fs;
+use std::path::PathBuf;
+
+const ELF: &[u8] = &[0x7F, b'E', b'L', b'F', 2, 1, 1, 0];
+
+#[test]
+fn test_clean_folder_title() {
+    assert_eq!(clean_folder_title("Hollow_Knight_v1.5.78"), "Hollow Knight");
+    assert_eq!(clean_folder_title("Celeste-1.4.0.0"), "Celeste");
+    assert_eq!(clean_folder_title("Portal 2"), "Portal 2");
+    assert_eq!(clean_folder_title("Half-Life"), "Half-Life");
+    assert_eq!(
+        clean_folder_title("Stardew Valley (1.6.8) [GOG]"),
+        "Stardew Valley"
+    );
+    assert_eq!(
+        clean_folder_title("FTL.Faster.Than.Light.v1.6.14"),
+        "FTL Faster Than Light"
+    );
+    assert_eq!(
+        clean_folder_title("Into_the_Breach_linux_1.2.88"),
+        "Into the Breach"
+    );
+    assert_eq!(clean_folder_title("Factorio build 1234"), "Factorio");
+}
+
+#[test]
+fn test_folder_indexer_scans_subfolders_with_executables() {
+    let root: PathBuf = std::env::temp_dir().join(format!("gt-folder-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    let game_dir = root.join("Hollow_Knight_v1.5.78");
+    fs::create_dir_all(&game_dir).unwrap();
+    fs::write(game_dir.join("hollow_knight.x86_64"), ELF).unwrap();
+    // No executable: not a game
+    fs::create_dir_all(root.join("Saves")).unwrap();
+    fs::write(root.join("Saves/slot1.dat"), b"data").unwrap();
+    // Hidden folders are ignored
+    fs::create_dir_all(root.join(".cache")).unwrap();
+    fs::write(root.join(".cache/tool.x86_64"), ELF).unwrap();
+
+    let indexer = FolderIndexer {
+        roots: vec![root.clone()],
+    };
+    assert!(indexer.is_available());
+    let games = indexer.scan().expect("scan should succeed");
+    assert_eq!(games.len(), 1);
+
+    let game = &games[0];
+    assert_eq!(game.title, "Hollow Knight");
+    assert_eq!(game.source, "folder");
+    assert_eq!(game.source_id, "Hollow_Knight_v1.5.78");
+    assert_eq!(
+        game.exe_path.as_deref(),
+        Some(
+            game_dir
+                .join("hollow_knight.x86_64")
+                .to_string_lossy()
+                .as_ref()
+        )
+    );
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_same_folder_name_under_two_roots_is_two_games() {
+    let base: PathBuf =
+        std::env::temp_dir().join(format!("gt-folder-roots-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&base);
+    let (d, e, p) = (base.join("D/Games"), base.join("E/Games"), base.join("E/Portable"));
+    for root in [&d, &e, &p] {
+        fs::create_dir_all(root.join("Doom")).unwrap();
+        fs::write(root.join("Doom/doom.x86_64"), ELF).unwrap();
+    }
+
+    let indexer = FolderIndexer {
+        roots: vec![d.clone(), e.clone(), p.clone(), d.clone()],
+    };
+    let games = indexer.scan().expect("scan should succeed");
+
+    // The first root keeps plain relative ids; same-named roots go by position
+    let ids: Vec<&str> = games.iter().map(|g| g.source_id.as_str()).collect();
+    assert_eq!(ids, ["Doom", "root2/Doom", "Portable/Doom"]);
+
+    // Moving a root keeps its games' ids
+    let moved = base.join("F/Games");
+    fs::create_dir_all(moved.parent().unwrap()).unwrap();
+    fs::rename(&d, &moved).unwrap();
+    let indexer = FolderIndexer {
+        roots: vec![moved.clone()],
+    };
+    let games = indexer.scan().expect("scan should succeed");
+    assert_eq!(games[0].source_id, "Doom");
+    assert_eq!(games[0].install_path.as_deref(), Some(&*moved.join("Doom").to_string_lossy()));
+
+    fs::remove_dir_all(&base).unwrap();
+}
@@ -0,0 +1,232 @@
+//! Tests for multi-source games: attaching, merging and splitting.
+
+mod common;
+
+use common::test_pool;
+use game_tracker_core::db;
+use game_tracker_core::models::Game;
+use sqlx::SqlitePool;
+
+fn indexed_game(title: &str, source: &str, source_id: &str, playtime_hours: f32) -> Game {
+    Game {
+        playtime_hours,
+        source: Some(source.to_string()),
+        source_id: Some(source_id.to_string()),
+        install_path: Some(format!("/games/{}/{}", source, source_id)),
+        ..common::game(title)
+    }
+}
+
+async fn add_session(pool: &SqlitePool, game_id: i64, minutes: i64) {
+    sqlx::query("INSERT INTO play_sessions (game_id, duration_minutes) VALUES (?, ?)")
+        .bind(game_id)
+        .bind(minutes)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn session_count(pool: &SqlitePool, game_id: i64) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM play_sessions WHERE game_id = ?")
+        .bind(game_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[test]
+fn test_normalize_title() {
+    assert_eq!(db::normalize_title("DOOM®"), "doom");
+    assert_eq!(
+        db::normalize_title("Hitman: Absolution"),
+        db::normalize_title("HITMAN - Absolution")
+    );
+    assert_eq!(
+        db::normalize_title("Assassin’s Creed™"),
+        db::normalize_title("Assassin's Creed")
+    );
+}
+
+#[tokio::test]
+async fn test_second_source_attaches_by_normalized_title() {
+    let pool = test_pool().await;
+
+    let steam = db::upsert_game_by_source(&pool, &indexed_game("Hades", "steam", "1145360", 10.0))
+        .await
+        .unwrap();
+    let epic = db::upsert_game_by_source(&pool, &indexed_game("HADES™", "epic", "Min", 3.0))
+        .await
+        .unwrap();
+    assert_eq!(steam, epic);
+    assert_eq!(db::count_games(&pool).await.unwrap(), 1);
+
+    let game = db::get_game_by_id(&pool, steam as i32).await.unwrap();
+    // Primary source (Steam) keeps the title and paths; playtime adds up
+    assert_eq!(game.title, "Hades");
+    assert_eq!(game.source.as_deref(), Some("steam"));
+    assert_eq!(game.playtime_hours, 13.0);
+
+    let sources = db::get_game_sources(&pool, steam as i32).await.unwrap();
+    let ids: Vec<&str> = sources.iter().map(|s| s.source_id.as_str()).collect();
+    assert_eq!(ids, ["1145360", "Min"]);
+
+    // Each source only adds its own growth
+    db::upsert_game_by_source(&pool, &indexed_game("HADES™", "epic", "Min", 4.0))
+        .await
+        .unwrap();
+    let game = db::get_game_by_id(&pool, steam as i32).await.unwrap();
+    assert_eq!(game.playtime_hours, 14.0);
+}
+
+#[tokio::test]
+async fn test_uninstalling_primary_falls_back_to_other_source() {
+    let pool = test_pool().await;
+
+    let id = db::upsert_game_by_source(&pool, &indexed_game("Hades", "steam", "1145360", 0.0))
+        .await
+        .unwrap();
+    db::upsert_game_by_source(&pool, &indexed_game("Hades", "epic", "Min", 0.0))
+        .await
+        .unwrap();
+
+    // Steam copy removed: the game stays installed through Epic
+    let marked = db::mark_missing_uninstalled(&pool, "steam", &[]).await.unwrap();
+    assert_eq!(marked, 0);
+    let game = db::get_game_by_id(&pool, id as i32).await.unwrap();
+    assert!(game.installed);
+    assert_eq!(game.source.as_deref(), Some("epic"));
+    assert_eq!(game.install_path.as_deref(), Some("/games/epic/Min"));
+
+    // Epic copy removed too: now it's uninstalled
+    let marked = db::mark_missing_uninstalled(&pool, "epic", &[]).await.unwrap();
+    assert_eq!(marked, 1);
+    assert!(!db::get_game_by_id(&pool, id as i32).await.unwrap().installed);
+}
+
+#[tokio::test]
+async fn test_attach_by_rawg_id() {
+    let pool = test_pool().await;
+
+    let id = db::upsert_game_by_source(&pool, &indexed_game("The Witcher 3", "gog", "1207664643", 0.0))
+        .await
+        .unwrap();
+    sqlx::query("UPDATE games SET rawg_id = 3328 WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let mut steam = indexed_game("The Witcher 3: Wild Hunt", "steam", "292030", 0.0);
+    steam.rawg_id = Some(3328);
+    assert_eq!(db::upsert_game_by_source(&pool, &steam).await.unwrap(), id);
+}
+
+#[tokio::test]
+async fn test_merge_moves_sources_sessions_and_playtime() {
+    let pool = test_pool().await;
+
+    let keep = db::upsert_game_by_source(&pool, &indexed_game("Celeste", "steam", "504230", 5.0))
+        .await
+        .unwrap();
+    let dupe = db::upsert_game_by_source(&pool, &indexed_game("Celeste (2018)", "itch", "42", 2.0))
+        .await
+        .unwrap();
+    assert_ne!(keep, dupe);
+    add_session(&pool, keep, 30).await;
+    add_session(&pool, dupe, 45).await;
+    sqlx::query("UPDATE games SET genre = 'Platformer' WHERE id = ?")
+        .bind(dupe)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    db::merge_games(&pool, keep as i32, dupe as i32).await.unwrap();
+
+    assert!(db::get_game_by_id(&pool, dupe as i32).await.is_err());
+    let game = db::get_game_by_id(&pool, keep as i32).await.unwrap();
+    assert_eq!(game.playtime_hours, 7.0);
+    assert_eq!(game.genre.as_deref(), Some("Platformer"));
+    assert_eq!(session_count(&pool, keep).await, 2);
+    assert_eq!(db::get_game_sources(&pool, keep as i32).await.unwrap().len(), 2);
+
+    // The merged source now resolves to the kept game
+    let again = db::upsert_game_by_source(&pool, &indexed_game("Celeste (2018)", "itch", "42", 2.0))
+        .await
+        .unwrap();
+    assert_eq!(again, keep);
+}
+
+#[tokio::test]
+async fn test_split_moves_source_and_its_playtime() {
+    let pool = test_pool().await;
+
+    let id = db::upsert_game_by_source(&pool, &indexed_game("Doom", "steam", "2280", 4.0))
+        .await
+        .unwrap();
+    db::upsert_game_by_source(&pool, &indexed_game("DOOM", "steam", "379720", 6.0))
+        .await
+        .unwrap();
+    add_session(&pool, id, 60).await;
+
+    let sources = db::get_game_sources(&pool, id as i32).await.unwrap();
+    let doom_2016 = sources.iter().find(|s| s.source_id == "379720").unwrap();
+    let new_id = db::split_game_source(&pool, doom_2016.id).await.unwrap();
+
+    let old = db::get_game_by_id(&pool, id as i32).await.unwrap();
+    let new = db::get_game_by_id(&pool, new_id as i32).await.unwrap();
+    assert_eq!(old.playtime_hours, 4.0);
+    assert_eq!(new.playtime_hours, 6.0);
+    assert_eq!(new.title, "Doom");
+    assert_eq!(new.source_id.as_deref(), Some("379720"));
+    assert_eq!(session_count(&pool, id).await, 1);
+
+    // Re-indexing keeps the split: the source stays with its new game
+    let again = db::upsert_game_by_source(&pool, &indexed_game("DOOM", "steam", "379720", 6.5))
+        .await
+        .unwrap();
+    assert_eq!(again, new_id);
+}
+
+#[tokio::test]
+async fn test_games_may_share_a_title() {
+    let pool = test_pool().await;
+    let id = db::upsert_game_by_source(&pool, &indexed_game("Hades", "steam", "1145360", 1.0))
+        .await
+        .unwrap();
+    add_session(&pool, id, 20).await;
+
+    // A remake or a split duplicate can share a title and platform
+    sqlx::query("INSERT INTO games (title, platform) VALUES ('Hades', 'PC')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(session_count(&pool, id).await, 1);
+}
+
+#[tokio::test]
+async fn test_attach_matches_stored_normalized_title() {
+    let pool = test_pool().await;
+
+    // A game stored before normalized titles existed matches once filled in
+    sqlx::query("INSERT INTO games (title, platform) VALUES ('Celeste™', 'PC')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(db::fill_normalized_titles(&pool).await.unwrap(), 1);
+    assert_eq!(db::fill_normalized_titles(&pool).await.unwrap(), 0);
+    let celeste = indexed_game("CELESTE", "itch", "celeste", 0.0);
+    assert!(db::find_game_for_source(&pool, &celeste).await.unwrap().is_some());
+
+    // A rename by the primary source moves the key along with the title
+    let id = db::upsert_game_by_source(&pool, &indexed_game("Hades", "steam", "1145360", 0.0))
+        .await
+        .unwrap();
+    db::upsert_game_by_source(&pool, &indexed_game("Hades II", "steam", "1145360", 0.0))
+        .await
+        .unwrap();
+    let renamed = indexed_game("HADES II", "epic", "Zeus", 0.0);
+    let owner = db::find_game_for_source(&pool, &renamed).await.unwrap().unwrap();
+    assert_eq!(owner.id as i64, id);
+    let old = indexed_game("Hades", "epic", "Min", 0.0);
+    assert!(db::find_game_for_source(&pool, &old).await.unwrap().is_none());
+}
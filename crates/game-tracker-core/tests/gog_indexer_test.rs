@@ -0,0 +1,55 @@
+//! Tests for GOG Galaxy indexer database parsing.
+
+use game_tracker_core::indexers::gog::scan_gog_games_from;
+use std::path::PathBuf;
+
+fn fixture_db() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gog/galaxy-2.0.db")
+}
+
+#[test]
+fn test_scan_gog_discovers_installed_products_only() {
+    let games = scan_gog_games_from(&fixture_db()).expect("scan should succeed");
+
+    // Three installed products; the owned-but-not-installed title is skipped
+    assert_eq!(games.len(), 3, "Expected 3 games, got {}", games.len());
+
+    let titles: Vec<&str> = games.iter().map(|g| g.title.as_str()).collect();
+    assert!(titles.contains(&"The Witcher: Enhanced Edition"));
+    assert!(titles.contains(&"The Witcher 3: Wild Hunt - Game of the Year Edition"));
+    assert!(!titles.contains(&"Owned But Not Installed"));
+}
+
+#[test]
+fn test_gog_game_fields() {
+    let games = scan_gog_games_from(&fixture_db()).expect("scan should succeed");
+
+    let game = games
+        .iter()
+        .find(|g| g.title == "The Witcher: Enhanced Edition")
+        .unwrap();
+    assert_eq!(game.source, "gog");
+    assert_eq!(game.source_id, "1207658924");
+    assert_eq!(game.platform, "PC");
+    assert_eq!(
+        game.install_path.as_deref(),
+        Some("C:\\GOG Games\\Witcher Enhanced Edition")
+    );
+    // The play task executable doesn't exist on disk in test, so it should be None
+    assert!(game.exe_path.is_none());
+}
+
+#[test]
+fn test_gog_title_falls_back_to_folder_name() {
+    let games = scan_gog_games_from(&fixture_db()).expect("scan should succeed");
+
+    let game = games.iter().find(|g| g.source_id == "1111111111").unwrap();
+    assert_eq!(game.title, "Untitled Thing");
+}
+
+#[test]
+fn test_gog_missing_db_returns_empty() {
+    let games = scan_gog_games_from(&PathBuf::from("/nonexistent/galaxy-2.0.db"))
+        .expect("scan should succeed even for missing db");
+    assert!(games.is_empty());
+}
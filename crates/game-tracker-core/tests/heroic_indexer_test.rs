@@ -0,0 +1,92 @@
+//! Tests for Heroic Games Launcher config parsing.
+
+use game_tracker_core::indexers::heroic::scan_heroic_games_from;
+use std::path::PathBuf;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/heroic")
+}
+
+#[test]
+fn test_scan_heroic_discovers_all_backends() {
+    let games = scan_heroic_games_from(&fixtures_dir()).expect("scan should succeed");
+
+    // Epic game (DLC skipped), two GOG games, one Amazon game
+    assert_eq!(games.len(), 4, "Expected 4 games, got {}", games.len());
+
+    let ids: Vec<&str> = games.iter().map(|g| g.source_id.as_str()).collect();
+    assert!(ids.contains(&"legendary:Sugar"));
+    assert!(ids.contains(&"gog:1453375253"));
+    assert!(ids.contains(&"gog:1000000001"));
+    assert!(ids.contains(&"nile:amzn1.adg.product.0000-test"));
+    assert!(games.iter().all(|g| g.source == "heroic"));
+}
+
+#[test]
+fn test_heroic_legendary_fields_and_playtime() {
+    let games = scan_heroic_games_from(&fixtures_dir()).expect("scan should succeed");
+
+    let game = games
+        .iter()
+        .find(|g| g.source_id == "legendary:Sugar")
+        .unwrap();
+    assert_eq!(game.title, "Rocket League");
+    assert_eq!(game.platform, "PC");
+    assert_eq!(
+        game.install_path.as_deref(),
+        Some("/home/user/Games/Heroic/rocketleague")
+    );
+    // exe_path won't exist on disk in test, so it should be None
+    assert!(game.exe_path.is_none());
+    assert_eq!(game.playtime_hours, Some(2.5));
+    assert_eq!(game.last_played.as_deref(), Some("2024-03-10 21:30:15"));
+}
+
+#[test]
+fn test_heroic_titles_from_library_caches() {
+    let games = scan_heroic_games_from(&fixtures_dir()).expect("scan should succeed");
+
+    let stardew = games
+        .iter()
+        .find(|g| g.source_id == "gog:1453375253")
+        .unwrap();
+    assert_eq!(stardew.title, "Stardew Valley");
+    assert_eq!(stardew.playtime_hours, Some(0.75));
+
+    // Not in the GOG library cache: falls back to the install folder name
+    let mystery = games
+        .iter()
+        .find(|g| g.source_id == "gog:1000000001")
+        .unwrap();
+    assert_eq!(mystery.title, "Mystery Game");
+    assert!(mystery.playtime_hours.is_none());
+
+    let amazon = games
+        .iter()
+        .find(|g| g.source_id == "nile:amzn1.adg.product.0000-test")
+        .unwrap();
+    assert_eq!(amazon.title, "Prime Freebie Adventure");
+}
+
+#[test]
+fn test_heroic_nonexistent_dir_returns_empty() {
+    let games = scan_heroic_games_from(&PathBuf::from("/nonexistent/heroic/12345"))
+        .expect("scan should succeed even for missing dir");
+    assert!(games.is_empty());
+}
+
+#[test]
+fn test_heroic_half_written_install_list_fails_scan() {
+    let dir = std::env::temp_dir().join(format!("gt-heroic-partial-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let legendary = dir.join("legendaryConfig/legendary");
+    std::fs::create_dir_all(&legendary).unwrap();
+    std::fs::write(legendary.join("installed.json"), r#"{"Sugar": {"app_name": "Su"#).unwrap();
+
+    // An error keeps the launcher's games from being marked uninstalled
+    assert!(scan_heroic_games_from(&dir).is_err());
+
+    // A missing install list is just nothing installed
+    std::fs::remove_file(legendary.join("installed.json")).unwrap();
+    assert!(scan_heroic_games_from(&dir).unwrap().is_empty());
+}
@@ -0,0 +1,224 @@
+//! Tests for dry-run index previews and applying selected entries.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod common;
+
+use game_tracker_core::db;
+use game_tracker_core::indexers::{IndexerError, IndexerRegistry, LauncherIndexer};
+use game_tracker_core::models::{DiscoveredGame, IndexChange, IndexChangeKind};
+use game_tracker_core::rawg::RawgClient;
+use game_tracker_core::service::GameService;
+
+struct FakeIndexer(Vec<DiscoveredGame>);
+
+impl LauncherIndexer for FakeIndexer {
+    fn id(&self) -> &'static str {
+        "steam"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Fake Steam"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Second launcher, so two sources can report the same game.
+struct FakeEpic(Vec<DiscoveredGame>);
+
+impl LauncherIndexer for FakeEpic {
+    fn id(&self) -> &'static str {
+        "epic"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Fake Epic"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        Ok(self.0.clone())
+    }
+}
+
+fn discovered(title: &str, source_id: &str, install_path: &str) -> DiscoveredGame {
+    DiscoveredGame {
+        title: title.to_string(),
+        platform: "PC".to_string(),
+        exe_path: None,
+        install_path: Some(install_path.to_string()),
+        source: "steam".to_string(),
+        source_id: source_id.to_string(),
+        playtime_hours: None,
+        last_played: None,
+    }
+}
+
+fn icons_dir() -> PathBuf {
+    std::env::temp_dir().join("gt-index-preview-icons")
+}
+
+async fn service_with(games: Vec<DiscoveredGame>) -> GameService {
+    let pool = common::test_pool().await;
+
+    let mut service = GameService::new(pool, Arc::new(RawgClient::new(String::new())), icons_dir());
+    let mut registry = IndexerRegistry::empty();
+    registry.register(Box::new(FakeIndexer(games)));
+    service.indexers = registry;
+    service
+}
+
+#[tokio::test]
+async fn test_preview_reports_changes_without_writing() {
+    let service = service_with(vec![
+        discovered("Portal 2", "620", "/games/portal2"),
+        discovered("Half-Life 2", "220", "/games/hl2-new"),
+        discovered("Celeste", "504230", "/games/celeste"),
+    ])
+    .await;
+
+    // Existing rows: one that moved, one that vanished, one manual game the
+    // launcher entry attaches to
+    service.index_all().await.unwrap();
+    let pool = &service.pool;
+    for table in ["games", "game_sources"] {
+        sqlx::query(&format!(
+            "UPDATE {} SET install_path = '/games/hl2' WHERE source_id = '220'",
+            table
+        ))
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+    sqlx::query("DELETE FROM games WHERE source_id = '620'")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO games (title, platform, source, source_id) \
+         VALUES ('Portal 2', 'PC', 'manual', NULL), ('Quake', 'PC', 'steam', '2310')",
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO game_sources (game_id, source, source_id) \
+         SELECT id, source, source_id FROM games WHERE source_id = '2310'",
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query("DELETE FROM games WHERE source_id = '504230'")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let before = db::get_all_games(pool).await.unwrap();
+    let changes = service.preview_index().await.unwrap();
+
+    let kind_of = |title: &str| {
+        changes
+            .iter()
+            .find(|c| {
+                c.discovered.as_ref().map(|d| d.title.as_str()) == Some(title)
+                    || (c.discovered.is_none()
+                        && c.existing.as_ref().map(|g| g.title.as_str()) == Some(title))
+            })
+            .map(|c| c.kind)
+    };
+    assert_eq!(kind_of("Portal 2"), Some(IndexChangeKind::Attach));
+    assert_eq!(kind_of("Half-Life 2"), Some(IndexChangeKind::Updated));
+    assert_eq!(kind_of("Celeste"), Some(IndexChangeKind::New));
+    assert_eq!(kind_of("Quake"), Some(IndexChangeKind::Vanished));
+    assert_eq!(changes.len(), 4);
+
+    let updated = changes
+        .iter()
+        .find(|c| c.kind == IndexChangeKind::Updated)
+        .unwrap();
+    assert_eq!(updated.fields, vec!["install_path".to_string()]);
+
+    // Nothing was written
+    let after = db::get_all_games(pool).await.unwrap();
+    assert_eq!(before.len(), after.len());
+
+    // Apply only the update
+    let result = service
+        .apply_index_changes(vec![updated.clone()])
+        .await
+        .unwrap();
+    assert_eq!(result.upserted, 1);
+    let games = db::get_all_games(pool).await.unwrap();
+    let hl2 = games.iter().find(|g| g.title == "Half-Life 2").unwrap();
+    assert_eq!(hl2.install_path.as_deref(), Some("/games/hl2-new"));
+    assert!(games.iter().all(|g| g.title != "Celeste"));
+    assert!(games.iter().find(|g| g.title == "Quake").unwrap().installed);
+
+    let _ = std::fs::remove_dir_all(icons_dir());
+}
+
+#[tokio::test]
+async fn test_preview_attaches_second_source_to_game_previewed_as_new() {
+    let mut service = service_with(vec![discovered("Hades", "1145360", "/games/hades")]).await;
+    service.indexers.register(Box::new(FakeEpic(vec![DiscoveredGame {
+        source: "epic".to_string(),
+        ..discovered("HADES", "min", "/epic/hades")
+    }])));
+
+    let changes = service.preview_index().await.unwrap();
+    let kinds: Vec<IndexChangeKind> = changes.iter().map(|c| c.kind).collect();
+    assert_eq!(kinds, vec![IndexChangeKind::New, IndexChangeKind::Attach]);
+    assert_eq!(changes[1].existing.as_ref().unwrap().title, "Hades");
+
+    // Applying creates one game with both sources, as previewed
+    let result = service.apply_index_changes(changes).await.unwrap();
+    assert_eq!(result.upserted, 2);
+    assert_eq!(db::get_all_games(&service.pool).await.unwrap().len(), 1);
+    assert_eq!(db::get_all_game_sources(&service.pool).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_apply_counts_failed_entries_and_applies_the_rest() {
+    let service = service_with(Vec::new()).await;
+    sqlx::query(
+        "CREATE TRIGGER reject_broken BEFORE INSERT ON games WHEN NEW.title = 'Broken' \
+         BEGIN SELECT RAISE(ABORT, 'rejected'); END",
+    )
+    .execute(&service.pool)
+    .await
+    .unwrap();
+
+    let new = |title: &str, source_id: &str| IndexChange {
+        kind: IndexChangeKind::New,
+        discovered: Some(discovered(title, source_id, "/games/x")),
+        existing: None,
+        fields: Vec::new(),
+    };
+    let malformed = IndexChange {
+        kind: IndexChangeKind::Vanished,
+        discovered: None,
+        existing: None,
+        fields: Vec::new(),
+    };
+
+    let result = service
+        .apply_index_changes(vec![new("Broken", "1"), malformed, new("Celeste", "2")])
+        .await
+        .unwrap();
+    assert_eq!(result.upserted, 1);
+    assert_eq!(result.failed, 2);
+    let games = db::get_all_games(&service.pool).await.unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].title, "Celeste");
+}
@@ -0,0 +1,155 @@
+//! Tests for progress reporting during `index_all`.
+
+use std::sync::{Arc, Mutex};
+
+mod common;
+
+use game_tracker_core::indexers::{IndexerError, IndexerRegistry, LauncherIndexer};
+use game_tracker_core::models::DiscoveredGame;
+use game_tracker_core::rawg::RawgClient;
+use game_tracker_core::service::{GameService, IndexProgress};
+
+struct FakeIndexer {
+    id: &'static str,
+    titles: Vec<&'static str>,
+    fail: bool,
+}
+
+impl LauncherIndexer for FakeIndexer {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn display_name(&self) -> &'static str {
+        self.id
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        if self.fail {
+            return Err("launcher database is locked".into());
+        }
+        Ok(self
+            .titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| DiscoveredGame {
+                title: title.to_string(),
+                platform: "PC".to_string(),
+                exe_path: None,
+                install_path: None,
+                source: self.id.to_string(),
+                source_id: i.to_string(),
+                playtime_hours: None,
+                last_played: None,
+            })
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn test_index_all_reports_progress_per_source_and_game() {
+    let pool = common::test_pool().await;
+
+    // Same-title entries attach to one game, so force a write failure
+    sqlx::query(
+        "CREATE TRIGGER reject_broken BEFORE INSERT ON games WHEN NEW.title = 'Broken' \
+         BEGIN SELECT RAISE(ABORT, 'rejected'); END",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let mut service = GameService::new(
+        pool,
+        Arc::new(RawgClient::new(String::new())),
+        std::env::temp_dir().join("gt-index-progress-icons"),
+    );
+    let mut registry = IndexerRegistry::empty();
+    registry.register(Box::new(FakeIndexer {
+        id: "steam",
+        titles: vec!["Portal", "Broken"],
+        fail: false,
+    }));
+    registry.register(Box::new(FakeIndexer {
+        id: "epic",
+        titles: Vec::new(),
+        fail: true,
+    }));
+    service.indexers = registry;
+
+    let events = Mutex::new(Vec::new());
+    let result = service
+        .index_all_with_progress(&|event| events.lock().unwrap().push(event))
+        .await
+        .unwrap();
+    assert_eq!(result.discovered, 2);
+    assert_eq!(result.upserted, 1);
+
+    let kinds: Vec<String> = events
+        .into_inner()
+        .unwrap()
+        .iter()
+        .map(|e| match e {
+            IndexProgress::SourceStarted { source, .. } => format!("started {}", source),
+            IndexProgress::GameDiscovered { title, .. } => format!("discovered {}", title),
+            IndexProgress::GameUpserted { title, .. } => format!("upserted {}", title),
+            IndexProgress::GameFailed { title, .. } => format!("failed {}", title),
+            IndexProgress::SourceFinished { source, result } => {
+                format!("finished {} {}", source, result.upserted)
+            }
+            IndexProgress::SourceFailed { source, .. } => format!("source failed {}", source),
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "started steam",
+            "discovered Portal",
+            "upserted Portal",
+            "discovered Broken",
+            "failed Broken",
+            "finished steam 1",
+            "started epic",
+            "source failed epic",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_library_indexer_runs_enabled_sources_without_the_service() {
+    let mut service = GameService::new(
+        common::test_pool().await,
+        Arc::new(RawgClient::new(String::new())),
+        std::env::temp_dir().join("gt-library-indexer-icons"),
+    );
+    let mut registry = IndexerRegistry::empty();
+    registry.register(Box::new(FakeIndexer {
+        id: "steam",
+        titles: vec!["Portal", "Celeste"],
+        fail: false,
+    }));
+    registry.register(Box::new(FakeIndexer {
+        id: "epic",
+        titles: vec!["Control"],
+        fail: false,
+    }));
+    registry.set_enabled("epic", false);
+    service.indexers = registry;
+
+    let indexer = service.library_indexer();
+    let pool = service.pool.clone();
+    drop(service);
+
+    let result = indexer.index_with_progress(&|_| {}).await.unwrap();
+    assert_eq!(result.discovered, 2);
+    assert_eq!(result.upserted, 2);
+    let titles: Vec<String> = sqlx::query_scalar("SELECT title FROM games ORDER BY title")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(titles, vec!["Celeste", "Portal"]);
+}
@@ -0,0 +1,55 @@
+//! Tests for the launcher indexer registry.
+
+use game_tracker_core::indexers::{IndexerError, IndexerRegistry, LauncherIndexer};
+use game_tracker_core::models::{AppSettings, DiscoveredGame};
+
+struct FakeIndexer(&'static str);
+
+impl LauncherIndexer for FakeIndexer {
+    fn id(&self) -> &'static str {
+        self.0
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Fake"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        Ok(Vec::new())
+    }
+}
+
+#[test]
+fn test_default_registry_contains_builtin_indexers() {
+    let registry = IndexerRegistry::default();
+    assert!(registry.get("steam").is_some());
+    assert!(registry.get("epic").is_some());
+}
+
+#[test]
+fn test_register_replaces_same_id() {
+    let mut registry = IndexerRegistry::empty();
+    registry.register(Box::new(FakeIndexer("fake")));
+    registry.register(Box::new(FakeIndexer("fake")));
+    registry.register(Box::new(FakeIndexer("other")));
+    assert_eq!(registry.list().len(), 2);
+}
+
+#[test]
+fn test_disabled_indexers_are_skipped() {
+    let settings = AppSettings {
+        disabled_indexers: vec!["epic".to_string()],
+        ..Default::default()
+    };
+    let registry = IndexerRegistry::from_settings(&settings);
+
+    let enabled: Vec<&str> = registry.enabled().map(|i| i.id()).collect();
+    assert!(enabled.contains(&"steam"));
+    assert!(!enabled.contains(&"epic"));
+    // Disabled indexers stay reachable for single-source runs.
+    assert!(registry.get("epic").is_some());
+}
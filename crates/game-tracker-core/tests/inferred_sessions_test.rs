@@ -0,0 +1,110 @@
+//! Tests for sessions inferred from launcher playtime growth.
+
+mod common;
+
+use common::test_pool;
+use game_tracker_core::db;
+use game_tracker_core::models::Game;
+use sqlx::SqlitePool;
+
+fn indexed_game(playtime_hours: f32, last_played: Option<&str>) -> Game {
+    Game {
+        playtime_hours,
+        last_played: last_played.map(str::to_string),
+        source: Some("steam".to_string()),
+        source_id: Some("620".to_string()),
+        install_path: Some("/games/portal2".to_string()),
+        ..common::game("Portal 2")
+    }
+}
+
+async fn index(pool: &SqlitePool, playtime_hours: f32, last_played: Option<&str>) -> i32 {
+    db::upsert_game_by_source(pool, &indexed_game(playtime_hours, last_played))
+        .await
+        .unwrap() as i32
+}
+
+#[tokio::test]
+async fn test_first_import_is_not_a_session() {
+    let pool = test_pool().await;
+    let id = index(&pool, 10.0, Some("2026-10-01 18:00:00")).await;
+
+    assert!(db::get_play_sessions(&pool, id).await.unwrap().is_empty());
+    assert_eq!(db::get_game_by_id(&pool, id).await.unwrap().playtime_hours, 10.0);
+
+    // Nothing new on the next run either
+    index(&pool, 10.0, Some("2026-10-01 18:00:00")).await;
+    assert!(db::get_play_sessions(&pool, id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_playtime_growth_becomes_inferred_session() {
+    let pool = test_pool().await;
+    let id = index(&pool, 10.0, Some("2026-10-01 18:00:00")).await;
+
+    index(&pool, 11.5, Some("2026-10-05 21:30:00")).await;
+
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert!(sessions[0].inferred);
+    assert!(!sessions[0].active);
+    assert_eq!(sessions[0].duration_minutes, 90);
+    assert_eq!(sessions[0].session_date, "2026-10-05 21:30:00");
+
+    let game = db::get_game_by_id(&pool, id).await.unwrap();
+    assert_eq!(game.playtime_hours, 11.5);
+    assert_eq!(game.last_played.as_deref(), Some("2026-10-05 21:30:00"));
+
+    // Measured from the new snapshot, not the first import
+    index(&pool, 12.0, Some("2026-10-06 20:00:00")).await;
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0].duration_minutes, 30);
+}
+
+#[tokio::test]
+async fn test_tracked_minutes_are_not_counted_twice() {
+    let pool = test_pool().await;
+    let id = index(&pool, 10.0, None).await;
+
+    let tracked = db::start_play_session(&pool, id).await.unwrap();
+    db::finish_play_session(&pool, tracked, 45).await.unwrap();
+
+    // Steam saw the same 45 minutes plus 15 played elsewhere
+    index(&pool, 11.0, None).await;
+
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    let inferred: Vec<_> = sessions.iter().filter(|s| s.inferred).collect();
+    assert_eq!(inferred.len(), 1);
+    assert_eq!(inferred[0].duration_minutes, 15);
+    assert_eq!(db::get_game_by_id(&pool, id).await.unwrap().playtime_hours, 11.0);
+}
+
+#[tokio::test]
+async fn test_growth_covered_by_tracking_infers_nothing() {
+    let pool = test_pool().await;
+    let id = index(&pool, 10.0, None).await;
+
+    let tracked = db::start_play_session(&pool, id).await.unwrap();
+    db::finish_play_session(&pool, tracked, 60).await.unwrap();
+    index(&pool, 11.0, None).await;
+
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert!(!sessions[0].inferred);
+    assert_eq!(db::get_game_by_id(&pool, id).await.unwrap().playtime_hours, 11.0);
+}
+
+#[tokio::test]
+async fn test_sub_minute_growth_adds_up() {
+    let pool = test_pool().await;
+    let id = index(&pool, 10.0, None).await;
+
+    index(&pool, 10.005, None).await;
+    assert!(db::get_play_sessions(&pool, id).await.unwrap().is_empty());
+
+    index(&pool, 10.02, None).await;
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].duration_minutes, 1);
+}
@@ -0,0 +1,57 @@
+//! Tests for itch.io indexer database parsing.
+
+use game_tracker_core::indexers::itch::scan_itch_games_from;
+use std::path::PathBuf;
+
+fn fixture_db() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/itch/butler.db")
+}
+
+#[test]
+fn test_scan_itch_discovers_installed_games_only() {
+    let games = scan_itch_games_from(&fixture_db()).expect("scan should succeed");
+
+    // Two installed games; the tool and the uninstalled game are skipped
+    assert_eq!(games.len(), 2, "Expected 2 games, got {}", games.len());
+
+    let titles: Vec<&str> = games.iter().map(|g| g.title.as_str()).collect();
+    assert!(titles.contains(&"Jam Game Deluxe"));
+    assert!(titles.contains(&"Tiny Platformer"));
+}
+
+#[test]
+fn test_itch_game_fields() {
+    let games = scan_itch_games_from(&fixture_db()).expect("scan should succeed");
+
+    let game = games.iter().find(|g| g.title == "Jam Game Deluxe").unwrap();
+    assert_eq!(game.source, "itch");
+    assert_eq!(game.source_id, "101");
+    assert_eq!(game.platform, "PC");
+    assert_eq!(
+        game.install_path.as_deref(),
+        Some("/home/user/.config/itch/apps/jam-game-deluxe")
+    );
+    // The verdict executable doesn't exist on disk in test, so it should be None
+    assert!(game.exe_path.is_none());
+    assert_eq!(game.playtime_hours, Some(1.5));
+    assert_eq!(game.last_played.as_deref(), Some("2024-04-02 08:15:00"));
+}
+
+#[test]
+fn test_itch_custom_install_folder() {
+    let games = scan_itch_games_from(&fixture_db()).expect("scan should succeed");
+
+    let game = games.iter().find(|g| g.source_id == "303").unwrap();
+    assert_eq!(
+        game.install_path.as_deref(),
+        Some("/mnt/games/tiny-platformer")
+    );
+    assert!(game.playtime_hours.is_none());
+}
+
+#[test]
+fn test_itch_missing_db_returns_empty() {
+    let games = scan_itch_games_from(&PathBuf::from("/nonexistent/itch/butler.db"))
+        .expect("scan should succeed even for missing db");
+    assert!(games.is_empty());
+}
@@ -0,0 +1,299 @@
+//! Tests for per-source launch strategies, the fallback chain and overrides.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+use game_tracker_core::launch::{
+    self, EmulatorStrategy, EpicStrategy, ExeStrategy, GogGalaxyStrategy, HeroicStrategy,
+    LaunchCommand, LaunchPlanner, LaunchStrategy, LutrisStrategy, RetroArchStrategy,
+    SteamStrategy,
+};
+use game_tracker_core::models::{AppSettings, Game, RomFolderRule};
+
+fn game(source: &str, source_id: &str) -> Game {
+    Game {
+        id: 1,
+        source: Some(source.to_string()),
+        source_id: Some(source_id.to_string()),
+        ..common::game("Test Game")
+    }
+}
+
+fn uri(s: &str) -> Option<LaunchCommand> {
+    Some(LaunchCommand::Uri(s.to_string()))
+}
+
+/// A real executable file in a fresh temp directory.
+fn temp_exe(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("gt-launch-strategy-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let exe = dir.join("game.exe");
+    fs::write(&exe, b"MZ").unwrap();
+    exe
+}
+
+fn exe_command(exe: &Path) -> LaunchCommand {
+    LaunchCommand::Exe {
+        path: exe.to_path_buf(),
+        args: Vec::new(),
+        working_dir: exe.parent().map(Path::to_path_buf),
+    }
+}
+
+#[test]
+fn test_steam_uri() {
+    assert_eq!(SteamStrategy.command(&game("steam", "620")), uri("steam://rungameid/620"));
+    assert_eq!(SteamStrategy.command(&game("steam", "not-a-number")), None);
+    assert_eq!(SteamStrategy.command(&game("epic", "620")), None);
+}
+
+#[test]
+fn test_steam_shortcut_uses_64_bit_game_id() {
+    assert_eq!(
+        SteamStrategy.command(&game("steam-shortcut", "3000000000")),
+        uri("steam://rungameid/12884901888033554432")
+    );
+}
+
+#[test]
+fn test_epic_uri() {
+    assert_eq!(
+        EpicStrategy.command(&game("epic", "Fortnite")),
+        uri("com.epicgames.launcher://apps/Fortnite?action=launch&silent=true")
+    );
+}
+
+#[test]
+fn test_heroic_uri_carries_runner() {
+    assert_eq!(
+        HeroicStrategy.command(&game("heroic", "gog:1207658924")),
+        uri("heroic://launch?appName=1207658924&runner=gog")
+    );
+    assert_eq!(
+        HeroicStrategy.command(&game("heroic", "legendary:Sugar Cane")),
+        uri("heroic://launch?appName=Sugar%20Cane&runner=legendary")
+    );
+    assert_eq!(HeroicStrategy.command(&game("heroic", "no-runner")), None);
+}
+
+#[test]
+fn test_lutris_uri() {
+    assert_eq!(LutrisStrategy.command(&game("lutris", "42")), uri("lutris:rungameid/42"));
+}
+
+#[test]
+fn test_gog_galaxy_needs_client() {
+    let mut g = game("gog", "1207658924");
+    g.install_path = Some(r"C:\GOG Games\Witcher".to_string());
+
+    let missing = GogGalaxyStrategy {
+        client: PathBuf::from("/nonexistent/GalaxyClient.exe"),
+    };
+    assert_eq!(missing.command(&g), None);
+
+    let client = temp_exe("galaxy");
+    let galaxy = GogGalaxyStrategy {
+        client: client.clone(),
+    };
+    assert_eq!(
+        galaxy.command(&g),
+        Some(LaunchCommand::Client {
+            path: client,
+            args: vec![
+                "/command=runGame".to_string(),
+                "/gameId=1207658924".to_string(),
+                r"/path=C:\GOG Games\Witcher".to_string(),
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_exe_strategy_requires_existing_file() {
+    let exe = temp_exe("exe");
+    let mut g = game("folder", "x");
+    g.exe_path = Some(exe.to_string_lossy().to_string());
+    assert_eq!(ExeStrategy.command(&g), Some(exe_command(&exe)));
+
+    g.exe_path = Some("/nonexistent/game.exe".to_string());
+    assert_eq!(ExeStrategy.command(&g), None);
+}
+
+#[test]
+fn test_launcher_is_preferred_over_exe() {
+    let exe = temp_exe("chain");
+    let mut g = game("steam", "620");
+    g.exe_path = Some(exe.to_string_lossy().to_string());
+
+    let plan = LaunchPlanner::default().plan(&g);
+
+    assert_eq!(
+        plan,
+        vec![
+            ("steam", LaunchCommand::Uri("steam://rungameid/620".to_string())),
+            ("exe", exe_command(&exe)),
+        ]
+    );
+    assert_eq!(launch::launch_command(&g), uri("steam://rungameid/620"));
+}
+
+#[test]
+fn test_chain_falls_back_to_exe() {
+    let exe = temp_exe("fallback");
+    let planner = LaunchPlanner::new(vec![
+        Box::new(GogGalaxyStrategy {
+            client: PathBuf::from("/nonexistent/GalaxyClient.exe"),
+        }),
+        Box::new(ExeStrategy),
+    ]);
+    let mut g = game("gog", "1207658924");
+    g.exe_path = Some(exe.to_string_lossy().to_string());
+
+    assert_eq!(planner.plan(&g), vec![("exe", exe_command(&exe))]);
+
+    // Nothing can start a game without an exe or a known launcher
+    let unknown = game("manual", "1");
+    assert!(planner.plan(&unknown).is_empty());
+    assert_eq!(launch::launch_command(&unknown), None);
+}
+
+#[test]
+fn test_override_moves_strategy_to_front() {
+    let exe = temp_exe("override");
+    let mut g = game("steam", "620");
+    g.exe_path = Some(exe.to_string_lossy().to_string());
+    g.launch_override = Some("exe".to_string());
+
+    let plan = LaunchPlanner::default().plan(&g);
+
+    assert_eq!(plan[0], ("exe", exe_command(&exe)));
+    assert_eq!(plan[1].0, "steam");
+    assert_eq!(plan.len(), 2, "strategies are tried once");
+}
+
+#[test]
+fn test_custom_override_command_line() {
+    let mut g = game("steam", "620");
+    g.launch_override =
+        Some(r#"/usr/bin/gamemoderun "/games/My Game/run.sh" --fast"#.to_string());
+
+    let plan = LaunchPlanner::default().plan(&g);
+
+    assert_eq!(
+        plan[0],
+        (
+            "custom",
+            LaunchCommand::Exe {
+                path: PathBuf::from("/usr/bin/gamemoderun"),
+                args: vec!["/games/My Game/run.sh".to_string(), "--fast".to_string()],
+                working_dir: None,
+            }
+        )
+    );
+    assert_eq!(plan[1], ("steam", LaunchCommand::Uri("steam://rungameid/620".to_string())));
+}
+
+#[test]
+fn test_custom_override_uri_and_windows_path() {
+    assert_eq!(
+        launch::parse_custom_command("steam://run/620//-novid/"),
+        uri("steam://run/620//-novid/")
+    );
+    assert_eq!(
+        launch::parse_custom_command(r"C:\Games\Foo\foo.exe"),
+        Some(LaunchCommand::Exe {
+            path: PathBuf::from(r"C:\Games\Foo\foo.exe"),
+            args: Vec::new(),
+            working_dir: None,
+        })
+    );
+    assert_eq!(launch::parse_custom_command("   "), None);
+}
+
+#[test]
+fn test_only_exe_launch_roots_process_tree() {
+    assert!(exe_command(Path::new("/g/game")).is_game_process());
+    assert!(!LaunchCommand::Uri("steam://rungameid/620".to_string()).is_game_process());
+    assert!(!LaunchCommand::Client {
+        path: PathBuf::from("GalaxyClient.exe"),
+        args: Vec::new(),
+    }
+    .is_game_process());
+}
+
+#[test]
+fn test_retroarch_runs_playlist_core() {
+    let config_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/retroarch");
+    let strategy = RetroArchStrategy {
+        config_dirs: vec![config_dir],
+    };
+
+    let chrono = game("retroarch", "/roms/snes/Chrono Trigger (USA).sfc");
+    assert_eq!(
+        strategy.command(&chrono),
+        Some(LaunchCommand::Exe {
+            path: PathBuf::from("retroarch"),
+            args: vec![
+                "-L".to_string(),
+                "/usr/lib/libretro/snes9x_libretro.so".to_string(),
+                "/roms/snes/Chrono Trigger (USA).sfc".to_string(),
+            ],
+            working_dir: None,
+        })
+    );
+
+    // No core to pass with -L
+    let metroid = game(
+        "retroarch",
+        "/roms/snes/Super Metroid.zip#Super Metroid (Japan, USA) (En,Ja).sfc",
+    );
+    assert_eq!(strategy.command(&metroid), None);
+}
+
+#[test]
+fn test_emulator_rom_opens_in_rule_emulator() {
+    let rule = |folder: &str, emulator: &str| RomFolderRule {
+        folder: folder.to_string(),
+        system: "GameCube".to_string(),
+        extensions: vec!["rvz".to_string()],
+        emulator: emulator.to_string(),
+    };
+    let settings = AppSettings {
+        rom_folders: vec![
+            rule("/roms", "retroarch"),
+            rule("/roms/gc", r#""/opt/Dolphin Emu/dolphin-emu" -b -e"#),
+        ],
+        ..Default::default()
+    };
+    let mut g = game("emulator", "/roms/gc/Metroid Prime (USA).rvz");
+    g.install_path = Some("/roms/gc/Metroid Prime (USA).rvz".to_string());
+
+    let plan = LaunchPlanner::from_settings(&settings).plan(&g);
+
+    assert_eq!(
+        plan,
+        vec![(
+            "emulator",
+            LaunchCommand::Exe {
+                path: PathBuf::from("/opt/Dolphin Emu/dolphin-emu"),
+                args: vec![
+                    "-b".to_string(),
+                    "-e".to_string(),
+                    "/roms/gc/Metroid Prime (USA).rvz".to_string(),
+                ],
+                working_dir: None,
+            }
+        )]
+    );
+
+    // ROMs outside every rule folder have no emulator
+    g.install_path = Some("/elsewhere/Pikmin.rvz".to_string());
+    let strategy = EmulatorStrategy {
+        rules: settings.rom_folders.clone(),
+    };
+    assert_eq!(strategy.command(&g), None);
+}
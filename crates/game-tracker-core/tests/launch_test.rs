@@ -0,0 +1,230 @@
+//! Tests for game launching, process watching and tracked sessions.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+use common::test_pool;
+use game_tracker_core::db;
+use game_tracker_core::launch::{self, LaunchedGame, ProcessWatch};
+use game_tracker_core::models::Game;
+use game_tracker_core::process::{self, ProcessInfo};
+use sqlx::SqlitePool;
+
+fn game(install_path: Option<&str>, exe_path: Option<&str>) -> Game {
+    Game {
+        exe_path: exe_path.map(str::to_string),
+        install_path: install_path.map(str::to_string),
+        ..common::game("Test Game")
+    }
+}
+
+fn process(pid: u32, ppid: u32, exe: &str, cmdline: &[&str]) -> ProcessInfo {
+    ProcessInfo {
+        pid,
+        ppid,
+        exe: Some(PathBuf::from(exe)),
+        cmdline: cmdline.iter().map(|s| s.to_string()).collect(),
+        wine_prefix: None,
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gt-launch-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_watch_follows_children_after_launcher_exits() {
+    let g = game(None, Some("/opt/launcher/start.sh"));
+    let mut watch = ProcessWatch::new(Some(100), &g);
+
+    // The launcher forks the game...
+    let snapshot = [
+        process(100, 1, "/usr/bin/bash", &["bash", "/opt/launcher/start.sh"]),
+        process(101, 100, "/opt/elsewhere/game", &["/opt/elsewhere/game"]),
+    ];
+    assert!(watch.update(&snapshot));
+
+    // ...and exits; the orphaned game is reparented to init
+    let snapshot = [process(101, 1, "/opt/elsewhere/game", &["/opt/elsewhere/game"])];
+    assert!(watch.update(&snapshot));
+
+    assert!(!watch.update(&[process(7, 1, "/usr/bin/other", &[])]));
+    assert!(watch.has_started());
+}
+
+#[test]
+fn test_watch_matches_processes_in_install_dir() {
+    let g = game(Some("/games/Portal"), None);
+    let mut watch = ProcessWatch::new(None, &g);
+
+    assert!(!watch.update(&[process(5, 1, "/usr/bin/steam", &["steam"])]));
+    assert!(!watch.has_started());
+
+    let snapshot = [
+        process(5, 1, "/usr/bin/steam", &["steam"]),
+        process(9, 5, "/games/Portal/hl2_linux", &["/games/Portal/hl2_linux", "-game", "portal"]),
+        process(10, 9, "/usr/bin/crash_reporter", &[]),
+    ];
+    assert!(watch.update(&snapshot));
+    assert!(watch.has_started());
+
+    // Steam, the game's parent, isn't part of the game
+    assert!(!watch.update(&[process(5, 1, "/usr/bin/steam", &["steam"])]));
+}
+
+#[test]
+fn test_watch_matches_only_exe_in_shared_dir() {
+    let g = game(None, Some("/usr/bin/supertux2"));
+    let mut watch = ProcessWatch::new(None, &g);
+
+    assert!(!watch.update(&[process(5, 1, "/usr/bin/steam", &["steam"])]));
+    assert!(watch.update(&[process(6, 1, "/usr/bin/supertux2", &["supertux2"])]));
+}
+
+#[test]
+fn test_shared_directories_are_not_game_dirs() {
+    for dir in [
+        "/opt",
+        "/usr/bin",
+        "/usr/share/games",
+        "/home/u/.wine/drive_c",
+        "/home/u/.wine/drive_c/Program Files",
+        "/home/u/.wine/drive_c/windows/system32",
+        "/home/u/.wine/drive_c/users/steamuser",
+        "/home/u/.wine/drive_c/users/steamuser/AppData/Local",
+    ] {
+        assert!(!process::is_game_dir(Path::new(dir)), "{} is shared", dir);
+    }
+    for dir in [
+        "/games/steam/Celeste",
+        "/opt/emu",
+        "/home/u/.wine/drive_c/Program Files/Celeste",
+        "/home/u/.wine/drive_c/GOG Games/Tunic",
+    ] {
+        assert!(process::is_game_dir(Path::new(dir)), "{} is a game's own", dir);
+    }
+}
+
+#[test]
+fn test_wine_arguments_are_mapped_to_host_paths() {
+    let wine = ProcessInfo {
+        pid: 3,
+        ppid: 1,
+        exe: Some(PathBuf::from("/usr/bin/wine64-preloader")),
+        cmdline: vec!["C:\\Games\\Foo\\foo.exe".to_string()],
+        wine_prefix: Some(PathBuf::from("/home/u/.wine")),
+    };
+    assert!(wine.runs_from(Path::new("/home/u/.wine/drive_c/Games/Foo")));
+
+    let z_drive = process(4, 1, "/usr/bin/wine64-preloader", &["Z:\\srv\\games\\Bar\\bar.exe"]);
+    assert!(z_drive.runs_from(Path::new("/srv/games/Bar")));
+    assert!(!z_drive.runs_from(Path::new("/srv/games/Baz")));
+}
+
+fn fake_process(root: &Path, pid: u32, stat: &str, exe: Option<&str>, cmdline: &[u8]) {
+    let dir = root.join(pid.to_string());
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("stat"), stat).unwrap();
+    fs::write(dir.join("cmdline"), cmdline).unwrap();
+    #[cfg(unix)]
+    if let Some(exe) = exe {
+        std::os::unix::fs::symlink(exe, dir.join("exe")).unwrap();
+    }
+    #[cfg(not(unix))]
+    let _ = exe;
+}
+
+#[test]
+fn test_snapshot_reads_proc_layout() {
+    let root = temp_dir("proc");
+    fake_process(
+        &root,
+        42,
+        "42 (My Game (x64)) S 7 42 42 0 -1",
+        Some("/games/My Game/game.x86_64"),
+        b"/games/My Game/game.x86_64\0--fullscreen\0",
+    );
+    fs::write(root.join("42/environ"), b"HOME=/home/u\0WINEPREFIX=/pfx\0").unwrap();
+    fake_process(&root, 43, "43 (defunct) Z 42 0 0", None, b"");
+    fs::create_dir_all(root.join("self")).unwrap();
+
+    let snapshot = process::snapshot_from(&root);
+
+    assert_eq!(snapshot.len(), 1, "zombies and non-pid entries are skipped");
+    let p = &snapshot[0];
+    assert_eq!((p.pid, p.ppid), (42, 7));
+    assert_eq!(p.cmdline, vec!["/games/My Game/game.x86_64", "--fullscreen"]);
+    assert_eq!(p.wine_prefix.as_deref(), Some(Path::new("/pfx")));
+    #[cfg(unix)]
+    assert_eq!(p.exe.as_deref(), Some(Path::new("/games/My Game/game.x86_64")));
+}
+
+async fn insert_game(pool: &SqlitePool) -> i32 {
+    db::insert_game(pool, &game(None, None)).await.unwrap() as i32
+}
+
+#[tokio::test]
+async fn test_tracked_session_lifecycle() {
+    let pool = test_pool().await;
+    let id = insert_game(&pool).await;
+
+    let session_id = db::start_play_session(&pool, id).await.unwrap();
+    let g = db::get_game_by_id(&pool, id).await.unwrap();
+    assert_eq!(g.status, "Playing");
+    assert!(g.last_played.is_some());
+    assert!(db::get_play_session(&pool, session_id as i32).await.unwrap().active);
+
+    db::record_session_progress(&pool, session_id, 30).await.unwrap();
+    db::record_session_progress(&pool, session_id, 45).await.unwrap();
+    let g = db::get_game_by_id(&pool, id).await.unwrap();
+    assert!((g.playtime_hours - 0.75).abs() < 1e-6);
+
+    let closed = db::finish_play_session(&pool, session_id, 60).await.unwrap().unwrap();
+    assert!(!closed.active);
+    assert_eq!(closed.duration_minutes, 60);
+    let g = db::get_game_by_id(&pool, id).await.unwrap();
+    assert!((g.playtime_hours - 1.0).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_stale_sessions_are_closed_at_last_heartbeat() {
+    let pool = test_pool().await;
+    let id = insert_game(&pool).await;
+
+    let crashed = db::start_play_session(&pool, id).await.unwrap();
+    db::record_session_progress(&pool, crashed, 20).await.unwrap();
+    let empty = db::start_play_session(&pool, id).await.unwrap();
+
+    assert_eq!(db::close_stale_sessions(&pool).await.unwrap(), 1);
+
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id as i64, crashed);
+    assert_eq!(sessions[0].duration_minutes, 20);
+    assert!(!sessions[0].active);
+    assert!(db::get_play_session(&pool, empty as i32).await.is_err());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_short_tracked_run_is_discarded() {
+    let pool = test_pool().await;
+    let id = insert_game(&pool).await;
+    let session_id = db::start_play_session(&pool, id).await.unwrap();
+
+    let child = std::process::Command::new("sleep").arg("1").spawn().unwrap();
+    let launched = LaunchedGame {
+        game_id: id,
+        session_id,
+        watch: ProcessWatch::new(Some(child.id()), &game(None, None)),
+        child: Some(child),
+    };
+
+    assert!(launch::track_session(&pool, launched).await.unwrap().is_none());
+    assert!(db::get_play_sessions(&pool, id).await.unwrap().is_empty());
+}
@@ -0,0 +1,75 @@
+//! Tests for Lutris indexer database and config parsing.
+
+use game_tracker_core::indexers::lutris::{lutris_platform, scan_lutris_games_from};
+use std::path::PathBuf;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/lutris")
+}
+
+#[test]
+fn test_scan_lutris_discovers_installed_games_only() {
+    let games = scan_lutris_games_from(&fixtures_dir()).expect("scan should succeed");
+
+    // Four installed games; the uninstalled one is skipped
+    assert_eq!(games.len(), 4, "Expected 4 games, got {}", games.len());
+    assert!(games.iter().all(|g| g.source == "lutris"));
+    assert!(!games.iter().any(|g| g.title == "Wishlist Game"));
+}
+
+#[test]
+fn test_lutris_runner_platform_mapping() {
+    let games = scan_lutris_games_from(&fixtures_dir()).expect("scan should succeed");
+    let platform = |title: &str| {
+        games
+            .iter()
+            .find(|g| g.title == title)
+            .map(|g| g.platform.clone())
+            .unwrap()
+    };
+
+    assert_eq!(platform("Celeste"), "PC");
+    assert_eq!(platform("Diablo II"), "PC");
+    assert_eq!(platform("Super Metroid"), "SNES");
+    // libretro is multi-system, so the platform label decides
+    assert_eq!(platform("Chrono Trigger"), "SNES");
+}
+
+#[test]
+fn test_lutris_platform_labels_match_whole_words() {
+    let label = |platform: &str| lutris_platform(Some("libretro"), Some(platform));
+
+    assert_eq!(label("Sega Genesis"), "Genesis");
+    assert_eq!(label("Nintendo Entertainment System"), "NES");
+    assert_eq!(label("Nintendo NES"), "NES");
+    assert_eq!(label("Super Nintendo Entertainment System"), "SNES");
+    assert_eq!(label("Sega Mega Drive/Genesis"), "Genesis");
+    assert_eq!(label("Nintendo Game Boy Advance"), "GBA");
+}
+
+#[test]
+fn test_lutris_playtime_and_last_played() {
+    let games = scan_lutris_games_from(&fixtures_dir()).expect("scan should succeed");
+
+    let celeste = games.iter().find(|g| g.title == "Celeste").unwrap();
+    assert_eq!(celeste.source_id, "1");
+    assert_eq!(celeste.playtime_hours, Some(12.5));
+    assert_eq!(celeste.last_played.as_deref(), Some("2024-03-09 16:00:00"));
+    assert_eq!(
+        celeste.install_path.as_deref(),
+        Some("/home/user/Games/celeste")
+    );
+    // The configured exe doesn't exist on disk in test, so it should be None
+    assert!(celeste.exe_path.is_none());
+
+    let diablo = games.iter().find(|g| g.title == "Diablo II").unwrap();
+    assert!(diablo.playtime_hours.is_none());
+    assert!(diablo.last_played.is_none());
+}
+
+#[test]
+fn test_lutris_missing_db_returns_empty() {
+    let games = scan_lutris_games_from(&PathBuf::from("/nonexistent/lutris/12345"))
+        .expect("scan should succeed even for missing db");
+    assert!(games.is_empty());
+}
@@ -0,0 +1,167 @@
+//! Tests for logging, editing and deleting play sessions.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod common;
+
+use game_tracker_core::db;
+use game_tracker_core::models::{CreateGameInput, Game, PlaySessionInput};
+use game_tracker_core::rawg::RawgClient;
+use game_tracker_core::service::GameService;
+
+async fn test_service() -> GameService {
+    let pool = common::test_pool().await;
+    let icons_dir: PathBuf = std::env::temp_dir().join("gt-play-sessions-icons");
+    GameService::new(pool, Arc::new(RawgClient::new(String::new())), icons_dir)
+}
+
+async fn add_game(service: &GameService, title: &str) -> Game {
+    service
+        .create_game(CreateGameInput {
+            title: title.to_string(),
+            platform: "PC".to_string(),
+            status: "Backlog".to_string(),
+            rawg_id: None,
+            exe_path: None,
+            source: None,
+            source_id: None,
+            install_path: None,
+        })
+        .await
+        .expect("game should be created")
+}
+
+fn session(date: Option<&str>, minutes: i32, notes: Option<&str>) -> PlaySessionInput {
+    PlaySessionInput {
+        session_date: date.map(str::to_string),
+        duration_minutes: minutes,
+        notes: notes.map(str::to_string),
+    }
+}
+
+async fn reload(service: &GameService, id: i32) -> Game {
+    db::get_game_by_id(&service.pool, id).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_logging_sessions_adds_playtime() {
+    let service = test_service().await;
+    let game = add_game(&service, "Celeste").await;
+
+    service
+        .log_play_session(game.id, session(Some("2026-03-01T20:00"), 90, Some("Chapter 1")))
+        .await
+        .unwrap();
+    let second = service
+        .log_play_session(game.id, session(Some("2026-03-02 21:30:00"), 30, None))
+        .await
+        .unwrap();
+
+    assert_eq!(second.session_date, "2026-03-02 21:30:00");
+    let game = reload(&service, game.id).await;
+    assert!((game.playtime_hours - 2.0).abs() < 1e-6);
+    assert_eq!(game.last_played.as_deref(), Some("2026-03-02 21:30:00"));
+
+    let sessions = service.list_play_sessions(game.id).await.unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0].id, second.id, "most recent first");
+    assert_eq!(sessions[1].session_date, "2026-03-01 20:00:00");
+    assert_eq!(sessions[1].notes.as_deref(), Some("Chapter 1"));
+}
+
+#[tokio::test]
+async fn test_session_without_date_is_logged_now() {
+    let service = test_service().await;
+    let game = add_game(&service, "Hades").await;
+
+    let logged = service.log_play_session(game.id, session(None, 45, None)).await.unwrap();
+
+    assert!(!logged.session_date.is_empty());
+    let game = reload(&service, game.id).await;
+    assert_eq!(game.last_played, Some(logged.session_date));
+}
+
+#[tokio::test]
+async fn test_editing_session_shifts_playtime_by_difference() {
+    let service = test_service().await;
+    let game = add_game(&service, "Outer Wilds").await;
+    let first = service
+        .log_play_session(game.id, session(Some("2026-04-01"), 60, None))
+        .await
+        .unwrap();
+    let latest = service
+        .log_play_session(game.id, session(Some("2026-04-05"), 60, None))
+        .await
+        .unwrap();
+
+    service
+        .update_play_session(first.id, session(None, 120, Some("longer")))
+        .await
+        .unwrap();
+    let game_after = reload(&service, game.id).await;
+    assert!((game_after.playtime_hours - 3.0).abs() < 1e-6);
+    assert_eq!(game_after.last_played.as_deref(), Some("2026-04-05 00:00:00"));
+
+    // Moving the latest session back makes the other one the latest
+    service
+        .update_play_session(latest.id, session(Some("2026-03-20"), 60, None))
+        .await
+        .unwrap();
+    let game_after = reload(&service, game.id).await;
+    assert_eq!(game_after.last_played.as_deref(), Some("2026-04-01 00:00:00"));
+}
+
+#[tokio::test]
+async fn test_deleting_session_removes_its_time() {
+    let service = test_service().await;
+    let game = add_game(&service, "Tunic").await;
+    service
+        .log_play_session(game.id, session(Some("2026-05-01"), 30, None))
+        .await
+        .unwrap();
+    let latest = service
+        .log_play_session(game.id, session(Some("2026-05-03"), 90, None))
+        .await
+        .unwrap();
+
+    service.delete_play_session(latest.id).await.unwrap();
+
+    let game = reload(&service, game.id).await;
+    assert!((game.playtime_hours - 0.5).abs() < 1e-6);
+    assert_eq!(game.last_played.as_deref(), Some("2026-05-01 00:00:00"));
+    assert_eq!(service.list_play_sessions(game.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_playtime_never_goes_negative() {
+    let service = test_service().await;
+    let game = add_game(&service, "Inside").await;
+    let logged = service
+        .log_play_session(game.id, session(Some("2026-06-01"), 60, None))
+        .await
+        .unwrap();
+    sqlx::query("UPDATE games SET playtime_hours = 0.25 WHERE id = ?")
+        .bind(game.id)
+        .execute(&service.pool)
+        .await
+        .unwrap();
+
+    service.delete_play_session(logged.id).await.unwrap();
+
+    assert_eq!(reload(&service, game.id).await.playtime_hours, 0.0);
+}
+
+#[tokio::test]
+async fn test_invalid_sessions_are_rejected() {
+    let service = test_service().await;
+    let game = add_game(&service, "Braid").await;
+
+    assert!(service.log_play_session(game.id, session(None, 0, None)).await.is_err());
+    assert!(service
+        .log_play_session(game.id, session(Some("yesterday"), 30, None))
+        .await
+        .is_err());
+    assert!(service.log_play_session(9999, session(None, 30, None)).await.is_err());
+    assert!(service.list_play_sessions(game.id).await.unwrap().is_empty());
+}
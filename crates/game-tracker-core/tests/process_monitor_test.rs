@@ -0,0 +1,205 @@
+//! Tests for passive running-game detection.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+mod common;
+
+use chrono::{DateTime, TimeZone, Utc};
+use common::test_pool;
+use game_tracker_core::db;
+use game_tracker_core::models::Game;
+use game_tracker_core::monitor::{self, MonitorEvent, ProcessMonitor};
+use game_tracker_core::process::ProcessInfo;
+use sqlx::SqlitePool;
+
+fn game(id: i32, title: &str, install_path: Option<&str>, exe_path: Option<&str>) -> Game {
+    Game {
+        id,
+        exe_path: exe_path.map(str::to_string),
+        install_path: install_path.map(str::to_string),
+        ..common::game(title)
+    }
+}
+
+fn process(pid: u32, exe: &str, cmdline: &[&str]) -> ProcessInfo {
+    ProcessInfo {
+        pid,
+        ppid: 1,
+        exe: Some(PathBuf::from(exe)),
+        cmdline: cmdline.iter().map(|s| s.to_string()).collect(),
+        wine_prefix: None,
+    }
+}
+
+fn at(seconds: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 1, 20, 0, 0).unwrap() + chrono::Duration::seconds(seconds)
+}
+
+#[test]
+fn test_matches_processes_by_install_dir() {
+    let games = [
+        game(1, "Hollow Knight", Some("/games/steam/Hollow Knight"), None),
+        game(2, "Celeste", Some("/games/steam/Celeste"), None),
+    ];
+    let processes = [
+        process(10, "/games/steam/Hollow Knight/hollow_knight.x86_64", &[]),
+        process(11, "/usr/bin/steam", &["steam"]),
+    ];
+
+    let running = monitor::match_running_games(&games, &processes);
+
+    assert_eq!(running.into_iter().collect::<Vec<_>>(), vec![1]);
+}
+
+#[test]
+fn test_shared_directory_goes_to_deepest_path_then_matching_exe() {
+    let games = [
+        game(1, "Emulator", Some("/opt/emu"), Some("/opt/emu/emu")),
+        game(2, "Mario", None, Some("/opt/emu/mario.sh")),
+        game(3, "Zelda", Some("/opt/emu/roms/zelda.sfc"), None),
+    ];
+
+    let running = monitor::match_running_games(&games, &[process(5, "/opt/emu/mario.sh", &[])]);
+    assert_eq!(running.into_iter().collect::<Vec<_>>(), vec![2]);
+
+    let running = monitor::match_running_games(
+        &games,
+        &[process(6, "/opt/emu/emu", &["/opt/emu/emu", "/opt/emu/roms/zelda.sfc"])],
+    );
+    assert_eq!(running.into_iter().collect::<Vec<_>>(), vec![3]);
+}
+
+#[test]
+fn test_broad_directories_are_ignored() {
+    let games = [game(1, "Everything", Some("/games"), None)];
+    let running = monitor::match_running_games(&games, &[process(5, "/games/x/y", &[])]);
+    assert!(running.is_empty());
+}
+
+#[test]
+fn test_shared_start_dir_matches_only_the_exe() {
+    // A non-Steam shortcut to a distro package starts in /usr/bin
+    let games = [
+        game(1, "SuperTux", Some("/usr/bin"), Some("/usr/bin/supertux2")),
+        game(2, "Celeste", Some("/games/steam/Celeste"), None),
+    ];
+
+    let others = [
+        process(5, "/usr/bin/steam", &["steam"]),
+        process(6, "/usr/bin/bash", &["bash", "/usr/bin/xdg-open"]),
+    ];
+    assert!(monitor::match_running_games(&games, &others).is_empty());
+
+    let running =
+        monitor::match_running_games(&games, &[process(7, "/usr/bin/supertux2", &["supertux2"])]);
+    assert_eq!(running.into_iter().collect::<Vec<_>>(), vec![1]);
+}
+
+async fn insert(pool: &SqlitePool, title: &str, install_path: &str) -> i32 {
+    db::insert_game(pool, &game(0, title, Some(install_path), None))
+        .await
+        .unwrap() as i32
+}
+
+#[tokio::test]
+async fn test_short_runs_are_ignored() {
+    let pool = test_pool().await;
+    let id = insert(&pool, "Celeste", "/games/steam/Celeste").await;
+    let running = [process(10, "/games/steam/Celeste/Celeste", &[])];
+    let mut monitor = ProcessMonitor::new(Duration::from_secs(120));
+
+    assert!(monitor.poll(&pool, &running, at(0)).await.unwrap().is_empty());
+    assert!(monitor.poll(&pool, &running, at(60)).await.unwrap().is_empty());
+    assert_eq!(monitor.running_games(), vec![id]);
+    assert!(monitor.poll(&pool, &[], at(90)).await.unwrap().is_empty());
+
+    assert!(monitor.running_games().is_empty());
+    assert!(db::get_play_sessions(&pool, id).await.unwrap().is_empty());
+    assert_eq!(db::get_game_by_id(&pool, id).await.unwrap().status, "Backlog");
+}
+
+#[tokio::test]
+async fn test_session_opens_after_minimum_and_closes_on_exit() {
+    let pool = test_pool().await;
+    let id = insert(&pool, "Celeste", "/games/steam/Celeste").await;
+    let running = [process(10, "/games/steam/Celeste/Celeste", &[])];
+    let mut monitor = ProcessMonitor::new(Duration::from_secs(120));
+
+    monitor.poll(&pool, &running, at(0)).await.unwrap();
+    monitor.poll(&pool, &running, at(60)).await.unwrap();
+    let events = monitor.poll(&pool, &running, at(120)).await.unwrap();
+    let [MonitorEvent::NowPlaying { game_id, title }] = events.as_slice() else {
+        panic!("expected a now playing event, got {:?}", events);
+    };
+    assert_eq!((*game_id, title.as_str()), (id, "Celeste"));
+
+    let g = db::get_game_by_id(&pool, id).await.unwrap();
+    assert_eq!(g.status, "Playing");
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    assert_eq!(sessions[0].session_date, "2026-10-01 20:00:00", "starts when first seen");
+    assert!(sessions[0].active);
+
+    monitor.poll(&pool, &running, at(180)).await.unwrap();
+    let events = monitor.poll(&pool, &[], at(240)).await.unwrap();
+
+    let [MonitorEvent::Stopped { game_id, session, .. }] = events.as_slice() else {
+        panic!("expected a stop event, got {:?}", events);
+    };
+    assert_eq!(*game_id, id);
+    assert_eq!(session.duration_minutes, 3);
+    assert!(!session.active);
+    let g = db::get_game_by_id(&pool, id).await.unwrap();
+    assert!((g.playtime_hours - 0.05).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_suspend_gap_is_not_counted() {
+    let pool = test_pool().await;
+    let id = insert(&pool, "Tunic", "/games/steam/Tunic").await;
+    let running = [process(10, "/games/steam/Tunic/Tunic", &[])];
+    let mut monitor = ProcessMonitor::new(Duration::from_secs(60));
+
+    monitor.poll(&pool, &running, at(0)).await.unwrap();
+    monitor.poll(&pool, &running, at(60)).await.unwrap();
+    // Eight hours asleep
+    monitor.poll(&pool, &running, at(60 + 8 * 3600)).await.unwrap();
+    monitor.poll(&pool, &running, at(120 + 8 * 3600)).await.unwrap();
+    monitor.poll(&pool, &[], at(180 + 8 * 3600)).await.unwrap();
+
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    assert_eq!(sessions[0].duration_minutes, 2);
+}
+
+#[tokio::test]
+async fn test_games_launched_from_app_are_left_alone() {
+    let pool = test_pool().await;
+    let id = insert(&pool, "Hades", "/games/steam/Hades").await;
+    let launched = db::start_play_session(&pool, id).await.unwrap();
+    let running = [process(10, "/games/steam/Hades/Hades", &[])];
+    let mut monitor = ProcessMonitor::new(Duration::ZERO);
+
+    assert!(monitor.poll(&pool, &running, at(0)).await.unwrap().is_empty());
+    assert!(monitor.poll(&pool, &running, at(60)).await.unwrap().is_empty());
+
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id as i64, launched);
+}
+
+#[tokio::test]
+async fn test_stop_all_closes_open_sessions() {
+    let pool = test_pool().await;
+    let id = insert(&pool, "Inside", "/games/steam/Inside").await;
+    let running = [process(10, "/games/steam/Inside/Inside", &[])];
+    let mut monitor = ProcessMonitor::new(Duration::from_secs(60));
+
+    monitor.poll(&pool, &running, at(0)).await.unwrap();
+    monitor.poll(&pool, &running, at(90)).await.unwrap();
+    let events = monitor.stop_all(&pool).await.unwrap();
+
+    assert_eq!(events.len(), 1);
+    let sessions = db::get_play_sessions(&pool, id).await.unwrap();
+    assert!(!sessions[0].active);
+    assert_eq!(sessions[0].duration_minutes, 2);
+}
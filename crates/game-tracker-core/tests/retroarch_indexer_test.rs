@@ -0,0 +1,116 @@
+//! Tests for RetroArch playlist and runtime log parsing.
+
+use game_tracker_core::indexers::retroarch::{read_playlist, scan_retroarch_from};
+use game_tracker_core::indexers::systems::platform_for_system;
+use std::path::PathBuf;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/retroarch")
+}
+
+#[test]
+fn test_scan_retroarch_maps_playlists_to_platforms() {
+    let games = scan_retroarch_from(&fixtures_dir()).expect("scan should succeed");
+
+    // History playlists are skipped, so Chrono Trigger appears once
+    assert_eq!(games.len(), 4, "got {:?}", games);
+
+    let chrono = games.iter().find(|g| g.title == "Chrono Trigger").unwrap();
+    assert_eq!(chrono.platform, "SNES");
+    assert_eq!(chrono.source, "retroarch");
+    assert_eq!(chrono.source_id, "/roms/snes/Chrono Trigger (USA).sfc");
+    assert!(chrono.exe_path.is_none());
+
+    // Empty label falls back to the archive member name
+    let metroid = games.iter().find(|g| g.title == "Super Metroid").unwrap();
+    assert_eq!(metroid.platform, "SNES");
+
+    let ff7 = games
+        .iter()
+        .find(|g| g.title == "Final Fantasy VII")
+        .unwrap();
+    assert_eq!(ff7.platform, "PS1");
+
+    // Custom playlists fall back to each entry's db_name
+    let advance_wars = games.iter().find(|g| g.title == "Advance Wars").unwrap();
+    assert_eq!(advance_wars.platform, "GBA");
+}
+
+#[test]
+fn test_retroarch_runtime_logs_import_playtime() {
+    let games = scan_retroarch_from(&fixtures_dir()).expect("scan should succeed");
+
+    let chrono = games.iter().find(|g| g.title == "Chrono Trigger").unwrap();
+    assert_eq!(chrono.playtime_hours, Some(12.5));
+    assert!(chrono.last_played.is_some());
+
+    // Per-core logs for the same content are summed
+    let ff7 = games
+        .iter()
+        .find(|g| g.title == "Final Fantasy VII")
+        .unwrap();
+    assert_eq!(ff7.playtime_hours, Some(3.5));
+    assert!(ff7.last_played.as_deref().unwrap().starts_with("2024-03-1"));
+
+    let metroid = games.iter().find(|g| g.title == "Super Metroid").unwrap();
+    assert!(metroid.playtime_hours.is_none());
+    assert!(metroid.last_played.is_none());
+}
+
+#[test]
+fn test_read_playlist_crc32() {
+    let entries = read_playlist(
+        &fixtures_dir().join("playlists/Nintendo - Super Nintendo Entertainment System.lpl"),
+    )
+    .unwrap();
+    assert_eq!(entries[0].crc32(), Some(0x2D20_6BF7));
+    assert_eq!(entries[1].crc32(), None);
+}
+
+fn temp_config(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gt-retroarch-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("playlists")).unwrap();
+    dir
+}
+
+#[test]
+fn test_legacy_playlist_format() {
+    let dir = temp_config("legacy");
+    std::fs::write(
+        dir.join("playlists/Nintendo - Game Boy.lpl"),
+        "/roms/gb/Tetris (World).gb\nTetris (World)\n/cores/gambatte_libretro.so\nGambatte\n\
+         46DF91AD|crc\nNintendo - Game Boy.lpl\n\
+         /roms/gb/Kirby's Dream Land (USA, Europe).gb\nKirby's Dream Land (USA, Europe)\n\
+         DETECT\nDETECT\nDETECT\nNintendo - Game Boy.lpl\n",
+    )
+    .unwrap();
+
+    let games = scan_retroarch_from(&dir).expect("legacy playlists should parse");
+
+    let titles: Vec<&str> = games.iter().map(|g| g.title.as_str()).collect();
+    assert_eq!(titles, ["Tetris", "Kirby's Dream Land"]);
+    assert!(games.iter().all(|g| g.platform == "GB"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_unreadable_playlist_fails_scan() {
+    let dir = temp_config("broken");
+    std::fs::write(dir.join("playlists/Sega - Mega Drive - Genesis.lpl"), "{\"items\": [").unwrap();
+
+    // A partial scan would mark the playlist's games uninstalled
+    assert!(scan_retroarch_from(&dir).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_platform_for_system_names() {
+    assert_eq!(platform_for_system("Sony - PlayStation"), Some("PS1"));
+    assert_eq!(platform_for_system("Sony - PlayStation 2.lpl"), Some("PS2"));
+    assert_eq!(
+        platform_for_system("Nintendo - Super Nintendo Entertainment System (Parent-Clone)"),
+        Some("SNES")
+    );
+    assert_eq!(platform_for_system("Something Else"), None);
+}
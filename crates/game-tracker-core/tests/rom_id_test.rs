@@ -0,0 +1,163 @@
+//! Tests for ROM identification against Logiqx DAT files.
+
+use game_tracker_core::indexers::retroarch::scan_retroarch_with;
+use game_tracker_core::indexers::rom_id::{canonical_title, hash_rom, RomDatabase, RomHashCache};
+use std::fs;
+use std::path::PathBuf;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn dat_files() -> Vec<PathBuf> {
+    let dats = fixtures_dir().join("dats");
+    let mut files: Vec<PathBuf> = fs::read_dir(dats)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Fresh scratch directory under the system temp dir.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gt-rom-id-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// ROM data listed in the SNES fixture DAT.
+fn snes_rom() -> Vec<u8> {
+    (0..1024u32).map(|i| (i * 7 % 256) as u8).collect()
+}
+
+/// ROM data listed (headerless) in the NES fixture DAT.
+fn nes_rom() -> Vec<u8> {
+    (0..32u32).map(|i| i as u8).collect()
+}
+
+#[test]
+fn test_identify_rom_by_hash() {
+    let db = RomDatabase::load(&dat_files());
+    let dir = scratch_dir("plain");
+    let rom = dir.join("zelda3.sfc");
+    fs::write(&rom, snes_rom()).unwrap();
+
+    let identity = db.identify(&rom).expect("ROM should be identified");
+    assert_eq!(identity.title, "The Legend of Zelda - A Link to the Past");
+    assert_eq!(identity.region.as_deref(), Some("USA"));
+    assert_eq!(
+        identity.system,
+        "Nintendo - Super Nintendo Entertainment System"
+    );
+    assert_eq!(identity.platform, Some("SNES"));
+}
+
+#[test]
+fn test_identify_skips_copier_and_ines_headers() {
+    let db = RomDatabase::load(&dat_files());
+    let dir = scratch_dir("headers");
+
+    let mut smc = vec![0u8; 512];
+    smc.extend(snes_rom());
+    fs::write(dir.join("zelda3.smc"), smc).unwrap();
+    let zelda = db.identify(&dir.join("zelda3.smc")).expect("headered SNES ROM");
+    assert_eq!(zelda.platform, Some("SNES"));
+
+    let mut nes = b"NES\x1a\x02\x01".to_vec();
+    nes.resize(16, 0);
+    nes.extend(nes_rom());
+    fs::write(dir.join("smb.nes"), nes).unwrap();
+    let smb = db.identify(&dir.join("smb.nes")).expect("iNES ROM");
+    assert_eq!(smb.title, "Super Mario Bros.");
+    assert_eq!(smb.region.as_deref(), Some("World"));
+    assert_eq!(smb.platform, Some("NES"));
+}
+
+#[test]
+fn test_identify_unknown_rom() {
+    let db = RomDatabase::load(&dat_files());
+    let dir = scratch_dir("unknown");
+    let rom = dir.join("homebrew.sfc");
+    fs::write(&rom, vec![0xAAu8; 1024]).unwrap();
+
+    assert!(db.identify(&rom).is_none());
+    assert!(RomDatabase::default().identify(&rom).is_none());
+}
+
+#[test]
+fn test_header_magic_on_tiny_file() {
+    let db = RomDatabase::load(&dat_files());
+    let dir = scratch_dir("tiny");
+    let rom = dir.join("stub.nes");
+    fs::write(&rom, b"NES\x1a").unwrap();
+
+    assert!(db.identify(&rom).is_none());
+    let (_, headerless) = hash_rom(&rom).unwrap();
+    assert!(headerless.is_none(), "a header longer than the file is no header");
+}
+
+#[test]
+fn test_hash_cache_reuses_unchanged_roms() {
+    let dir = scratch_dir("cache");
+    let rom = dir.join("zelda3.sfc");
+    fs::write(&rom, snes_rom()).unwrap();
+    let modified = fs::metadata(&rom).unwrap().modified().unwrap();
+    let cache_file = dir.join("cache/rom_hashes.json");
+
+    let db = RomDatabase::load(&dat_files()).with_hash_cache(RomHashCache::open(&cache_file));
+    assert!(db.identify(&rom).is_some());
+    db.save_hash_cache();
+
+    // Same size and mtime: the stored hashes are trusted without reading
+    let mut changed = snes_rom();
+    changed[0] ^= 0xFF;
+    fs::write(&rom, &changed).unwrap();
+    fs::File::options().write(true).open(&rom).unwrap().set_modified(modified).unwrap();
+    let cache = RomHashCache::open(&cache_file);
+    assert_eq!(cache.len(), 1);
+    let db = RomDatabase::load(&dat_files()).with_hash_cache(cache);
+    assert!(db.identify(&rom).is_some());
+
+    // A new mtime means the file is hashed again
+    fs::File::options()
+        .write(true)
+        .open(&rom)
+        .unwrap()
+        .set_modified(modified + std::time::Duration::from_secs(60))
+        .unwrap();
+    assert!(db.identify(&rom).is_none());
+
+    // Deleted ROMs are dropped on save
+    fs::remove_file(&rom).unwrap();
+    db.save_hash_cache();
+    assert!(RomHashCache::open(&cache_file).is_empty());
+}
+
+#[test]
+fn test_canonical_title_moves_articles() {
+    assert_eq!(
+        canonical_title("Legend of Zelda, The - Ocarina of Time (USA) (Rev 2)"),
+        "The Legend of Zelda - Ocarina of Time"
+    );
+    assert_eq!(canonical_title("Chrono Trigger (USA) [!]"), "Chrono Trigger");
+}
+
+#[test]
+fn test_retroarch_entries_use_dat_identity() {
+    let db = RomDatabase::load(&dat_files());
+    let games =
+        scan_retroarch_with(&fixtures_dir().join("retroarch"), &db).expect("scan should succeed");
+
+    // Matched through the playlist CRC
+    let chrono = games
+        .iter()
+        .find(|g| g.source_id == "/roms/snes/Chrono Trigger (USA).sfc")
+        .unwrap();
+    assert_eq!(chrono.title, "Chrono Trigger");
+    assert_eq!(chrono.platform, "SNES");
+
+    // Unmatched entries keep their playlist title
+    assert!(games.iter().any(|g| g.title == "Super Metroid"));
+}
@@ -0,0 +1,115 @@
+//! Tests for re-indexing one source after a watched change.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+mod common;
+
+use game_tracker_core::db;
+use game_tracker_core::indexers::{IndexerError, IndexerRegistry, LauncherIndexer};
+use game_tracker_core::models::DiscoveredGame;
+use game_tracker_core::rawg::RawgClient;
+use game_tracker_core::service::GameService;
+
+/// Launcher whose manifests are named after the source id of their game.
+struct ManifestIndexer {
+    installed: Arc<Mutex<Vec<&'static str>>>,
+    full_scans: Arc<Mutex<u32>>,
+}
+
+fn discovered(id: &str) -> DiscoveredGame {
+    DiscoveredGame {
+        title: format!("Game {}", id),
+        platform: "PC".to_string(),
+        exe_path: None,
+        install_path: None,
+        source: "manifests".to_string(),
+        source_id: id.to_string(),
+        playtime_hours: None,
+        last_played: None,
+    }
+}
+
+impl LauncherIndexer for ManifestIndexer {
+    fn id(&self) -> &'static str {
+        "manifests"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Manifests"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn scan(&self) -> Result<Vec<DiscoveredGame>, IndexerError> {
+        *self.full_scans.lock().unwrap() += 1;
+        Ok(self.installed.lock().unwrap().iter().map(|id| discovered(id)).collect())
+    }
+
+    fn scan_changed(
+        &self,
+        changed: &[PathBuf],
+    ) -> Option<Result<Vec<DiscoveredGame>, IndexerError>> {
+        let installed = self.installed.lock().unwrap();
+        let mut games = Vec::new();
+        for path in changed {
+            let id = path.file_name()?.to_str()?;
+            // Removed manifest: needs a full scan
+            if !installed.contains(&id) {
+                return None;
+            }
+            games.push(discovered(id));
+        }
+        Some(Ok(games))
+    }
+}
+
+#[tokio::test]
+async fn test_changed_manifests_reindex_only_their_games() {
+    let pool = common::test_pool().await;
+
+    let installed = Arc::new(Mutex::new(vec!["a", "b"]));
+    let full_scans = Arc::new(Mutex::new(0));
+    let mut service = GameService::new(
+        pool.clone(),
+        Arc::new(RawgClient::new(String::new())),
+        std::env::temp_dir().join("gt-source-indexer-icons"),
+    );
+    let mut registry = IndexerRegistry::empty();
+    registry.register(Box::new(ManifestIndexer {
+        installed: installed.clone(),
+        full_scans: full_scans.clone(),
+    }));
+    service.indexers = registry;
+
+    // The handle works without the service
+    let indexer = service.source_indexer("manifests").unwrap();
+    drop(service);
+
+    let result = indexer.index().await.unwrap();
+    assert_eq!((result.upserted, *full_scans.lock().unwrap()), (2, 1));
+
+    // "b" is gone but only "a" changed: nothing is marked uninstalled
+    installed.lock().unwrap().retain(|id| *id != "b");
+    let result = indexer.index_changes(vec![PathBuf::from("/m/a")]).await.unwrap();
+    assert_eq!((result.upserted, result.uninstalled), (1, 0));
+    assert_eq!(*full_scans.lock().unwrap(), 1);
+
+    // A removed manifest falls back to a full scan
+    let result = indexer.index_changes(vec![PathBuf::from("/m/b")]).await.unwrap();
+    assert_eq!(result.uninstalled, 1);
+    assert_eq!(*full_scans.lock().unwrap(), 2);
+    let installed: Vec<bool> =
+        db::get_all_games(&pool).await.unwrap().iter().map(|g| g.installed).collect();
+    assert_eq!(installed.iter().filter(|i| **i).count(), 1);
+
+    assert!(GameService::new(
+        pool,
+        Arc::new(RawgClient::new(String::new())),
+        std::env::temp_dir().join("gt-source-indexer-icons"),
+    )
+    .source_indexer("nope")
+    .is_err());
+}
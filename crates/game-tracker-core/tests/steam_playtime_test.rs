@@ -0,0 +1,39 @@
+//! Tests for Steam playtime import from `localconfig.vdf`.
+
+use game_tracker_core::indexers::steam::scan_steam_playtime_from;
+use std::path::PathBuf;
+
+fn steam_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/steam")
+}
+
+#[test]
+fn test_playtime_summed_across_accounts() {
+    let playtime = scan_steam_playtime_from(&steam_root(), None);
+
+    // 600 + 120 minutes, most recent last-played of the two accounts
+    let tf2 = &playtime["440"];
+    assert_eq!(tf2.playtime_hours, 12.0);
+    assert_eq!(tf2.last_played.as_deref(), Some("2024-03-09 16:00:00"));
+
+    let portal2 = &playtime["620"];
+    assert_eq!(portal2.playtime_hours, 0.75);
+
+    // Apps with neither playtime nor last-played are omitted
+    assert!(!playtime.contains_key("228980"));
+}
+
+#[test]
+fn test_playtime_for_single_account() {
+    let playtime = scan_steam_playtime_from(&steam_root(), Some("12345678"));
+
+    let tf2 = &playtime["440"];
+    assert_eq!(tf2.playtime_hours, 10.0);
+    assert_eq!(tf2.last_played.as_deref(), Some("2023-11-14 22:13:20"));
+}
+
+#[test]
+fn test_playtime_missing_userdata_returns_empty() {
+    let playtime = scan_steam_playtime_from(&PathBuf::from("/nonexistent/steam/12345"), None);
+    assert!(playtime.is_empty());
+}
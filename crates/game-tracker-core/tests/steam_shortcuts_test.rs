@@ -0,0 +1,50 @@
+//! Tests for non-Steam shortcut parsing from binary `shortcuts.vdf` files.
+
+use game_tracker_core::indexers::steam_shortcuts::scan_steam_shortcuts_from;
+use std::path::PathBuf;
+
+fn steam_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/steam")
+}
+
+#[test]
+fn test_scan_shortcuts_across_users_deduplicates() {
+    let games = scan_steam_shortcuts_from(&steam_root()).expect("scan should succeed");
+
+    // RetroArch is present for both users but reported once
+    assert_eq!(games.len(), 3, "Expected 3 shortcuts, got {}", games.len());
+    assert!(games.iter().all(|g| g.source == "steam-shortcut"));
+}
+
+#[test]
+fn test_shortcut_fields() {
+    let games = scan_steam_shortcuts_from(&steam_root()).expect("scan should succeed");
+
+    let retroarch = games.iter().find(|g| g.title == "RetroArch").unwrap();
+    // Signed appid from the file, reinterpreted as unsigned
+    assert_eq!(retroarch.source_id, "3060399406");
+    assert_eq!(retroarch.exe_path.as_deref(), Some("/usr/bin/retroarch"));
+    assert_eq!(retroarch.install_path.as_deref(), Some("/usr/bin/"));
+    assert_eq!(retroarch.platform, "PC");
+    assert_eq!(
+        retroarch.last_played.as_deref(),
+        Some("2023-11-14 22:13:20")
+    );
+}
+
+#[test]
+fn test_legacy_shortcut_without_appid() {
+    let games = scan_steam_shortcuts_from(&steam_root()).expect("scan should succeed");
+
+    // Lowercase `appname` key and no stored appid: id derived from exe + name
+    let old = games.iter().find(|g| g.title == "Old Shortcut").unwrap();
+    assert_eq!(old.source_id, "3208365117");
+    assert_eq!(old.exe_path.as_deref(), Some("C:\\Games\\Old\\old.exe"));
+}
+
+#[test]
+fn test_shortcuts_missing_userdata_returns_empty() {
+    let games = scan_steam_shortcuts_from(&PathBuf::from("/nonexistent/steam/12345"))
+        .expect("scan should succeed even for missing dir");
+    assert!(games.is_empty());
+}
@@ -0,0 +1,116 @@
+//! Tests for the launcher directory watcher.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use game_tracker_core::watcher::{changed_sources, LibraryWatcher, WatchTarget};
+
+fn target(source: &str, path: &str) -> WatchTarget {
+    WatchTarget {
+        source: source.to_string(),
+        path: PathBuf::from(path),
+        recursive: false,
+    }
+}
+
+#[test]
+fn test_changed_sources_maps_paths_to_owners() {
+    let targets = vec![
+        target("steam", "/lib1/steamapps"),
+        target("steam", "/lib2/steamapps"),
+        target("epic", "/epic/Manifests"),
+    ];
+    let paths = [
+        Path::new("/lib2/steamapps/appmanifest_440.acf"),
+        Path::new("/lib1/steamapps/appmanifest_620.acf"),
+        Path::new("/elsewhere/file"),
+    ];
+    let changes = changed_sources(&targets, &paths);
+    assert_eq!(changes.keys().collect::<Vec<_>>(), ["steam"]);
+    assert_eq!(
+        changes["steam"],
+        [
+            PathBuf::from("/lib2/steamapps/appmanifest_440.acf"),
+            PathBuf::from("/lib1/steamapps/appmanifest_620.acf"),
+        ]
+    );
+
+    let paths = [Path::new("/epic/Manifests/ABC.item")];
+    assert_eq!(changed_sources(&targets, &paths).keys().collect::<Vec<_>>(), ["epic"]);
+}
+
+#[test]
+fn test_download_churn_is_ignored() {
+    let targets = vec![target("steam", "/lib1/steamapps")];
+    let paths = [
+        Path::new("/lib1/steamapps/downloading"),
+        Path::new("/lib1/steamapps/downloading/620/portal2.vpk"),
+        Path::new("/lib1/steamapps/temp/620"),
+    ];
+    assert!(changed_sources(&targets, &paths).is_empty());
+}
+
+#[test]
+fn test_watcher_reports_debounced_source_change() {
+    let dir = std::env::temp_dir().join(format!("gt-watcher-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let _watcher = LibraryWatcher::start(
+        vec![WatchTarget {
+            source: "epic".to_string(),
+            path: dir.clone(),
+            recursive: false,
+        }],
+        Duration::from_millis(200),
+        move |source, _| tx.send(source.to_string()).unwrap(),
+    )
+    .expect("watcher should start");
+
+    // Writes are reported per source, not per file
+    for i in 0..3 {
+        std::fs::write(dir.join(format!("game{}.item", i)), "{}").unwrap();
+    }
+
+    let source = rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("change should be reported");
+    assert_eq!(source, "epic");
+    while let Ok(source) = rx.recv_timeout(Duration::from_millis(600)) {
+        assert_eq!(source, "epic");
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_recursive_target_reports_nested_changes() {
+    let dir = std::env::temp_dir().join(format!("gt-watcher-nested-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("snes")).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let _watcher = LibraryWatcher::start(
+        vec![WatchTarget {
+            source: "emulator".to_string(),
+            path: dir.clone(),
+            recursive: true,
+        }],
+        Duration::from_millis(200),
+        move |source, changed| tx.send((source.to_string(), changed.to_vec())).unwrap(),
+    )
+    .expect("watcher should start");
+
+    let rom = dir.join("snes/Chrono Trigger (USA).sfc");
+    std::fs::write(&rom, "").unwrap();
+
+    let (source, changed) = rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("nested change should be reported");
+    assert_eq!(source, "emulator");
+    assert!(changed.contains(&rom));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,6 @@
+-- Key/value store for user settings (JSON-encoded values)
+
+CREATE TABLE settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
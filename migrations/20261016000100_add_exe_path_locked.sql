@@ -0,0 +1,3 @@
+-- Remember executables picked by the user so re-indexing doesn't replace them
+
+ALTER TABLE games ADD COLUMN exe_path_locked INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- Track whether indexed games are still installed in their launcher
+
+ALTER TABLE games ADD COLUMN installed INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE games ADD COLUMN uninstalled_at DATETIME;   -- Set when a scan stops finding the game
@@ -0,0 +1,76 @@
+-- One game, many sources: launcher ownership moves to game_sources
+--
+-- games keeps source/source_id/install_path/exe_path/installed as a copy of
+-- its primary source so single-source views keep working.
+
+-- Rebuild games without UNIQUE(title, platform): two distinct games may share
+-- a title (a split duplicate, a remake). DROP TABLE cascades into
+-- play_sessions, so sessions are parked in a temp table meanwhile.
+CREATE TEMP TABLE sessions_backup AS SELECT * FROM play_sessions;
+
+CREATE TABLE games_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    platform TEXT NOT NULL,
+    status TEXT DEFAULT 'Backlog',
+    description TEXT,
+    genre TEXT,
+    release_year INTEGER,
+    icon_path TEXT,
+    cover_url TEXT,
+    rawg_id INTEGER,
+    exe_path TEXT,
+    playtime_hours REAL DEFAULT 0,
+    rating INTEGER,
+    added_date DATETIME DEFAULT CURRENT_TIMESTAMP,
+    last_played DATETIME,
+    source TEXT,
+    source_id TEXT,
+    install_path TEXT,
+    exe_path_locked INTEGER NOT NULL DEFAULT 0,
+    installed INTEGER NOT NULL DEFAULT 1,
+    uninstalled_at DATETIME
+);
+
+INSERT INTO games_new (id, title, platform, status, description, genre, release_year,
+    icon_path, cover_url, rawg_id, exe_path, playtime_hours, rating, added_date,
+    last_played, source, source_id, install_path, exe_path_locked, installed, uninstalled_at)
+SELECT id, title, platform, status, description, genre, release_year,
+    icon_path, cover_url, rawg_id, exe_path, playtime_hours, rating, added_date,
+    last_played, source, source_id, install_path, exe_path_locked, installed, uninstalled_at
+FROM games;
+
+DROP TABLE games;
+ALTER TABLE games_new RENAME TO games;
+
+DELETE FROM play_sessions;
+INSERT INTO play_sessions SELECT * FROM sessions_backup;
+DROP TABLE sessions_backup;
+
+CREATE INDEX idx_games_status ON games(status);
+CREATE INDEX idx_games_platform ON games(platform);
+CREATE INDEX idx_games_source ON games(source);
+CREATE INDEX idx_games_source_id ON games(source, source_id);   -- primary source only, no longer unique
+CREATE INDEX idx_games_rawg_id ON games(rawg_id);
+
+CREATE TABLE game_sources (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id INTEGER NOT NULL,
+    source TEXT NOT NULL,            -- "steam", "epic", "gog", ...
+    source_id TEXT NOT NULL,         -- launcher-specific id
+    install_path TEXT,
+    exe_path TEXT,
+    installed INTEGER NOT NULL DEFAULT 1,
+    playtime_hours REAL NOT NULL DEFAULT 0,  -- last launcher-reported playtime
+    added_date DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(source, source_id),
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_game_sources_game ON game_sources(game_id);
+
+INSERT INTO game_sources (game_id, source, source_id, install_path, exe_path, installed,
+    playtime_hours)
+SELECT id, source, source_id, install_path, exe_path, installed, COALESCE(playtime_hours, 0)
+FROM games
+WHERE source IS NOT NULL AND source_id IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Sessions recorded by launching a game from the app
+--
+-- A tracked session is written when the game starts and its duration is
+-- updated while it runs, so a crash or power loss leaves the time played up
+-- to the last heartbeat. Sessions still active at startup are closed then.
+
+ALTER TABLE play_sessions ADD COLUMN active INTEGER NOT NULL DEFAULT 0;  -- game still running
+ALTER TABLE play_sessions ADD COLUMN last_seen DATETIME;                 -- last heartbeat
+
+CREATE INDEX idx_sessions_active ON play_sessions(active);
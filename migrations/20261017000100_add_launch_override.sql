@@ -0,0 +1,4 @@
+-- Per-game launch override: a launch strategy id ("exe", "steam", ...) to try
+-- first, or a custom command line / URI to launch with instead
+
+ALTER TABLE games ADD COLUMN launch_override TEXT;
@@ -0,0 +1,19 @@
+-- Sessions inferred from launcher playtime
+--
+-- When a launcher reports more playtime than at the previous index run, the
+-- difference (less whatever the app tracked itself meanwhile) is logged as
+-- an inferred session. playtime_snapshots keeps the launcher values that
+-- difference is measured against, one row per game source.
+
+ALTER TABLE play_sessions ADD COLUMN inferred INTEGER NOT NULL DEFAULT 0;  -- from launcher playtime
+
+CREATE TABLE playtime_snapshots (
+    game_source_id INTEGER PRIMARY KEY,
+    playtime_hours REAL NOT NULL DEFAULT 0,     -- launcher playtime last accounted for
+    last_played DATETIME,                       -- launcher last-played at that point
+    taken_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (game_source_id) REFERENCES game_sources(id) ON DELETE CASCADE
+);
+
+INSERT INTO playtime_snapshots (game_source_id, playtime_hours)
+SELECT id, playtime_hours FROM game_sources;
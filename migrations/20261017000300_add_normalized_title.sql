@@ -0,0 +1,8 @@
+-- Normalized title for matching a launcher entry to an existing game
+--
+-- Holds db::normalize_title(title), written by the app whenever it stores a
+-- title. Rows from before this migration are filled in at startup.
+
+ALTER TABLE games ADD COLUMN normalized_title TEXT;
+
+CREATE INDEX idx_games_normalized_title ON games(normalized_title, platform);
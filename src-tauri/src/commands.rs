@@ -2,14 +2,17 @@
 
 use game_tracker_core::models::*;
 use game_tracker_core::rawg::RawgGame;
-use game_tracker_core::service::{GameService, IndexResult};
+use game_tracker_core::service::{GameService, IndexProgress, IndexResult};
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use tokio::sync::Mutex;
 
 /// Shared mutable service state managed by Tauri.
 pub type ServiceState = Arc<Mutex<GameService>>;
 
+/// Event carrying [`IndexProgress`] updates while `index_now` runs.
+pub const INDEX_PROGRESS_EVENT: &str = "index-progress";
+
 // ---- Games CRUD -------------------------------------------------------------
 
 #[tauri::command]
@@ -70,6 +73,138 @@ pub async fn delete_game(
     svc.delete_game(id).await
 }
 
+#[tauri::command]
+/// Rank executables under a game's install directory.
+pub async fn exe_candidates(
+    service: State<'_, ServiceState>,
+    id: i32,
+) -> Result<ExeDetection, String> {
+    let svc = service.lock().await;
+    svc.exe_candidates(id).await
+}
+
+#[tauri::command]
+/// Set the executable for a game, overriding detection.
+pub async fn set_game_exe(
+    service: State<'_, ServiceState>,
+    id: i32,
+    exe_path: String,
+) -> Result<(), String> {
+    let svc = service.lock().await;
+    svc.set_game_exe(id, &exe_path).await
+}
+
+#[tauri::command]
+/// List the launcher sources attached to a game.
+pub async fn get_game_sources(
+    service: State<'_, ServiceState>,
+    id: i32,
+) -> Result<Vec<GameSource>, String> {
+    let svc = service.lock().await;
+    svc.game_sources(id).await
+}
+
+#[tauri::command]
+/// Merge game `from` into `into`, moving its sources and sessions.
+pub async fn merge_games(
+    service: State<'_, ServiceState>,
+    into: i32,
+    from: i32,
+) -> Result<Game, String> {
+    let svc = service.lock().await;
+    svc.merge_games(into, from).await
+}
+
+#[tauri::command]
+/// Detach one source row from its game into a new game.
+pub async fn split_game_source(
+    service: State<'_, ServiceState>,
+    source_id: i32,
+) -> Result<Game, String> {
+    let svc = service.lock().await;
+    svc.split_game_source(source_id).await
+}
+
+// ---- Play sessions ----------------------------------------------------------
+
+#[tauri::command]
+/// List a game's play sessions, most recent first.
+pub async fn list_play_sessions(
+    service: State<'_, ServiceState>,
+    game_id: i32,
+) -> Result<Vec<PlaySession>, String> {
+    let svc = service.lock().await;
+    svc.list_play_sessions(game_id).await
+}
+
+#[tauri::command]
+/// Log a play session for a game.
+pub async fn log_play_session(
+    service: State<'_, ServiceState>,
+    game_id: i32,
+    input: PlaySessionInput,
+) -> Result<PlaySession, String> {
+    let svc = service.lock().await;
+    svc.log_play_session(game_id, input).await
+}
+
+#[tauri::command]
+/// Edit a play session's date, duration or notes.
+pub async fn update_play_session(
+    service: State<'_, ServiceState>,
+    id: i32,
+    input: PlaySessionInput,
+) -> Result<PlaySession, String> {
+    let svc = service.lock().await;
+    svc.update_play_session(id, input).await
+}
+
+#[tauri::command]
+/// Delete a play session by id.
+pub async fn delete_play_session(
+    service: State<'_, ServiceState>,
+    id: i32,
+) -> Result<(), String> {
+    let svc = service.lock().await;
+    svc.delete_play_session(id).await
+}
+
+#[tauri::command]
+/// Start a game and track its play session until it exits. Returns whether
+/// the session is tracked; see [`GameService::launch_game`].
+pub async fn launch_game(
+    app: AppHandle,
+    service: State<'_, ServiceState>,
+    id: i32,
+) -> Result<bool, String> {
+    let svc = service.lock().await;
+    let Some(launched) = svc.launch_game(id).await? else {
+        return Ok(false);
+    };
+    crate::tracking::track(app, svc.pool.clone(), launched);
+    Ok(true)
+}
+
+#[tauri::command]
+/// Set or clear a game's launch override (strategy id or custom command).
+pub async fn set_launch_override(
+    service: State<'_, ServiceState>,
+    id: i32,
+    launch_override: Option<String>,
+) -> Result<(), String> {
+    let svc = service.lock().await;
+    svc.set_launch_override(id, launch_override).await
+}
+
+#[tauri::command]
+/// List the built-in launch strategy ids.
+pub async fn list_launch_strategies(
+    service: State<'_, ServiceState>,
+) -> Result<Vec<String>, String> {
+    let svc = service.lock().await;
+    Ok(svc.launch_strategies().into_iter().map(String::from).collect())
+}
+
 // ---- Stats ------------------------------------------------------------------
 
 #[tauri::command]
@@ -96,10 +231,95 @@ pub async fn search_rawg(
 // ---- Indexing ----------------------------------------------------------------
 
 #[tauri::command]
-/// Run launcher indexing for all supported sources.
+/// Run launcher indexing for all supported sources, emitting
+/// `index-progress` events as each source and game is processed.
 pub async fn index_now(
+    app: AppHandle,
+    service: State<'_, ServiceState>,
+) -> Result<IndexResult, String> {
+    // Scan without holding the service so other commands aren't blocked
+    let indexer = service.lock().await.library_indexer();
+    indexer.index_with_progress(&|event: IndexProgress| {
+        if let Err(e) = app.emit(INDEX_PROGRESS_EVENT, &event) {
+            log::warn!("Failed to emit index progress: {}", e);
+        }
+    })
+    .await
+}
+
+#[tauri::command]
+/// Dry-run all enabled indexers and return the changes they would make.
+pub async fn preview_index(
+    service: State<'_, ServiceState>,
+) -> Result<Vec<IndexChange>, String> {
+    let indexer = service.lock().await.library_indexer();
+    indexer.preview().await
+}
+
+#[tauri::command]
+/// Apply the preview entries the user selected.
+pub async fn apply_index_changes(
+    service: State<'_, ServiceState>,
+    changes: Vec<IndexChange>,
+) -> Result<IndexResult, String> {
+    let svc = service.lock().await;
+    svc.apply_index_changes(changes).await
+}
+
+#[tauri::command]
+/// Run launcher indexing for a single source id (e.g. `steam`).
+pub async fn index_source(
     service: State<'_, ServiceState>,
+    source: String,
 ) -> Result<IndexResult, String> {
+    // Scan without holding the service so other commands aren't blocked
+    let indexer = service.lock().await.source_indexer(&source)?;
+    indexer.index().await
+}
+
+#[tauri::command]
+/// List registered launcher indexers and their state.
+pub async fn list_indexers(
+    service: State<'_, ServiceState>,
+) -> Result<Vec<IndexerInfo>, String> {
     let svc = service.lock().await;
-    svc.index_all().await
+    Ok(svc.list_indexers())
+}
+
+#[tauri::command]
+/// Enable or disable a launcher indexer for full indexing runs.
+pub async fn set_indexer_enabled(
+    app: AppHandle,
+    service: State<'_, ServiceState>,
+    id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut svc = service.lock().await;
+    svc.set_indexer_enabled(&id, enabled).await?;
+    crate::watch::apply(&app, &svc);
+    Ok(())
+}
+
+// ---- Settings ----------------------------------------------------------------
+
+#[tauri::command]
+/// Return the current application settings.
+pub async fn get_settings(
+    service: State<'_, ServiceState>,
+) -> Result<AppSettings, String> {
+    let svc = service.lock().await;
+    Ok(svc.get_settings())
+}
+
+#[tauri::command]
+/// Persist and apply new application settings.
+pub async fn update_settings(
+    app: AppHandle,
+    service: State<'_, ServiceState>,
+    settings: AppSettings,
+) -> Result<(), String> {
+    let mut svc = service.lock().await;
+    svc.update_settings(settings).await?;
+    crate::watch::apply(&app, &svc);
+    Ok(())
 }
@@ -14,6 +14,8 @@ use tauri::Manager;
 use tokio::sync::Mutex;
 
 mod commands;
+mod tracking;
+mod watch;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 /// Start the Tauri desktop runtime.
@@ -21,7 +23,10 @@ mod commands;
 /// During setup, this function:
 /// - resolves app data paths for SQLite and icon storage
 /// - creates a SQLx pool and runs migrations
-/// - initializes `GameService` state
+/// - initializes `GameService` state and loads persisted settings
+/// - closes play sessions left open by a crash or shutdown
+/// - starts the launcher watcher when enabled in settings
+/// - starts the process monitor that records games started elsewhere
 /// - registers command handlers for frontend `invoke` calls
 pub fn run() {
     tauri::Builder::default()
@@ -46,6 +51,7 @@ pub fn run() {
 
             let db_path = app_data_dir.join("game_tracker.db");
             let icons_dir = app_data_dir.join("icons");
+            let cache_dir = app_data_dir.join("cache");
 
             let db_url = format!("sqlite:{}?mode=rwc", db_path.to_string_lossy());
 
@@ -53,6 +59,7 @@ pub fn run() {
             dotenv::dotenv().ok();
             let rawg_api_key = std::env::var("RAWG_API_KEY").unwrap_or_default();
 
+            app.manage(watch::WatcherState::default());
             let handle = app.handle().clone();
 
             // Spawn async setup on the Tokio runtime
@@ -68,9 +75,23 @@ pub fn run() {
                     .expect("Failed to run migrations");
 
                 let rawg_client = Arc::new(RawgClient::new(rawg_api_key));
-                let service = GameService::new(pool, rawg_client, icons_dir);
+                let mut service = GameService::new(pool, rawg_client, icons_dir);
+                service.set_cache_dir(cache_dir);
+                if let Err(e) = service.load_settings().await {
+                    log::warn!("Failed to load settings, using defaults: {}", e);
+                }
+                match service.close_stale_sessions().await {
+                    Ok(0) => {}
+                    Ok(n) => log::info!("Closed {} play sessions left open", n),
+                    Err(e) => log::warn!("Failed to close stale play sessions: {}", e),
+                }
+                if let Err(e) = service.fill_normalized_titles().await {
+                    log::warn!("Failed to fill normalized titles: {}", e);
+                }
+                watch::apply(&handle, &service);
                 let service_state: commands::ServiceState = Arc::new(Mutex::new(service));
 
+                tracking::start_monitor(handle.clone(), service_state.clone());
                 handle.manage(service_state);
             });
 
@@ -83,9 +104,28 @@ pub fn run() {
             commands::create_game,
             commands::update_game_status,
             commands::delete_game,
+            commands::exe_candidates,
+            commands::set_game_exe,
+            commands::get_game_sources,
+            commands::merge_games,
+            commands::split_game_source,
+            commands::list_play_sessions,
+            commands::log_play_session,
+            commands::update_play_session,
+            commands::delete_play_session,
+            commands::launch_game,
+            commands::set_launch_override,
+            commands::list_launch_strategies,
             commands::get_game_stats,
             commands::search_rawg,
             commands::index_now,
+            commands::index_source,
+            commands::preview_index,
+            commands::apply_index_changes,
+            commands::list_indexers,
+            commands::set_indexer_enabled,
+            commands::get_settings,
+            commands::update_settings,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
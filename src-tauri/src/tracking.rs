@@ -0,0 +1,98 @@
+//! Background tracking of play sessions.
+//!
+//! `launch_game` hands the started game to [`track`], which follows it on the
+//! async runtime without holding the service lock, and tells the frontend
+//! when a session starts and ends. [`start_monitor`] picks up games started
+//! elsewhere (Steam Big Picture, a desktop shortcut) from the process list.
+
+use std::time::Duration;
+
+use game_tracker_core::launch::{self, LaunchedGame};
+use game_tracker_core::models::PlaySession;
+use game_tracker_core::monitor::ProcessMonitor;
+use game_tracker_core::process;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::ServiceState;
+
+/// Event emitted when a launched game starts or stops running.
+pub const GAME_SESSION_EVENT: &str = "game-session";
+
+/// Event emitted when the process monitor sees a game start or stop.
+pub const NOW_PLAYING_EVENT: &str = "now-playing";
+
+/// Time between two process snapshots.
+const MONITOR_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Payload of [`GAME_SESSION_EVENT`].
+#[derive(Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum GameSessionEvent {
+    Started { game_id: i32 },
+    /// `session` is `None` when the run was too short to keep.
+    Ended { game_id: i32, session: Option<PlaySession> },
+}
+
+/// Follow `launched` until the game exits.
+pub fn track(app: AppHandle, pool: SqlitePool, launched: LaunchedGame) {
+    let game_id = launched.game_id;
+    emit(&app, GameSessionEvent::Started { game_id });
+
+    tauri::async_runtime::spawn(async move {
+        match launch::track_session(&pool, launched).await {
+            Ok(session) => emit(&app, GameSessionEvent::Ended { game_id, session }),
+            Err(e) => {
+                log::warn!("Tracking game {} failed: {}", game_id, e);
+                emit(&app, GameSessionEvent::Ended { game_id, session: None });
+            }
+        }
+    });
+}
+
+/// Poll running processes for the lifetime of the app, recording sessions
+/// while `monitor_processes` is enabled in settings.
+pub fn start_monitor(app: AppHandle, service: ServiceState) {
+    tauri::async_runtime::spawn(async move {
+        let mut monitor = ProcessMonitor::new(Duration::ZERO);
+        loop {
+            let (enabled, min_minutes, pool) = {
+                let svc = service.lock().await;
+                (
+                    svc.settings.monitor_processes,
+                    svc.settings.min_session_minutes,
+                    svc.pool.clone(),
+                )
+            };
+
+            let events = if enabled {
+                monitor.set_min_session(Duration::from_secs(u64::from(min_minutes) * 60));
+                let snapshot = tauri::async_runtime::spawn_blocking(process::snapshot)
+                    .await
+                    .unwrap_or_default();
+                monitor.poll(&pool, &snapshot, chrono::Utc::now()).await
+            } else {
+                monitor.stop_all(&pool).await
+            };
+            match events {
+                Ok(events) => {
+                    for event in events {
+                        if let Err(e) = app.emit(NOW_PLAYING_EVENT, &event) {
+                            log::warn!("Failed to emit now playing event: {}", e);
+                        }
+                    }
+                }
+                Err(e) => log::warn!("Process monitor failed: {}", e),
+            }
+
+            tokio::time::sleep(MONITOR_INTERVAL).await;
+        }
+    });
+}
+
+fn emit(app: &AppHandle, event: GameSessionEvent) {
+    if let Err(e) = app.emit(GAME_SESSION_EVENT, &event) {
+        log::warn!("Failed to emit game session event: {}", e);
+    }
+}
@@ -0,0 +1,144 @@
+//! Background launcher watcher wiring.
+//!
+//! Owns the core [`LibraryWatcher`] for the app and re-indexes the affected
+//! source whenever it reports a change: only the changed manifests when the
+//! indexer supports it, at most once per [`MIN_REINDEX_INTERVAL`] per source,
+//! and without holding the service lock while scanning.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use game_tracker_core::service::GameService;
+use game_tracker_core::watcher::LibraryWatcher;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::ServiceState;
+
+/// Running watcher, if enabled. Managed by Tauri.
+pub type WatcherState = Mutex<Option<LibraryWatcher>>;
+
+/// Event emitted after a watched source was re-indexed.
+pub const LIBRARY_CHANGED_EVENT: &str = "library-changed";
+
+/// Quiet period before a burst of launcher writes triggers re-indexing.
+const DEBOUNCE: Duration = Duration::from_secs(5);
+
+/// Shortest time between two re-index runs of one source. Steam rewrites a
+/// downloading app's manifest every few seconds; changes arriving sooner
+/// are collected and handled in the next run.
+const MIN_REINDEX_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Changes waiting for a source's next re-index run.
+#[derive(Default)]
+struct PendingChanges {
+    paths: BTreeSet<PathBuf>,
+    /// A task is already waiting to run (or running) for this source.
+    scheduled: bool,
+    last_run: Option<Instant>,
+}
+
+type Pending = Arc<Mutex<HashMap<String, PendingChanges>>>;
+
+/// Payload of [`LIBRARY_CHANGED_EVENT`].
+#[derive(Clone, Serialize)]
+struct LibraryChanged {
+    source: String,
+    result: game_tracker_core::service::IndexResult,
+}
+
+/// Start, restart or stop the watcher to match the service's settings and
+/// enabled indexers.
+pub fn apply(app: &AppHandle, service: &GameService) {
+    let state = app.state::<WatcherState>();
+    let mut slot = state.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Dropping the previous watcher stops it
+    *slot = None;
+
+    let targets = service.watch_targets();
+    if targets.is_empty() {
+        return;
+    }
+
+    let handle = app.clone();
+    let pending = Pending::default();
+    let started = LibraryWatcher::start(targets, DEBOUNCE, move |source, changed| {
+        schedule(&handle, &pending, source, changed);
+    });
+    match started {
+        Ok(watcher) => *slot = Some(watcher),
+        Err(e) => log::warn!("Failed to start launcher watcher: {}", e),
+    }
+}
+
+/// Queue `changed` for `source` and start its re-index task unless one is
+/// already waiting.
+fn schedule(app: &AppHandle, pending: &Pending, source: &str, changed: &[PathBuf]) {
+    let delay = {
+        let mut pending = pending.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = pending.entry(source.to_string()).or_default();
+        entry.paths.extend(changed.iter().cloned());
+        if entry.scheduled {
+            return;
+        }
+        entry.scheduled = true;
+        entry
+            .last_run
+            .map(|last| MIN_REINDEX_INTERVAL.saturating_sub(last.elapsed()))
+            .unwrap_or_default()
+    };
+
+    let app = app.clone();
+    let pending = pending.clone();
+    let source = source.to_string();
+    tauri::async_runtime::spawn(async move {
+        let mut delay = delay;
+        loop {
+            tokio::time::sleep(delay).await;
+            let changed = {
+                let mut pending = pending.lock().unwrap_or_else(|e| e.into_inner());
+                let entry = pending.entry(source.clone()).or_default();
+                std::mem::take(&mut entry.paths)
+            };
+            reindex(&app, &source, changed.into_iter().collect()).await;
+
+            // Changes that came in meanwhile wait out the interval
+            let mut pending = pending.lock().unwrap_or_else(|e| e.into_inner());
+            let entry = pending.entry(source.clone()).or_default();
+            entry.last_run = Some(Instant::now());
+            if entry.paths.is_empty() {
+                entry.scheduled = false;
+                return;
+            }
+            delay = MIN_REINDEX_INTERVAL;
+        }
+    });
+}
+
+async fn reindex(app: &AppHandle, source: &str, changed: Vec<PathBuf>) {
+    let Some(service) = app.try_state::<ServiceState>() else {
+        return;
+    };
+    // Only look the indexer up under the lock; the scan runs without it
+    let indexer = service.lock().await.source_indexer(source);
+    let result = match indexer {
+        Ok(indexer) => indexer.index_changes(changed).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(result) => {
+            log::info!("Re-indexed {} after launcher change", source);
+            let change = LibraryChanged {
+                source: source.to_string(),
+                result,
+            };
+            if let Err(e) = app.emit(LIBRARY_CHANGED_EVENT, change) {
+                log::warn!("Failed to emit library change: {}", e);
+            }
+        }
+        Err(e) => log::warn!("Re-indexing {} failed: {}", source, e),
+    }
+}
@@ -1,15 +1,44 @@
-import { type Game, updateGameStatus, deleteGame } from "../hooks/useBackend";
+import { useState } from "react";
+import {
+  type ExeCandidate,
+  type Game,
+  type GameSource,
+  updateGameStatus,
+  deleteGame,
+  exeCandidates,
+  setGameExe,
+  getGameSources,
+  mergeGames,
+  splitGameSource,
+  launchGame,
+  setLaunchOverride,
+} from "../hooks/useBackend";
+import { SessionLog } from "./SessionLog";
 
 /** Props for rendering and mutating a single game entry. */
 interface GameCardProps {
   game: Game;
+  /** Other library games, offered as merge targets. */
+  games: Game[];
+  /** Launch strategy ids offered as overrides. */
+  launchStrategies: string[];
   onUpdate: () => void;
 }
 
 const STATUSES = ["Backlog", "Playing", "Completed", "Wishlist"];
 
 /** Card UI for one game with status update and delete actions. */
-export function GameCard({ game, onUpdate }: GameCardProps) {
+export function GameCard({
+  game,
+  games,
+  launchStrategies,
+  onUpdate,
+}: GameCardProps) {
+  const [candidates, setCandidates] = useState<ExeCandidate[] | null>(null);
+  const [sources, setSources] = useState<GameSource[] | null>(null);
+  const [showSessions, setShowSessions] = useState(false);
+  const [launchWith, setLaunchWith] = useState(game.launch_override ?? "");
+
   const handleStatusChange = async (e: React.ChangeEvent<HTMLSelectElement>) => {
     await updateGameStatus(game.id, e.target.value);
     onUpdate();
@@ -22,6 +51,61 @@ export function GameCard({ game, onUpdate }: GameCardProps) {
     }
   };
 
+  const handleShowExes = async () => {
+    const detection = await exeCandidates(game.id);
+    setCandidates(
+      detection.best ? [detection.best, ...detection.alternatives] : []
+    );
+  };
+
+  const handleExeChange = async (e: React.ChangeEvent<HTMLSelectElement>) => {
+    await setGameExe(game.id, e.target.value);
+    setCandidates(null);
+    onUpdate();
+  };
+
+  const handleLaunch = async () => {
+    try {
+      const tracked = await launchGame(game.id);
+      if (!tracked) {
+        alert(
+          `${game.title} was started through its launcher, but play time ` +
+            "can't be tracked for it on this system. Log the session by hand " +
+            "when you're done."
+        );
+      }
+    } catch (e) {
+      alert(String(e));
+    }
+  };
+
+  const handleLaunchWith = async () => {
+    if (launchWith.trim() !== (game.launch_override ?? "")) {
+      await setLaunchOverride(game.id, launchWith.trim() || null);
+      onUpdate();
+    }
+  };
+
+  const handleShowSources = async () => {
+    setSources(await getGameSources(game.id));
+  };
+
+  const handleSplit = async (source: GameSource) => {
+    if (confirm(`Split ${source.source} copy into a separate game?`)) {
+      await splitGameSource(source.id);
+      setSources(null);
+      onUpdate();
+    }
+  };
+
+  const handleMerge = async (e: React.ChangeEvent<HTMLSelectElement>) => {
+    const target = games.find((g) => g.id === Number(e.target.value));
+    if (target && confirm(`Merge "${game.title}" into "${target.title}"?`)) {
+      await mergeGames(target.id, game.id);
+      onUpdate();
+    }
+  };
+
   // Prefer cover art, then extracted icon path, otherwise show placeholder.
   const coverSrc = game.cover_url || game.icon_path || null;
 
@@ -40,6 +124,10 @@ export function GameCard({ game, onUpdate }: GameCardProps) {
         <span className="source-badge">{game.source}</span>
       )}
 
+      {!game.installed && (
+        <span className="uninstalled-badge">Not installed</span>
+      )}
+
       <select
         className="status-select"
         value={game.status}
@@ -58,6 +146,88 @@ export function GameCard({ game, onUpdate }: GameCardProps) {
 
       <p className="game-card-playtime">{game.playtime_hours}h played</p>
 
+      {game.installed && (
+        <button className="btn-primary" onClick={handleLaunch}>
+          Play
+        </button>
+      )}
+      <input
+        className="launch-override"
+        list={`launch-strategies-${game.id}`}
+        placeholder="Launch with (default)"
+        value={launchWith}
+        onChange={(e) => setLaunchWith(e.target.value)}
+        onBlur={handleLaunchWith}
+      />
+      <datalist id={`launch-strategies-${game.id}`}>
+        {launchStrategies.map((id) => (
+          <option key={id} value={id} />
+        ))}
+      </datalist>
+
+      <button
+        className="btn-secondary"
+        onClick={() => setShowSessions(!showSessions)}
+      >
+        {showSessions ? "Hide sessions" : "Sessions"}
+      </button>
+      {showSessions && <SessionLog gameId={game.id} onChange={onUpdate} />}
+
+      {game.installed && game.install_path &&
+        (candidates === null ? (
+          <button className="btn-secondary" onClick={handleShowExes}>
+            Change executable
+          </button>
+        ) : (
+          <select
+            className="status-select"
+            value={game.exe_path ?? ""}
+            onChange={handleExeChange}
+          >
+            <option value="" disabled>
+              {candidates.length ? "Pick executable..." : "No executables found"}
+            </option>
+            {candidates.map((c) => (
+              <option key={c.path} value={c.path}>
+                {c.path}
+              </option>
+            ))}
+          </select>
+        ))}
+
+      {sources === null ? (
+        <button className="btn-secondary" onClick={handleShowSources}>
+          Sources
+        </button>
+      ) : (
+        <ul className="game-card-sources">
+          {sources.map((s) => (
+            <li key={s.id}>
+              <span className="source-badge">{s.source}</span>
+              {!s.installed && " (not installed)"}
+              {sources.length > 1 && (
+                <button className="btn-secondary" onClick={() => handleSplit(s)}>
+                  Split
+                </button>
+              )}
+            </li>
+          ))}
+        </ul>
+      )}
+
+      <select className="status-select" value="" onChange={handleMerge}>
+        <option value="" disabled>
+          Merge into...
+        </option>
+        {games
+          .filter((g) => g.id !== game.id)
+          .map((g) => (
+            <option key={g.id} value={g.id}>
+              {g.title} ({g.platform})
+            </option>
+          ))}
+      </select>
+
       <button className="delete-btn" onClick={handleDelete}>
         Delete
       </button>
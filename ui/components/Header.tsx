@@ -1,3 +1,6 @@
+import { useEffect, useState } from "react";
+import { onNowPlaying } from "../hooks/useBackend";
+
 /** Props for the app header navigation. */
 interface HeaderProps {
   currentView: string;
@@ -6,6 +9,28 @@ interface HeaderProps {
 
 /** App header with primary view navigation controls. */
 export function Header({ currentView, onNavigate }: HeaderProps) {
+  // Games the process monitor currently sees running, by id.
+  const [nowPlaying, setNowPlaying] = useState<Record<number, string>>({});
+
+  useEffect(() => {
+    const unlisten = onNowPlaying((event) =>
+      setNowPlaying((playing) => {
+        const next = { ...playing };
+        if (event.kind === "now_playing") {
+          next[event.game_id] = event.title;
+        } else {
+          delete next[event.game_id];
+        }
+        return next;
+      })
+    );
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  const playing = Object.values(nowPlaying);
+
   return (
     <header className="header">
       <h1 className="header-title">Game Tracker</h1>
@@ -29,6 +54,9 @@ export function Header({ currentView, onNavigate }: HeaderProps) {
           Index Games
         </button>
       </nav>
+      {playing.length > 0 && (
+        <span className="now-playing">Now playing: {playing.join(", ")}</span>
+      )}
     </header>
   );
 }
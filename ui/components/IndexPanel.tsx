@@ -1,18 +1,108 @@
-import { useState } from "react";
-import { type IndexResult, indexNow } from "../hooks/useBackend";
+import { useCallback, useEffect, useState } from "react";
+import {
+  type AppSettings,
+  type IndexChange,
+  type IndexProgress,
+  type IndexResult,
+  type IndexerInfo,
+  type RomFolderRule,
+  applyIndexChanges,
+  getSettings,
+  indexNow,
+  indexSource,
+  listIndexers,
+  onIndexProgress,
+  previewIndex,
+  setIndexerEnabled,
+  updateSettings,
+} from "../hooks/useBackend";
+import { IndexPreview } from "./IndexPreview";
 
-/** Controls for running Steam/Epic discovery and showing summary results. */
+/** Live counters built from `index-progress` events. */
+interface ProgressState {
+  current: string | null;
+  discovered: number;
+  upserted: number;
+  failures: { title: string; error: string }[];
+}
+
+const EMPTY_PROGRESS: ProgressState = {
+  current: null,
+  discovered: 0,
+  upserted: 0,
+  failures: [],
+};
+
+function applyProgress(state: ProgressState, event: IndexProgress): ProgressState {
+  switch (event.kind) {
+    case "source_started":
+      return { ...state, current: event.display_name };
+    case "game_discovered":
+      return { ...state, discovered: state.discovered + 1 };
+    case "game_upserted":
+      return { ...state, upserted: state.upserted + 1 };
+    case "game_failed":
+      return {
+        ...state,
+        failures: [...state.failures, { title: event.title, error: event.error }],
+      };
+    case "source_failed":
+      return {
+        ...state,
+        failures: [...state.failures, { title: event.source, error: event.error }],
+      };
+    case "source_finished":
+      return { ...state, current: null };
+  }
+}
+
+/** Draft ROM folder rule; extensions are typed as a comma-separated list. */
+const EMPTY_RULE = { folder: "", system: "", extensions: "", emulator: "" };
+
+/** Controls for running launcher discovery and showing summary results. */
 export function IndexPanel() {
   const [running, setRunning] = useState(false);
   const [result, setResult] = useState<IndexResult | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [indexers, setIndexers] = useState<IndexerInfo[]>([]);
+  const [preview, setPreview] = useState<IndexChange[] | null>(null);
+  const [progress, setProgress] = useState<ProgressState>(EMPTY_PROGRESS);
+  const [settings, setSettings] = useState<AppSettings | null>(null);
+  const [newFolder, setNewFolder] = useState("");
+  const [newDat, setNewDat] = useState("");
+  const [newRule, setNewRule] = useState(EMPTY_RULE);
+
+  const refreshIndexers = useCallback(async () => {
+    try {
+      setIndexers(await listIndexers());
+    } catch (e) {
+      console.error("Failed to load indexers:", e);
+    }
+  }, []);
+
+  useEffect(() => {
+    refreshIndexers();
+    getSettings()
+      .then(setSettings)
+      .catch((e) => console.error("Failed to load settings:", e));
+  }, [refreshIndexers]);
 
-  const handleIndex = async () => {
+  useEffect(() => {
+    const unlisten = onIndexProgress((event) =>
+      setProgress((state) => applyProgress(state, event))
+    );
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  const runIndex = async (run: () => Promise<IndexResult>) => {
     setRunning(true);
     setError(null);
     setResult(null);
+    setProgress(EMPTY_PROGRESS);
     try {
-      const res = await indexNow();
+      const res = await run();
       setResult(res);
     } catch (e) {
       setError(String(e));
@@ -21,28 +111,185 @@ export function IndexPanel() {
     }
   };
 
+  const handlePreview = async () => {
+    setRunning(true);
+    setError(null);
+    setResult(null);
+    try {
+      setPreview(await previewIndex());
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const handleApply = async (selected: IndexChange[]) => {
+    setPreview(null);
+    await runIndex(() => applyIndexChanges(selected));
+  };
+
+  const saveSettings = async (next: AppSettings) => {
+    try {
+      await updateSettings(next);
+      setSettings(next);
+      await refreshIndexers();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleWatchToggle = () => {
+    if (!settings) return;
+    saveSettings({ ...settings, watch_launchers: !settings.watch_launchers });
+  };
+
+  const handleMonitorToggle = () => {
+    if (!settings) return;
+    saveSettings({ ...settings, monitor_processes: !settings.monitor_processes });
+  };
+
+  const handleMinSessionChange = (value: string) => {
+    const minutes = parseInt(value, 10);
+    if (!settings || Number.isNaN(minutes) || minutes < 0) return;
+    saveSettings({ ...settings, min_session_minutes: minutes });
+  };
+
+  const handleAddFolder = () => {
+    const folder = newFolder.trim();
+    if (!settings || !folder || settings.game_folders.includes(folder)) return;
+    saveSettings({ ...settings, game_folders: [...settings.game_folders, folder] });
+    setNewFolder("");
+  };
+
+  const handleRemoveFolder = (folder: string) => {
+    if (!settings) return;
+    saveSettings({
+      ...settings,
+      game_folders: settings.game_folders.filter((f) => f !== folder),
+    });
+  };
+
+  const handleAddDat = () => {
+    const dat = newDat.trim();
+    if (!settings || !dat || settings.dat_files.includes(dat)) return;
+    saveSettings({ ...settings, dat_files: [...settings.dat_files, dat] });
+    setNewDat("");
+  };
+
+  const handleRemoveDat = (dat: string) => {
+    if (!settings) return;
+    saveSettings({
+      ...settings,
+      dat_files: settings.dat_files.filter((d) => d !== dat),
+    });
+  };
+
+  const handleAddRule = () => {
+    const extensions = newRule.extensions
+      .split(",")
+      .map((e) => e.trim().replace(/^\./, ""))
+      .filter(Boolean);
+    const folder = newRule.folder.trim();
+    if (!settings || !folder || !newRule.system.trim() || extensions.length === 0) return;
+    const rule: RomFolderRule = {
+      folder,
+      system: newRule.system.trim(),
+      extensions,
+      emulator: newRule.emulator.trim(),
+    };
+    saveSettings({ ...settings, rom_folders: [...settings.rom_folders, rule] });
+    setNewRule(EMPTY_RULE);
+  };
+
+  const handleRemoveRule = (index: number) => {
+    if (!settings) return;
+    saveSettings({
+      ...settings,
+      rom_folders: settings.rom_folders.filter((_, i) => i !== index),
+    });
+  };
+
+  const handleToggle = async (indexer: IndexerInfo) => {
+    try {
+      await setIndexerEnabled(indexer.id, !indexer.enabled);
+      await refreshIndexers();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
   return (
     <div className="index-panel">
       <h2>Game Indexing</h2>
       <p className="index-description">
-        Automatically discover installed games from Steam and Epic Games
-        Launcher. Discovered games are added to your library with "Backlog"
-        status. Existing entries are updated if the install path changed.
+        Automatically discover installed games from your launchers. Discovered
+        games are added to your library with "Backlog" status. Existing
+        entries are updated if the install path changed.
       </p>
 
       <button
         className="btn-primary index-btn"
-        onClick={handleIndex}
+        onClick={() => runIndex(indexNow)}
         disabled={running}
       >
         {running ? "Scanning..." : "Scan Now"}
       </button>
+      <button
+        className="btn-secondary index-btn"
+        onClick={handlePreview}
+        disabled={running}
+      >
+        Preview changes
+      </button>
+
+      {running && progress.discovered > 0 && (
+        <div className="index-progress">
+          <p>
+            {progress.current && <>Scanning {progress.current}… </>}
+            <strong>{progress.upserted}</strong> / {progress.discovered} games
+            saved
+          </p>
+        </div>
+      )}
+
+      {progress.failures.length > 0 && (
+        <div className="index-result error">
+          <ul>
+            {progress.failures.map((f, i) => (
+              <li key={i}>
+                <strong>{f.title}</strong>: {f.error}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+
+      {preview && (
+        <IndexPreview
+          changes={preview}
+          onApply={handleApply}
+          onCancel={() => setPreview(null)}
+        />
+      )}
 
       {result && (
         <div className="index-result success">
           <p>
             Discovered <strong>{result.discovered}</strong> games,{" "}
             <strong>{result.upserted}</strong> added/updated in library.
+            {result.uninstalled > 0 && (
+              <>
+                {" "}
+                <strong>{result.uninstalled}</strong> no longer installed.
+              </>
+            )}
+            {result.failed > 0 && (
+              <>
+                {" "}
+                <strong>{result.failed}</strong> could not be saved.
+              </>
+            )}
           </p>
         </div>
       )}
@@ -53,18 +300,203 @@ export function IndexPanel() {
         </div>
       )}
 
+      {settings && (
+        <label className="index-watch">
+          <input
+            type="checkbox"
+            checked={settings.watch_launchers}
+            onChange={handleWatchToggle}
+          />{" "}
+          Re-index automatically when launchers install or remove games
+        </label>
+      )}
+
+      {settings && (
+        <div className="index-monitor">
+          <label>
+            <input
+              type="checkbox"
+              checked={settings.monitor_processes}
+              onChange={handleMonitorToggle}
+            />{" "}
+            Record sessions for games started outside the tracker
+          </label>
+          <label>
+            Ignore runs shorter than{" "}
+            <input
+              type="number"
+              min={0}
+              value={settings.min_session_minutes}
+              disabled={!settings.monitor_processes}
+              onChange={(e) => handleMinSessionChange(e.target.value)}
+            />{" "}
+            minutes
+          </label>
+        </div>
+      )}
+
       <div className="index-sources">
-        <h3>Supported Sources</h3>
+        <h3>Sources</h3>
         <ul>
-          <li>
-            <strong>Steam</strong> — Reads library folders and app manifests
-          </li>
-          <li>
-            <strong>Epic Games</strong> — Parses install manifests from
-            ProgramData
-          </li>
+          {indexers.map((indexer) => (
+            <li key={indexer.id} className="index-source">
+              <label>
+                <input
+                  type="checkbox"
+                  checked={indexer.enabled}
+                  onChange={() => handleToggle(indexer)}
+                  disabled={running}
+                />{" "}
+                <strong>{indexer.display_name}</strong>
+                {!indexer.available && " — not detected"}
+              </label>
+              <button
+                className="btn-secondary"
+                onClick={() => runIndex(() => indexSource(indexer.id))}
+                disabled={running}
+              >
+                Scan
+              </button>
+            </li>
+          ))}
         </ul>
       </div>
+
+      {settings && (
+        <div className="index-sources">
+          <h3>Game folders</h3>
+          <p className="index-description">
+            Each subfolder of these folders is added as a game (for DRM-free
+            and portable installs).
+          </p>
+          <ul>
+            {settings.game_folders.map((folder) => (
+              <li key={folder} className="index-source">
+                <span>{folder}</span>
+                <button
+                  className="btn-secondary"
+                  onClick={() => handleRemoveFolder(folder)}
+                  disabled={running}
+                >
+                  Remove
+                </button>
+              </li>
+            ))}
+          </ul>
+          <div className="index-source">
+            <input
+              type="text"
+              className="search-input"
+              placeholder="/home/me/Games or D:\Games"
+              value={newFolder}
+              onChange={(e) => setNewFolder(e.target.value)}
+            />
+            <button
+              className="btn-secondary"
+              onClick={handleAddFolder}
+              disabled={running}
+            >
+              Add folder
+            </button>
+          </div>
+
+          <h3>ROM DAT files</h3>
+          <p className="index-description">
+            No-Intro or Redump DAT files (Logiqx XML). Matching ROMs get their
+            canonical title and system.
+          </p>
+          <ul>
+            {settings.dat_files.map((dat) => (
+              <li key={dat} className="index-source">
+                <span>{dat}</span>
+                <button
+                  className="btn-secondary"
+                  onClick={() => handleRemoveDat(dat)}
+                  disabled={running}
+                >
+                  Remove
+                </button>
+              </li>
+            ))}
+          </ul>
+          <div className="index-source">
+            <input
+              type="text"
+              className="search-input"
+              placeholder="/home/me/dats/Nintendo - Super Nintendo Entertainment System.dat"
+              value={newDat}
+              onChange={(e) => setNewDat(e.target.value)}
+            />
+            <button
+              className="btn-secondary"
+              onClick={handleAddDat}
+              disabled={running}
+            >
+              Add DAT
+            </button>
+          </div>
+
+          <h3>Emulator ROM folders</h3>
+          <p className="index-description">
+            ROMs in each folder are added for one system. Multi-disc sets
+            (.m3u, .cue/.bin, "(Disc N)") become a single game.
+          </p>
+          <ul>
+            {settings.rom_folders.map((rule, index) => (
+              <li key={`${rule.folder}-${index}`} className="index-source">
+                <span>
+                  {rule.folder} — {rule.system} ({rule.extensions.join(", ")})
+                  {rule.emulator && ` via ${rule.emulator}`}
+                </span>
+                <button
+                  className="btn-secondary"
+                  onClick={() => handleRemoveRule(index)}
+                  disabled={running}
+                >
+                  Remove
+                </button>
+              </li>
+            ))}
+          </ul>
+          <div className="index-source">
+            <input
+              type="text"
+              className="search-input"
+              placeholder="/home/me/roms/gc"
+              value={newRule.folder}
+              onChange={(e) => setNewRule({ ...newRule, folder: e.target.value })}
+            />
+            <input
+              type="text"
+              className="search-input"
+              placeholder="GameCube"
+              value={newRule.system}
+              onChange={(e) => setNewRule({ ...newRule, system: e.target.value })}
+            />
+            <input
+              type="text"
+              className="search-input"
+              placeholder="iso, rvz, gcz"
+              value={newRule.extensions}
+              onChange={(e) => setNewRule({ ...newRule, extensions: e.target.value })}
+            />
+            <input
+              type="text"
+              className="search-input"
+              placeholder="dolphin-emu"
+              value={newRule.emulator}
+              onChange={(e) => setNewRule({ ...newRule, emulator: e.target.value })}
+            />
+            <button
+              className="btn-secondary"
+              onClick={handleAddRule}
+              disabled={running}
+            >
+              Add ROM folder
+            </button>
+          </div>
+        </div>
+      )}
     </div>
   );
 }
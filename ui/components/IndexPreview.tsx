@@ -0,0 +1,87 @@
+import { useState } from "react";
+import { type IndexChange, type IndexResult } from "../hooks/useBackend";
+
+/** Props for reviewing a dry-run index preview. */
+interface IndexPreviewProps {
+  changes: IndexChange[];
+  onApply: (selected: IndexChange[]) => Promise<IndexResult | void>;
+  onCancel: () => void;
+}
+
+const KIND_LABELS: Record<IndexChange["kind"], string> = {
+  new: "New",
+  attach: "Add source",
+  updated: "Updated",
+  vanished: "No longer installed",
+};
+
+function describe(change: IndexChange): string {
+  const title = change.discovered?.title ?? change.existing?.title ?? "";
+  switch (change.kind) {
+    case "updated":
+      return `${change.existing?.title} (${change.fields.join(", ")})`;
+    case "attach":
+      return `${change.discovered?.source} → ${change.existing?.title}`;
+    case "vanished":
+      return `${title} (${change.existing?.source})`;
+    default:
+      return title;
+  }
+}
+
+/** Checklist of pending index changes, all selected by default. */
+export function IndexPreview({ changes, onApply, onCancel }: IndexPreviewProps) {
+  const [selected, setSelected] = useState<Set<number>>(
+    () => new Set(changes.map((_, i) => i))
+  );
+
+  const toggle = (i: number) => {
+    const next = new Set(selected);
+    if (next.has(i)) {
+      next.delete(i);
+    } else {
+      next.add(i);
+    }
+    setSelected(next);
+  };
+
+  if (changes.length === 0) {
+    return (
+      <div className="index-preview">
+        <p>Library is up to date — nothing would change.</p>
+        <button className="btn-secondary" onClick={onCancel}>
+          Close
+        </button>
+      </div>
+    );
+  }
+
+  return (
+    <div className="index-preview">
+      <ul>
+        {changes.map((change, i) => (
+          <li key={i} className={`index-change ${change.kind}`}>
+            <label>
+              <input
+                type="checkbox"
+                checked={selected.has(i)}
+                onChange={() => toggle(i)}
+              />{" "}
+              <strong>{KIND_LABELS[change.kind]}:</strong> {describe(change)}
+            </label>
+          </li>
+        ))}
+      </ul>
+      <button
+        className="btn-primary"
+        onClick={() => onApply(changes.filter((_, i) => selected.has(i)))}
+        disabled={selected.size === 0}
+      >
+        Apply selected
+      </button>
+      <button className="btn-secondary" onClick={onCancel}>
+        Cancel
+      </button>
+    </div>
+  );
+}
@@ -1,5 +1,14 @@
 import { useEffect, useState, useCallback } from "react";
-import { type Game, listGames, searchGames, filterGames } from "../hooks/useBackend";
+import {
+  type Game,
+  listGames,
+  searchGames,
+  filterGames,
+  onLibraryChanged,
+  onGameSession,
+  onNowPlaying,
+  listLaunchStrategies,
+} from "../hooks/useBackend";
 import { GameCard } from "./GameCard";
 import { AddGameModal } from "./AddGameModal";
 
@@ -16,6 +25,7 @@ export function Library() {
   const [statusFilter, setStatusFilter] = useState("");
   const [showAddModal, setShowAddModal] = useState(false);
   const [loading, setLoading] = useState(true);
+  const [launchStrategies, setLaunchStrategies] = useState<string[]>([]);
 
   const refresh = useCallback(async () => {
     setLoading(true);
@@ -40,6 +50,36 @@ export function Library() {
     return () => clearTimeout(timer);
   }, [refresh]);
 
+  useEffect(() => {
+    // The launcher watcher re-indexes in the background; pick up its changes.
+    const unlisten = onLibraryChanged(() => refresh());
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, [refresh]);
+
+  useEffect(() => {
+    listLaunchStrategies()
+      .then(setLaunchStrategies)
+      .catch((e) => console.error("Failed to load launch strategies:", e));
+  }, []);
+
+  useEffect(() => {
+    // Launched games change status and playtime as they start and exit.
+    const unlisten = onGameSession(() => refresh());
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, [refresh]);
+
+  useEffect(() => {
+    // So do games the process monitor picks up.
+    const unlisten = onNowPlaying(() => refresh());
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, [refresh]);
+
   return (
     <div className="library">
       <div className="controls">
@@ -84,7 +124,13 @@ export function Library() {
       ) : (
         <div className="game-grid">
           {games.map((game) => (
-            <GameCard key={game.id} game={game} onUpdate={refresh} />
+            <GameCard
+              key={game.id}
+              game={game}
+              games={games}
+              launchStrategies={launchStrategies}
+              onUpdate={refresh}
+            />
           ))}
         </div>
       )}
@@ -0,0 +1,152 @@
+import { useEffect, useState } from "react";
+import {
+  type PlaySession,
+  listPlaySessions,
+  logPlaySession,
+  updatePlaySession,
+  deletePlaySession,
+} from "../hooks/useBackend";
+
+/** Props for the play session list of one game. */
+interface SessionLogProps {
+  gameId: number;
+  /** Called after any change, since the game's playtime follows sessions. */
+  onChange: () => void;
+}
+
+/** Draft values of the add/edit form. */
+interface SessionDraft {
+  date: string;
+  minutes: string;
+  notes: string;
+}
+
+const EMPTY_DRAFT: SessionDraft = { date: "", minutes: "", notes: "" };
+
+/** `YYYY-MM-DD HH:MM:SS` to the `datetime-local` input format. */
+function toInputDate(date: string): string {
+  return date.slice(0, 16).replace(" ", "T");
+}
+
+/** List, log, edit and delete play sessions for one game. */
+export function SessionLog({ gameId, onChange }: SessionLogProps) {
+  const [sessions, setSessions] = useState<PlaySession[]>([]);
+  const [draft, setDraft] = useState<SessionDraft>(EMPTY_DRAFT);
+  const [editing, setEditing] = useState<number | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = async () => {
+    setSessions(await listPlaySessions(gameId));
+  };
+
+  useEffect(() => {
+    refresh();
+  }, [gameId]);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    const input = {
+      session_date: draft.date || null,
+      duration_minutes: Number(draft.minutes),
+      notes: draft.notes || null,
+    };
+    try {
+      if (editing === null) {
+        await logPlaySession(gameId, input);
+      } else {
+        await updatePlaySession(editing, input);
+      }
+      setDraft(EMPTY_DRAFT);
+      setEditing(null);
+      setError(null);
+      await refresh();
+      onChange();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleEdit = (session: PlaySession) => {
+    setEditing(session.id);
+    setDraft({
+      date: toInputDate(session.session_date),
+      minutes: String(session.duration_minutes),
+      notes: session.notes ?? "",
+    });
+  };
+
+  const handleDelete = async (session: PlaySession) => {
+    if (confirm(`Delete the ${session.duration_minutes} min session?`)) {
+      await deletePlaySession(session.id);
+      await refresh();
+      onChange();
+    }
+  };
+
+  return (
+    <div className="session-log">
+      <form onSubmit={handleSubmit}>
+        <input
+          type="datetime-local"
+          value={draft.date}
+          onChange={(e) => setDraft({ ...draft, date: e.target.value })}
+        />
+        <input
+          type="number"
+          min={1}
+          placeholder="Minutes"
+          value={draft.minutes}
+          onChange={(e) => setDraft({ ...draft, minutes: e.target.value })}
+          required
+        />
+        <input
+          type="text"
+          placeholder="Notes"
+          value={draft.notes}
+          onChange={(e) => setDraft({ ...draft, notes: e.target.value })}
+        />
+        <button className="btn-primary" type="submit">
+          {editing === null ? "Log session" : "Save"}
+        </button>
+        {editing !== null && (
+          <button
+            className="btn-secondary"
+            type="button"
+            onClick={() => {
+              setEditing(null);
+              setDraft(EMPTY_DRAFT);
+            }}
+          >
+            Cancel
+          </button>
+        )}
+      </form>
+      {error && <p className="session-error">{error}</p>}
+
+      <ul>
+        {sessions.map((s) => (
+          <li key={s.id}>
+            {s.session_date} — {s.duration_minutes} min
+            {s.notes && ` — ${s.notes}`}
+            {s.active && " (running)"}
+            {s.inferred && (
+              <span
+                className="session-inferred"
+                title="Reported by the launcher, not seen by the tracker"
+              >
+                {" "}
+                (from launcher)
+              </span>
+            )}
+            <button className="btn-secondary" onClick={() => handleEdit(s)}>
+              Edit
+            </button>
+            <button className="btn-secondary" onClick={() => handleDelete(s)}>
+              Delete
+            </button>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
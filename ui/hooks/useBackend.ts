@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
 // ---- Types (match Rust DTOs) ----
 
@@ -22,6 +23,44 @@ export interface Game {
   source: string | null;
   source_id: string | null;
   install_path: string | null;
+  exe_path_locked: boolean;
+  installed: boolean;
+  uninstalled_at: string | null;
+  /** Launch strategy id to try first, or a custom command line / URI. */
+  launch_override: string | null;
+}
+
+/** One launcher that owns or installed a game. */
+export interface GameSource {
+  id: number;
+  game_id: number;
+  source: string;
+  source_id: string;
+  install_path: string | null;
+  exe_path: string | null;
+  installed: boolean;
+  playtime_hours: number;
+  added_date: string;
+}
+
+/** One logged play session. */
+export interface PlaySession {
+  id: number;
+  game_id: number;
+  session_date: string;
+  duration_minutes: number;
+  notes: string | null;
+  /** True while a launched game is still running. */
+  active: boolean;
+  /** Derived from launcher playtime growth; dated at the launcher's last-played time. */
+  inferred: boolean;
+}
+
+/** Payload for logging or editing a play session. */
+export interface PlaySessionInput {
+  session_date?: string | null;
+  duration_minutes: number;
+  notes?: string | null;
 }
 
 /** Payload used to create a new game entry. */
@@ -46,6 +85,18 @@ export interface RawgGame {
   description_raw: string | null;
 }
 
+/** Executable found under a game's install directory. */
+export interface ExeCandidate {
+  path: string;
+  score: number;
+}
+
+/** Ranked executable candidates for one game. */
+export interface ExeDetection {
+  best: ExeCandidate | null;
+  alternatives: ExeCandidate[];
+}
+
 /** Aggregated library metrics for the stats screen. */
 export interface GameStats {
   total_games: number;
@@ -58,6 +109,80 @@ export interface GameStats {
 export interface IndexResult {
   discovered: number;
   upserted: number;
+  uninstalled: number;
+  failed: number;
+}
+
+/** Kind of change an index run would make. */
+export type IndexChangeKind = "new" | "attach" | "updated" | "vanished";
+
+/** Game as reported by a launcher indexer. */
+export interface DiscoveredGame {
+  title: string;
+  platform: string;
+  exe_path: string | null;
+  install_path: string | null;
+  source: string;
+  source_id: string;
+  playtime_hours: number | null;
+  last_played: string | null;
+}
+
+/** One entry of a dry-run index preview. */
+export interface IndexChange {
+  kind: IndexChangeKind;
+  discovered: DiscoveredGame | null;
+  existing: Game | null;
+  fields: string[];
+}
+
+/** Emitted as the `game-session` event when a launched game starts or exits. */
+export type GameSessionEvent =
+  | { kind: "started"; game_id: number }
+  | { kind: "ended"; game_id: number; session: PlaySession | null };
+
+/** Emitted as the `now-playing` event when the process monitor sees a game start or stop. */
+export type NowPlayingEvent =
+  | { kind: "now_playing"; game_id: number; title: string }
+  | { kind: "stopped"; game_id: number; title: string; session: PlaySession };
+
+/** Progress update emitted as the `index-progress` event during `indexNow`. */
+export type IndexProgress =
+  | { kind: "source_started"; source: string; display_name: string }
+  | { kind: "game_discovered"; source: string; title: string }
+  | { kind: "game_upserted"; source: string; title: string; game_id: number }
+  | { kind: "game_failed"; source: string; title: string; error: string }
+  | { kind: "source_finished"; source: string; result: IndexResult }
+  | { kind: "source_failed"; source: string; error: string };
+
+/** Registered launcher indexer and its current state. */
+export interface IndexerInfo {
+  id: string;
+  display_name: string;
+  enabled: boolean;
+  available: boolean;
+}
+
+/** User-editable application settings. */
+export interface AppSettings {
+  disabled_indexers: string[];
+  steam_account: string | null;
+  epic_manifest_dirs: string[];
+  watch_launchers: boolean;
+  game_folders: string[];
+  dat_files: string[];
+  rom_folders: RomFolderRule[];
+  battlenet_titles: Record<string, string>;
+  monitor_processes: boolean;
+  min_session_minutes: number;
+}
+
+/** ROM folder scanned for one system and played with one emulator. */
+export interface RomFolderRule {
+  folder: string;
+  system: string;
+  extensions: string[];
+  emulator: string;
 }
 
 // ---- API functions ----
@@ -95,6 +220,97 @@ export async function deleteGame(id: number): Promise<void> {
   return invoke("delete_game", { id });
 }
 
+/** Rank executables under a game's install directory. */
+export async function exeCandidates(id: number): Promise<ExeDetection> {
+  return invoke<ExeDetection>("exe_candidates", { id });
+}
+
+/** Pick the executable for a game, overriding automatic detection. */
+export async function setGameExe(id: number, exePath: string): Promise<void> {
+  return invoke("set_game_exe", { id, exePath });
+}
+
+/** List the launcher sources attached to a game. */
+export async function getGameSources(id: number): Promise<GameSource[]> {
+  return invoke<GameSource[]>("get_game_sources", { id });
+}
+
+/** Merge game `from` into `into` and return the merged game. */
+export async function mergeGames(into: number, from: number): Promise<Game> {
+  return invoke<Game>("merge_games", { into, from });
+}
+
+/** Split one source off its game into a new game. */
+export async function splitGameSource(sourceId: number): Promise<Game> {
+  return invoke<Game>("split_game_source", { sourceId });
+}
+
+/** List a game's play sessions, most recent first. */
+export async function listPlaySessions(gameId: number): Promise<PlaySession[]> {
+  return invoke<PlaySession[]>("list_play_sessions", { gameId });
+}
+
+/** Log a play session; the game's playtime follows. */
+export async function logPlaySession(
+  gameId: number,
+  input: PlaySessionInput
+): Promise<PlaySession> {
+  return invoke<PlaySession>("log_play_session", { gameId, input });
+}
+
+/** Edit a play session. */
+export async function updatePlaySession(
+  id: number,
+  input: PlaySessionInput
+): Promise<PlaySession> {
+  return invoke<PlaySession>("update_play_session", { id, input });
+}
+
+/** Delete a play session. */
+export async function deletePlaySession(id: number): Promise<void> {
+  return invoke("delete_play_session", { id });
+}
+
+/**
+ * Start a game; its play session is tracked until it exits. Resolves to
+ * false when this system can't see when the game exits, so no session is
+ * recorded.
+ */
+export async function launchGame(id: number): Promise<boolean> {
+  return invoke<boolean>("launch_game", { id });
+}
+
+/** Set or clear (null) a game's launch override. */
+export async function setLaunchOverride(
+  id: number,
+  launchOverride: string | null
+): Promise<void> {
+  return invoke("set_launch_override", { id, launchOverride });
+}
+
+/** List the built-in launch strategy ids (`steam`, `exe`, ...). */
+export async function listLaunchStrategies(): Promise<string[]> {
+  return invoke<string[]>("list_launch_strategies");
+}
+
+/** Subscribe to launched games starting and exiting. */
+export async function onGameSession(
+  handler: (event: GameSessionEvent) => void
+): Promise<UnlistenFn> {
+  return listen<GameSessionEvent>("game-session", (event) =>
+    handler(event.payload)
+  );
+}
+
+/** Subscribe to games started outside the app being detected and exiting. */
+export async function onNowPlaying(
+  handler: (event: NowPlayingEvent) => void
+): Promise<UnlistenFn> {
+  return listen<NowPlayingEvent>("now-playing", (event) =>
+    handler(event.payload)
+  );
+}
+
 /** Load aggregate statistics for the stats dashboard. */
 export async function getGameStats(): Promise<GameStats> {
   return invoke<GameStats>("get_game_stats");
@@ -105,7 +321,66 @@ export async function searchRawg(query: string): Promise<RawgGame[]> {
   return invoke<RawgGame[]>("search_rawg", { query });
 }
 
-/** Trigger Steam/Epic indexing and return summary counts. */
+/** Trigger indexing for all enabled launchers and return summary counts. */
 export async function indexNow(): Promise<IndexResult> {
   return invoke<IndexResult>("index_now");
 }
+
+/** Subscribe to live progress of `indexNow`; resolves to an unsubscribe function. */
+export async function onIndexProgress(
+  handler: (progress: IndexProgress) => void
+): Promise<UnlistenFn> {
+  return listen<IndexProgress>("index-progress", (event) =>
+    handler(event.payload)
+  );
+}
+
+/** Subscribe to automatic re-indexing triggered by the launcher watcher. */
+export async function onLibraryChanged(
+  handler: (change: { source: string; result: IndexResult }) => void
+): Promise<UnlistenFn> {
+  return listen<{ source: string; result: IndexResult }>(
+    "library-changed",
+    (event) => handler(event.payload)
+  );
+}
+
+/** Dry-run all enabled indexers and list what would change. */
+export async function previewIndex(): Promise<IndexChange[]> {
+  return invoke<IndexChange[]>("preview_index");
+}
+
+/** Apply selected entries from an index preview. */
+export async function applyIndexChanges(
+  changes: IndexChange[]
+): Promise<IndexResult> {
+  return invoke<IndexResult>("apply_index_changes", { changes });
+}
+
+/** Trigger indexing for a single launcher by id. */
+export async function indexSource(source: string): Promise<IndexResult> {
+  return invoke<IndexResult>("index_source", { source });
+}
+
+/** List registered launcher indexers. */
+export async function listIndexers(): Promise<IndexerInfo[]> {
+  return invoke<IndexerInfo[]>("list_indexers");
+}
+
+/** Enable or disable a launcher indexer. */
+export async function setIndexerEnabled(
+  id: string,
+  enabled: boolean
+): Promise<void> {
+  return invoke("set_indexer_enabled", { id, enabled });
+}
+
+/** Load the current application settings. */
+export async function getSettings(): Promise<AppSettings> {
+  return invoke<AppSettings>("get_settings");
+}
+
+/** Persist and apply new application settings. */
+export async function updateSettings(settings: AppSettings): Promise<void> {
+  return invoke("update_settings", { settings });
+}